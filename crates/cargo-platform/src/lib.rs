@@ -16,6 +16,7 @@ use std::str::FromStr;
 
 mod cfg;
 mod error;
+pub mod toolchain;
 
 pub use cfg::{Cfg, CfgExpr};
 pub use error::{ParseError, ParseErrorKind};
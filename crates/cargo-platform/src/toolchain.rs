@@ -0,0 +1,120 @@
+//! Conventional names of cross-compilation tools for a target triple.
+//!
+//! Linux distributions and MinGW package cross toolchains and user-mode
+//! emulators under names derived from the target, like `aarch64-linux-gnu-gcc`
+//! or `qemu-aarch64`, and WASI programs are run by a WebAssembly runtime such
+//! as `wasmtime`. The functions here map a Rust target triple onto those
+//! names so that tools can look for them on `PATH`. They only know about
+//! the common setups; anything else yields no candidates.
+
+/// The components of a target triple that matter for tool names.
+struct Triple<'a> {
+    arch: &'a str,
+    os: &'a str,
+    env: &'a str,
+}
+
+impl<'a> Triple<'a> {
+    fn parse(triple: &'a str) -> Option<Triple<'a>> {
+        let parts: Vec<&str> = triple.split('-').collect();
+        let (arch, os, env) = match parts.as_slice() {
+            [arch, _vendor, os, env] => (*arch, *os, *env),
+            // Vendor-less triples such as `aarch64-linux-android`.
+            [arch, "linux", env] => (*arch, "linux", *env),
+            [arch, _vendor, os] => (*arch, *os, ""),
+            [arch, os] => (*arch, *os, ""),
+            _ => return None,
+        };
+        Some(Triple { arch, os, env })
+    }
+
+    /// The architecture name used by GNU toolchains.
+    fn gnu_arch(&self) -> Option<&'a str> {
+        let arch = match self.arch {
+            "aarch64" | "aarch64_be" | "loongarch64" | "mips" | "mipsel" | "mips64"
+            | "mips64el" | "powerpc" | "powerpc64" | "powerpc64le" | "s390x" | "sparc64"
+            | "x86_64" => self.arch,
+            "i586" | "i686" => "i686",
+            "riscv64gc" => "riscv64",
+            a if a.starts_with("arm") || a.starts_with("thumb") => "arm",
+            _ => return None,
+        };
+        Some(arch)
+    }
+
+    /// The architecture name used by `qemu-<arch>` user-mode emulators.
+    fn qemu_arch(&self) -> Option<&'a str> {
+        let arch = match self.arch {
+            "aarch64" | "aarch64_be" | "loongarch64" | "mips" | "mipsel" | "mips64"
+            | "mips64el" | "s390x" | "sparc64" | "x86_64" => self.arch,
+            "i586" | "i686" => "i386",
+            "powerpc" => "ppc",
+            "powerpc64" => "ppc64",
+            "powerpc64le" => "ppc64le",
+            a if a.starts_with("riscv64") => "riscv64",
+            a if a.starts_with("riscv32") => "riscv32",
+            a if a.starts_with("arm") || a.starts_with("thumb") => "arm",
+            _ => return None,
+        };
+        Some(arch)
+    }
+}
+
+/// Returns the GNU-style tool prefix for a target, if it has a conventional one.
+///
+/// For example `aarch64-unknown-linux-gnu` maps to `aarch64-linux-gnu` and
+/// `x86_64-pc-windows-gnu` maps to `x86_64-w64-mingw32`. This is the prefix of
+/// the toolchain binaries, and usually the name of the sysroot directory under
+/// `/usr` as well.
+pub fn gnu_prefix(triple: &str) -> Option<String> {
+    let t = Triple::parse(triple)?;
+    match (t.os, t.env) {
+        (
+            "linux",
+            "gnu" | "gnuabi64" | "gnueabi" | "gnueabihf" | "musl" | "musleabi" | "musleabihf",
+        ) => Some(format!("{}-linux-{}", t.gnu_arch()?, t.env)),
+        ("windows", "gnu") => match t.arch {
+            "x86_64" => Some("x86_64-w64-mingw32".to_string()),
+            "i586" | "i686" => Some("i686-w64-mingw32".to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the names of linkers that conventionally target `triple`, most
+/// preferred first.
+pub fn linkers(triple: &str) -> Vec<String> {
+    gnu_prefix(triple)
+        .map(|prefix| vec![format!("{prefix}-gcc")])
+        .unwrap_or_default()
+}
+
+/// Returns the names of programs that can conventionally run executables
+/// built for `triple` on another host, most preferred first.
+pub fn runners(triple: &str) -> Vec<String> {
+    if is_wasi(triple) {
+        return vec!["wasmtime".to_string()];
+    }
+    let Some(t) = Triple::parse(triple) else {
+        return Vec::new();
+    };
+    match t.os {
+        "linux" => t
+            .qemu_arch()
+            .map(|arch| vec![format!("qemu-{arch}")])
+            .unwrap_or_default(),
+        "windows" => vec!["wine".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+/// Returns whether `triple` is a WASI target, like `wasm32-wasip2`.
+///
+/// These don't follow the usual triple layout, for example
+/// `wasm32-wasip1-threads`, so they are checked separately.
+pub fn is_wasi(triple: &str) -> bool {
+    let mut parts = triple.split('-');
+    parts.next().map_or(false, |arch| arch.starts_with("wasm"))
+        && parts.any(|part| part.starts_with("wasi"))
+}
@@ -0,0 +1,58 @@
+use cargo_platform::toolchain::{gnu_prefix, is_wasi, linkers, runners};
+
+#[test]
+fn gnu_prefixes() {
+    assert_eq!(
+        gnu_prefix("aarch64-unknown-linux-gnu").as_deref(),
+        Some("aarch64-linux-gnu")
+    );
+    assert_eq!(
+        gnu_prefix("armv7-unknown-linux-gnueabihf").as_deref(),
+        Some("arm-linux-gnueabihf")
+    );
+    assert_eq!(
+        gnu_prefix("riscv64gc-unknown-linux-gnu").as_deref(),
+        Some("riscv64-linux-gnu")
+    );
+    assert_eq!(
+        gnu_prefix("x86_64-unknown-linux-musl").as_deref(),
+        Some("x86_64-linux-musl")
+    );
+    assert_eq!(
+        gnu_prefix("x86_64-pc-windows-gnu").as_deref(),
+        Some("x86_64-w64-mingw32")
+    );
+    assert_eq!(gnu_prefix("x86_64-pc-windows-msvc"), None);
+    assert_eq!(gnu_prefix("aarch64-apple-darwin"), None);
+    assert_eq!(gnu_prefix("wasm32-unknown-unknown"), None);
+    assert_eq!(gnu_prefix("not a triple"), None);
+}
+
+#[test]
+fn tool_names() {
+    assert_eq!(
+        linkers("aarch64-unknown-linux-gnu"),
+        ["aarch64-linux-gnu-gcc"]
+    );
+    assert_eq!(runners("aarch64-unknown-linux-gnu"), ["qemu-aarch64"]);
+    assert_eq!(runners("powerpc64le-unknown-linux-gnu"), ["qemu-ppc64le"]);
+    assert_eq!(runners("i686-unknown-linux-gnu"), ["qemu-i386"]);
+    assert_eq!(runners("thumbv7neon-unknown-linux-gnueabihf"), ["qemu-arm"]);
+    assert_eq!(runners("x86_64-pc-windows-gnu"), ["wine"]);
+    assert_eq!(runners("wasm32-wasip2"), ["wasmtime"]);
+    assert!(linkers("wasm32-wasip2").is_empty());
+    assert!(linkers("aarch64-apple-darwin").is_empty());
+    assert!(runners("aarch64-apple-darwin").is_empty());
+}
+
+#[test]
+fn wasi_targets() {
+    assert!(is_wasi("wasm32-wasi"));
+    assert!(is_wasi("wasm32-wasip1"));
+    assert!(is_wasi("wasm32-wasip1-threads"));
+    assert!(is_wasi("wasm32-wasip2"));
+    assert!(is_wasi("wasm64-unknown-wasi"));
+    assert!(!is_wasi("wasm32-unknown-unknown"));
+    assert!(!is_wasi("wasm32-unknown-emscripten"));
+    assert!(!is_wasi("x86_64-unknown-linux-gnu"));
+}
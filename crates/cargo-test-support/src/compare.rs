@@ -330,6 +330,9 @@ static E2E_LITERAL_REDACTIONS: &[(&str, &str)] = &[
     ("[BLOCKING]", "    Blocking"),
     ("[GENERATED]", "   Generated"),
     ("[OPENING]", "     Opening"),
+    ("[MERGED]", "      Merged"),
+    ("[EXPORTED]", "    Exported"),
+    ("[IMPORTED]", "    Imported"),
 ];
 
 /// Normalizes the output so that it can be compared against the expected value.
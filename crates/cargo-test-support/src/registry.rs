@@ -179,6 +179,11 @@ pub struct RegistryBuilder {
     delayed_index_update: usize,
     /// Credential provider in configuration
     credential_provider: Option<String>,
+    /// If set, config.json includes 'changes', served by the http index.
+    index_changes: bool,
+    /// If set, config.json includes 'signing-key', and the http index signs
+    /// index files with this secret key.
+    index_signing_key: Option<String>,
 }
 
 /// A local registry fixture
@@ -251,6 +256,8 @@ impl RegistryBuilder {
             not_found_handler: Box::new(not_found),
             delayed_index_update: 0,
             credential_provider: None,
+            index_changes: false,
+            index_signing_key: None,
         }
     }
 
@@ -345,6 +352,25 @@ impl RegistryBuilder {
         self
     }
 
+    /// Serve an index change log, built from the history of the index.
+    ///
+    /// Requires [`RegistryBuilder::http_index`].
+    #[must_use]
+    pub fn index_changes(mut self) -> Self {
+        self.index_changes = true;
+        self
+    }
+
+    /// Sign the index files served by the http index with the given secret
+    /// key, in PASERK format.
+    ///
+    /// Requires [`RegistryBuilder::http_index`].
+    #[must_use]
+    pub fn index_signing(mut self, secret_key: &str) -> Self {
+        self.index_signing_key = Some(secret_key.to_string());
+        self
+    }
+
     /// The credential provider to configure for this registry.
     #[must_use]
     pub fn credential_provider(mut self, provider: &[&str]) -> Self {
@@ -385,6 +411,7 @@ impl RegistryBuilder {
                 self.custom_responders,
                 self.not_found_handler,
                 self.delayed_index_update,
+                self.index_signing_key.clone(),
             );
             let index_url = if self.http_index {
                 server.index_url()
@@ -522,11 +549,30 @@ impl RegistryBuilder {
         } else {
             String::new()
         };
+        let changes = match &registry.server {
+            Some(server) if self.index_changes => {
+                format!(r#","changes":"{}""#, server.changes_url())
+            }
+            _ => String::new(),
+        };
+        let signing_key = if let Some(secret_key) = &self.index_signing_key {
+            let secret: AsymmetricSecretKey<pasetors::version3::V3> =
+                secret_key.as_str().try_into().unwrap();
+            let public: AsymmetricPublicKey<pasetors::version3::V3> = (&secret).try_into().unwrap();
+            let mut paserk_pub_key = String::new();
+            FormatAsPaserk::fmt(&public, &mut paserk_pub_key).unwrap();
+            format!(r#","signing-key":"{paserk_pub_key}""#)
+        } else {
+            String::new()
+        };
         // Initialize a new registry.
         repo(&registry.path)
             .file(
                 "config.json",
-                &format!(r#"{{"dl":"{}"{api}{auth}}}"#, registry.dl_url),
+                &format!(
+                    r#"{{"dl":"{}"{api}{auth}{changes}{signing_key}}}"#,
+                    registry.dl_url
+                ),
             )
             .build();
         fs::create_dir_all(api_path.join("api/v1/crates")).unwrap();
@@ -576,6 +622,7 @@ pub struct Package {
     proc_macro: bool,
     links: Option<String>,
     rust_version: Option<String>,
+    pubtime: Option<String>,
     cargo_features: Vec<String>,
     v: Option<u32>,
 }
@@ -656,6 +703,10 @@ impl HttpServerHandle {
         Url::parse(&format!("http://{}/dl", self.addr.to_string())).unwrap()
     }
 
+    pub fn changes_url(&self) -> Url {
+        Url::parse(&format!("http://{}/changes", self.addr.to_string())).unwrap()
+    }
+
     fn stop(&self) {
         if let Ok(mut stream) = TcpStream::connect(self.addr) {
             // shutdown the server
@@ -713,6 +764,7 @@ pub struct HttpServer {
     custom_responders: HashMap<String, RequestCallback>,
     not_found_handler: RequestCallback,
     delayed_index_update: usize,
+    index_signing_key: Option<String>,
 }
 
 /// A helper struct that collects the arguments for [`HttpServer::check_authorized`].
@@ -734,6 +786,7 @@ impl HttpServer {
         custom_responders: HashMap<String, RequestCallback>,
         not_found_handler: RequestCallback,
         delayed_index_update: usize,
+        index_signing_key: Option<String>,
     ) -> HttpServerHandle {
         let listener = TcpListener::bind("127.0.0.1:0").unwrap();
         let addr = listener.local_addr().unwrap();
@@ -748,6 +801,7 @@ impl HttpServer {
             custom_responders,
             not_found_handler,
             delayed_index_update,
+            index_signing_key,
         };
         let handle = Some(thread::spawn(move || server.start()));
         HttpServerHandle { addr, handle }
@@ -972,6 +1026,13 @@ impl HttpServer {
                     self.index(&req)
                 }
             }
+            ("get", ["changes"]) => {
+                if !self.check_authorized(req, None) {
+                    self.unauthorized(req)
+                } else {
+                    self.changes(&req)
+                }
+            }
             ("get", ["dl", ..]) => {
                 if !self.check_authorized(req, None) {
                     self.unauthorized(req)
@@ -1074,9 +1135,8 @@ impl HttpServer {
 
     /// Serve the registry index
     pub fn index(&self, req: &Request) -> Response {
-        let file = self
-            .registry_path
-            .join(req.url.path().strip_prefix("/index/").unwrap());
+        let path = req.url.path().strip_prefix("/index/").unwrap();
+        let file = self.registry_path.join(path);
         if !file.exists() {
             return self.not_found(req);
         } else {
@@ -1111,18 +1171,60 @@ impl HttpServer {
                     headers: vec![],
                 };
             } else {
+                let mut headers = vec![
+                    format!("ETag: \"{}\"", etag),
+                    format!("Last-Modified: {}", last_modified),
+                ];
+                if let Some(secret_key) = &self.index_signing_key {
+                    if path != "config.json" {
+                        let expires = OffsetDateTime::now_utc() + Duration::hours(1);
+                        let signature = sign_index_file(secret_key, path, &data, expires);
+                        headers.push(format!("Cargo-Index-Signature: {signature}"));
+                    }
+                }
                 return Response {
                     body: data,
                     code: 200,
-                    headers: vec![
-                        format!("ETag: \"{}\"", etag),
-                        format!("Last-Modified: {}", last_modified),
-                    ],
+                    headers,
                 };
             }
         }
     }
 
+    /// Serve the index change log
+    ///
+    /// The token is the commit of the index, and the changed crates are the
+    /// files changed since the commit given as `since`.
+    pub fn changes(&self, req: &Request) -> Response {
+        let repo = t!(git2::Repository::open(&self.registry_path));
+        let head = t!(repo.head().and_then(|head| head.peel_to_commit()));
+        let since = req
+            .url
+            .query_pairs()
+            .find(|(key, _)| key == "since")
+            .and_then(|(_, since)| git2::Oid::from_str(&since).ok())
+            .and_then(|since| repo.find_commit(since).ok());
+        let crates = since.map(|since| {
+            let diff =
+                t!(repo.diff_tree_to_tree(Some(&t!(since.tree())), Some(&t!(head.tree())), None));
+            diff.deltas()
+                .filter_map(|delta| delta.new_file().path())
+                .filter(|path| *path != Path::new("config.json"))
+                .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        });
+        Response {
+            code: 200,
+            headers: vec![],
+            body: serde_json::json!({
+                "token": head.id().to_string(),
+                "crates": crates,
+            })
+            .to_string()
+            .into_bytes(),
+        }
+    }
+
     pub fn check_authorized_publish(&self, req: &Request) -> Response {
         if let Some(body) = &req.body {
             // Mimic the publish behavior for local registries by writing out the request
@@ -1229,6 +1331,7 @@ fn save_new_crate(
         new_crate.links,
         new_crate.rust_version.as_deref(),
         None,
+        None,
     );
 
     write_to_index(registry_path, &new_crate.name, line, false);
@@ -1257,6 +1360,7 @@ impl Package {
             proc_macro: false,
             links: None,
             rust_version: None,
+            pubtime: None,
             cargo_features: Vec::new(),
             v: None,
         }
@@ -1420,6 +1524,14 @@ impl Package {
         self
     }
 
+    /// Sets the time the package was published, as recorded in the index.
+    ///
+    /// The time is in RFC 3339 format, like `2024-03-01T12:00:00Z`.
+    pub fn pubtime(&mut self, pubtime: &str) -> &mut Package {
+        self.pubtime = Some(pubtime.into());
+        self
+    }
+
     /// Causes the JSON line emitted in the index to be invalid, presumably
     /// causing Cargo to skip over this version.
     pub fn invalid_json(&mut self, invalid: bool) -> &mut Package {
@@ -1510,6 +1622,7 @@ impl Package {
             self.yanked,
             self.links.clone(),
             self.rust_version.as_deref(),
+            self.pubtime.as_deref(),
             self.v,
         );
 
@@ -1735,6 +1848,24 @@ impl Package {
 }
 
 /// Generate a checksum
+/// Signs the index file at `path` with the secret key of a registry, in
+/// PASERK format, as a registry signing its index would.
+pub fn sign_index_file(
+    secret_key: &str,
+    path: &str,
+    data: &[u8],
+    expires: OffsetDateTime,
+) -> String {
+    let secret: AsymmetricSecretKey<pasetors::version3::V3> = secret_key.try_into().unwrap();
+    let message = serde_json::json!({
+        "path": path,
+        "cksum": cksum(data),
+        "expires": expires.format(&Rfc3339).unwrap(),
+    });
+    pasetors::version3::PublicToken::sign(&secret, message.to_string().as_bytes(), None, None)
+        .unwrap()
+}
+
 pub fn cksum(s: &[u8]) -> String {
     Sha256::new().update(s).finish_hex()
 }
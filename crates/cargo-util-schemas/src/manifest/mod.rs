@@ -136,6 +136,9 @@ pub struct TomlWorkspace {
     pub package: Option<InheritablePackage>,
     pub dependencies: Option<BTreeMap<PackageName, TomlDependency>>,
     pub lints: Option<TomlLints>,
+
+    // Requirements applied to packages anywhere in the dependency graph.
+    pub constraints: Option<BTreeMap<PackageName, String>>,
 }
 
 /// A group of fields that are inheritable by members of the workspace
@@ -161,6 +164,7 @@ pub struct InheritablePackage {
     pub include: Option<Vec<String>>,
     #[cfg_attr(feature = "unstable-schema", schemars(with = "Option<String>"))]
     pub rust_version: Option<RustVersion>,
+    pub target_specs: Option<Vec<String>>,
 }
 
 /// Represents the `package`/`project` sections of a `Cargo.toml`.
@@ -184,6 +188,7 @@ pub struct TomlPackage {
     pub metabuild: Option<StringOrVec>,
     pub default_target: Option<String>,
     pub forced_target: Option<String>,
+    pub target_specs: Option<InheritableVecString>,
     pub links: Option<String>,
     pub exclude: Option<InheritableVecString>,
     pub include: Option<InheritableVecString>,
@@ -234,6 +239,7 @@ impl TomlPackage {
             metabuild: None,
             default_target: None,
             forced_target: None,
+            target_specs: None,
             links: None,
             exclude: None,
             include: None,
@@ -297,6 +303,13 @@ impl TomlPackage {
         self.include.as_ref().map(|v| v.normalized()).transpose()
     }
 
+    pub fn normalized_target_specs(&self) -> Result<Option<&Vec<String>>, UnresolvedError> {
+        self.target_specs
+            .as_ref()
+            .map(|v| v.normalized())
+            .transpose()
+    }
+
     pub fn normalized_publish(&self) -> Result<Option<&VecStringOrBool>, UnresolvedError> {
         self.publish.as_ref().map(|v| v.normalized()).transpose()
     }
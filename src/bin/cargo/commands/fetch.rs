@@ -1,11 +1,27 @@
 use crate::command_prelude::*;
 
+use anyhow::anyhow;
 use cargo::ops;
 use cargo::ops::FetchOptions;
 
 pub fn cli() -> Command {
     subcommand("fetch")
         .about("Fetch dependencies of a package from the network")
+        .arg(
+            opt(
+                "export-index",
+                "Export the locked registry packages to a directory (unstable)",
+            )
+            .value_name("DIR"),
+        )
+        .arg(
+            opt(
+                "import-index",
+                "Import the locked registry packages from a directory (unstable)",
+            )
+            .value_name("DIR")
+            .conflicts_with("export-index"),
+        )
         .arg_silent_suggestion()
         .arg_target_triple("Fetch dependencies for the target triple")
         .arg_manifest_path()
@@ -16,11 +32,27 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+    let export_index = args.value_of_path("export-index", gctx);
+    let import_index = args.value_of_path("import-index", gctx);
+    for (flag, set) in [
+        ("--export-index", export_index.is_some()),
+        ("--import-index", import_index.is_some()),
+    ] {
+        if set && !gctx.cli_unstable().index_snapshot {
+            return Err(anyhow!(
+                "the `{flag}` flag is unstable, pass `-Z index-snapshot` to enable it"
+            )
+            .into());
+        }
+    }
+
     let ws = args.workspace(gctx)?;
 
     let opts = FetchOptions {
         gctx,
         targets: args.targets()?,
+        export_index,
+        import_index,
     };
     let _ = ops::fetch(&ws, &opts)?;
     Ok(())
@@ -76,7 +76,7 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     // Can't use workspace() to avoid using -Zavoid-dev-deps (if passed)
     let mut ws = Workspace::new(&root_manifest, gctx)?;
     ws.set_resolve_honors_rust_version(args.honor_rust_version());
-    let lockfile_path = args.lockfile_path(gctx)?;
+    let lockfile_path = args.workspace_lockfile_path(gctx)?;
     ws.set_requested_lockfile_path(lockfile_path.clone());
 
     let mut opts = args.compile_options(gctx, mode, Some(&ws), ProfileChecking::LegacyTestOnly)?;
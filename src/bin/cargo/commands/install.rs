@@ -5,6 +5,7 @@ use anyhow::bail;
 use anyhow::format_err;
 use cargo::core::{GitReference, SourceId, Workspace};
 use cargo::ops;
+use cargo::util::context::CargoResolverConfig;
 use cargo::util::IntoUrl;
 use cargo::util::VersionExt;
 use cargo::CargoResult;
@@ -71,6 +72,10 @@ pub fn cli() -> Command {
         .arg(flag("force", "Force overwriting existing crates or binaries").short('f'))
         .arg_dry_run("Perform all checks without installing (unstable)")
         .arg(flag("no-track", "Do not save tracking information"))
+        .arg(flag(
+            "debug-symbols",
+            "Also install split debuginfo files (unstable)",
+        ))
         .arg(flag(
             "list",
             "List all installed packages and their versions",
@@ -205,11 +210,27 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     if args.dry_run() {
         gctx.cli_unstable().fail_if_stable_opt("--dry-run", 11123)?;
     }
+    if args.flag("debug-symbols") && !gctx.cli_unstable().debug_symbols {
+        return Err(anyhow!(
+            "the `--debug-symbols` flag is unstable, pass `-Z debug-symbols` to enable it"
+        )
+        .into());
+    }
 
     let requested_lockfile_path = args.lockfile_path(gctx)?;
     // 14421: lockfile path should imply --locked on running `install`
     if requested_lockfile_path.is_some() {
         gctx.set_locked(true);
+    } else if !args.flag("list")
+        && gctx
+            .get::<CargoResolverConfig>("resolver")?
+            .lockfile_path
+            .is_some()
+    {
+        gctx.shell().warn(
+            "`cargo install` ignores the `resolver.lockfile-path` config, \
+            pass `--lockfile-path` to install with that lockfile",
+        )?;
     }
 
     if args.flag("list") {
@@ -224,6 +245,7 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
             &compile_opts,
             args.flag("force"),
             args.flag("no-track"),
+            args.flag("debug-symbols"),
             args.dry_run(),
             requested_lockfile_path.as_deref(),
         )?;
@@ -0,0 +1,54 @@
+use crate::command_prelude::*;
+
+use anyhow::anyhow;
+use cargo::ops::{self, MergeLockfileOptions};
+
+pub fn cli() -> Command {
+    subcommand("merge-lockfile")
+        .about("Merge two diverged lockfiles, for use as a git merge driver (unstable)")
+        .arg(
+            Arg::new("base")
+                .value_name("BASE")
+                .help("The lockfile of the common ancestor")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .required(true),
+        )
+        .arg(
+            Arg::new("ours")
+                .value_name("OURS")
+                .help("Our lockfile, overwritten with the merge result")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .required(true),
+        )
+        .arg(
+            Arg::new("theirs")
+                .value_name("THEIRS")
+                .help("Their lockfile")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .required(true),
+        )
+        .arg_silent_suggestion()
+        .arg_manifest_path()
+        .after_help(color_print::cstr!(
+            "Run `<cyan,bold>cargo help merge-lockfile</>` for more detailed information.\n"
+        ))
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+    if !gctx.cli_unstable().merge_lockfile {
+        return Err(anyhow!(
+            "the `cargo merge-lockfile` command is unstable, pass `-Z merge-lockfile` to enable it"
+        )
+        .into());
+    }
+
+    let ws = args.workspace(gctx)?;
+    let path = |name| args.get_one::<std::path::PathBuf>(name).unwrap().as_path();
+    let opts = MergeLockfileOptions {
+        base: path("base"),
+        ours: path("ours"),
+        theirs: path("theirs"),
+    };
+    ops::merge_lockfile(&ws, &opts)?;
+    Ok(())
+}
@@ -34,7 +34,9 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
-    let ws = args.workspace(gctx)?;
+    let mut ws = args.workspace(gctx)?;
+    // The output of `cargo metadata` is always JSON.
+    ws.set_resolve_errors_as_json(true);
 
     let version = match args.get_one::<String>("format-version") {
         None => {
@@ -20,6 +20,7 @@ pub fn builtin() -> Vec<Command> {
         locate_project::cli(),
         login::cli(),
         logout::cli(),
+        merge_lockfile::cli(),
         metadata::cli(),
         new::cli(),
         owner::cli(),
@@ -66,6 +67,7 @@ pub fn builtin_exec(cmd: &str) -> Option<Exec> {
         "locate-project" => locate_project::exec,
         "login" => login::exec,
         "logout" => logout::exec,
+        "merge-lockfile" => merge_lockfile::exec,
         "metadata" => metadata::exec,
         "new" => new::exec,
         "owner" => owner::exec,
@@ -110,6 +112,7 @@ pub mod install;
 pub mod locate_project;
 pub mod login;
 pub mod logout;
+pub mod merge_lockfile;
 pub mod metadata;
 pub mod new;
 pub mod owner;
@@ -66,6 +66,20 @@ pub fn cli() -> Command {
             .short('d')
             .alias("duplicate"),
         )
+        .arg(
+            opt(
+                "why-feature",
+                "Show the chains that enable a `pkg/feature` (unstable)",
+            )
+            .value_name("FEATURE")
+            .conflicts_with_all(["invert", "duplicates"]),
+        )
+        .arg(
+            opt("message-format", "Why-feature format (unstable)")
+                .value_name("FMT")
+                .value_parser(["human", "json"])
+                .requires("why-feature"),
+        )
         .arg(
             opt("charset", "Character set to use in output")
                 .value_name("CHARSET")
@@ -157,7 +171,24 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     };
     let target = tree::Target::from_cli(targets);
 
-    let (edge_kinds, no_proc_macro) = parse_edge_kinds(gctx, args)?;
+    let why_feature = args.get_one::<String>("why-feature").cloned();
+    if why_feature.is_some() {
+        gctx.cli_unstable().fail_if_stable_opt_custom_z(
+            "--why-feature",
+            None,
+            "why-feature",
+            gctx.cli_unstable().why_feature,
+        )?;
+    }
+    let why_feature_json = args
+        .get_one::<String>("message-format")
+        .is_some_and(|fmt| fmt == "json");
+
+    let (mut edge_kinds, no_proc_macro) = parse_edge_kinds(gctx, args)?;
+    if why_feature.is_some() {
+        // Chains are computed from the feature graph.
+        edge_kinds.insert(EdgeKind::Feature);
+    }
     let graph_features = edge_kinds.contains(&EdgeKind::Feature);
 
     let pkgs_to_prune = args._values_of("prune");
@@ -224,6 +255,8 @@ subtree of the package given to -p.\n\
         graph_features,
         max_display_depth: args.value_of_u32("depth")?.unwrap_or(u32::MAX),
         no_proc_macro,
+        why_feature,
+        why_feature_json,
     };
 
     if opts.graph_features && opts.duplicates {
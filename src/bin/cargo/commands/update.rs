@@ -1,8 +1,11 @@
 use crate::command_prelude::*;
 
 use anyhow::anyhow;
-use cargo::ops::{self, UpdateOptions};
+use cargo::ops::{self, UpdateOptions, UpdateReportFormat};
 use cargo::util::print_available_packages;
+use cargo::CargoResult;
+use time::format_description::well_known::{Iso8601, Rfc3339};
+use time::{Date, OffsetDateTime};
 
 pub fn cli() -> Command {
     subcommand("update")
@@ -25,6 +28,27 @@ pub fn cli() -> Command {
                 .group("package-group"),
         )
         .arg_dry_run("Don't actually write the lockfile")
+        .arg(
+            opt("report", "Report changes by SemVer (unstable)")
+                .value_name("FMT")
+                .value_parser(["human", "json"])
+                .conflicts_with("breaking"),
+        )
+        .arg(
+            opt(
+                "advisory-fix",
+                "Update away from the vulnerable versions in FILE (unstable)",
+            )
+            .value_name("FILE")
+            .value_parser(clap::value_parser!(std::path::PathBuf))
+            .conflicts_with_all([
+                "package-group",
+                "precise",
+                "recursive",
+                "breaking",
+                "workspace",
+            ]),
+        )
         .arg(
             flag(
                 "recursive",
@@ -38,6 +62,13 @@ pub fn cli() -> Command {
                 .value_name("PRECISE")
                 .requires("package-group"),
         )
+        .arg(
+            opt(
+                "publish-before",
+                "Only use versions published before TIME (unstable)",
+            )
+            .value_name("TIME"),
+        )
         .arg(
             flag(
                 "breaking",
@@ -45,6 +76,13 @@ pub fn cli() -> Command {
             )
             .short('b'),
         )
+        .arg(
+            flag(
+                "minimal-versions",
+                "Write the minimal versions allowed to Cargo.minimal.lock",
+            )
+            .conflicts_with_all(["breaking", "advisory-fix"]),
+        )
         .arg_silent_suggestion()
         .arg(
             flag("workspace", "Only update the workspace packages")
@@ -82,12 +120,48 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
         }
     }
 
+    let report = match args.get_one::<String>("report").map(String::as_str) {
+        None => None,
+        Some("json") => Some(UpdateReportFormat::Json),
+        Some(_) => Some(UpdateReportFormat::Human),
+    };
+    if report.is_some() && !gctx.cli_unstable().update_report {
+        return Err(anyhow!(
+            "the `--report` flag is unstable, pass `-Z update-report` to enable it"
+        )
+        .into());
+    }
+
+    let advisories = args.get_one::<std::path::PathBuf>("advisory-fix");
+    if advisories.is_some() && !gctx.cli_unstable().advisory_fix {
+        return Err(anyhow!(
+            "the `--advisory-fix` flag is unstable, pass `-Z advisory-fix` to enable it"
+        )
+        .into());
+    }
+
+    if let Some(time) = args.get_one::<String>("publish-before") {
+        if !gctx.cli_unstable().publish_time {
+            return Err(anyhow!(
+                "the `--publish-before` flag is unstable, pass `-Z publish-time` to enable it"
+            )
+            .into());
+        }
+        ws.set_resolve_publish_time(parse_publish_time(time)?);
+    }
+
+    if args.flag("minimal-versions") {
+        ws.set_resolve_minimal_versions(true);
+    }
+
     let update_opts = UpdateOptions {
         recursive: args.flag("recursive"),
         precise: args.get_one::<String>("precise").map(String::as_str),
         to_update,
         dry_run: args.dry_run(),
         workspace: args.flag("workspace"),
+        report,
+        advisories: advisories.map(|p| p.as_path()),
         gctx,
     };
 
@@ -111,3 +185,18 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
 
     Ok(())
 }
+
+/// Parses a `--publish-before` value, either an RFC 3339 time or a date,
+/// which means midnight UTC.
+fn parse_publish_time(time: &str) -> CargoResult<OffsetDateTime> {
+    if let Ok(time) = OffsetDateTime::parse(time, &Rfc3339) {
+        return Ok(time);
+    }
+    let date = Date::parse(time, &Iso8601::DATE).map_err(|_| {
+        anyhow!(
+            "invalid value `{time}` for `--publish-before`, \
+             expected a date like `2024-03-01` or an RFC 3339 time like `2024-03-01T12:00:00Z`"
+        )
+    })?;
+    Ok(date.midnight().assume_utc())
+}
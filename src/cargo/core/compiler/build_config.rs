@@ -19,6 +19,12 @@ pub struct BuildConfig {
     pub jobs: u32,
     /// Do not abort the build as soon as there is an error.
     pub keep_going: bool,
+    /// Build every requested kind to completion and report the outcome and
+    /// artifacts of each one separately (`-Zmultitarget-summary`).
+    ///
+    /// Only set when more than one `--target` is requested, and implies
+    /// `keep_going`.
+    pub multitarget_summary: bool,
     /// Build profile
     pub requested_profile: InternedString,
     /// The mode we are compiling in.
@@ -99,10 +105,14 @@ impl BuildConfig {
             },
         };
 
+        let multitarget_summary =
+            gctx.cli_unstable().multitarget_summary && requested_kinds.len() > 1;
+
         Ok(BuildConfig {
             requested_kinds,
             jobs,
-            keep_going,
+            keep_going: keep_going || multitarget_summary,
+            multitarget_summary,
             requested_profile: InternedString::new("dev"),
             mode,
             message_format: MessageFormat::Human,
@@ -534,6 +534,14 @@ impl<'a, 'gctx: 'a> CompilationFiles<'a, 'gctx> {
                 None
             } else {
                 self.export_dir.as_ref().and_then(|export_dir| {
+                    // Keep the artifacts of each target apart when several
+                    // are built at once, as they usually share file names.
+                    let export_dir = match unit.kind {
+                        CompileKind::Target(target) if bcx.build_config.multitarget_summary => {
+                            export_dir.join(target.short_name())
+                        }
+                        _ => export_dir.clone(),
+                    };
                     hardlink
                         .as_ref()
                         .map(|hardlink| export_dir.join(hardlink.file_name().unwrap()))
@@ -309,7 +309,21 @@ impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
 
     fn collect_tests_and_executables(&mut self, unit: &Unit) -> CargoResult<()> {
         for output in self.outputs(unit)?.iter() {
-            if output.flavor == FileFlavor::DebugInfo || output.flavor == FileFlavor::Auxiliary {
+            if output.flavor == FileFlavor::DebugInfo {
+                // Debug files are only produced when debuginfo is on and the
+                // target splits it out, so only track the ones that exist.
+                let bindst = output.bin_dst();
+                if unit.mode == CompileMode::Build
+                    && (unit.target.is_executable() || unit.target.is_cdylib())
+                    && bindst.exists()
+                {
+                    self.compilation
+                        .debuginfo
+                        .push(self.unit_output(unit, bindst));
+                }
+                continue;
+            }
+            if output.flavor == FileFlavor::Auxiliary {
                 continue;
             }
 
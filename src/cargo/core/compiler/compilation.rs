@@ -1,16 +1,17 @@
 //! Type definitions for the result of a compilation.
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ffi::{OsStr, OsString};
 use std::path::PathBuf;
 
-use cargo_platform::CfgExpr;
+use cargo_platform::{Cfg, CfgExpr};
 use cargo_util::{paths, ProcessBuilder};
 
 use crate::core::compiler::apply_env_config;
 use crate::core::compiler::BuildContext;
 use crate::core::compiler::{CompileKind, Metadata, Unit};
-use crate::core::Package;
+use crate::core::{Dependency, Package};
+use crate::util::context::DiscoveredToolchain;
 use crate::util::{context, CargoResult, GlobalContext};
 
 /// Represents the kind of process we are creating.
@@ -75,6 +76,10 @@ pub struct Compilation<'gctx> {
     /// An array of all cdylibs created.
     pub cdylibs: Vec<UnitOutput>,
 
+    /// An array of all split debuginfo files (`.dwp`, `.pdb`, `.dSYM`, ...)
+    /// uplifted alongside [`Compilation::binaries`] and [`Compilation::cdylibs`].
+    pub debuginfo: Vec<UnitOutput>,
+
     /// The crate names of the root units specified on the command-line.
     pub root_crate_names: Vec<String>,
 
@@ -109,6 +114,9 @@ pub struct Compilation<'gctx> {
     /// The target host triple.
     pub host: String,
 
+    /// Dependencies reported by the `unused_dependencies` lint.
+    pub unused_dependencies: Vec<(Package, Dependency)>,
+
     gctx: &'gctx GlobalContext,
 
     /// Rustc process to be used by default
@@ -120,6 +128,9 @@ pub struct Compilation<'gctx> {
     primary_rustc_process: Option<ProcessBuilder>,
 
     target_runners: HashMap<CompileKind, Option<(PathBuf, Vec<String>)>>,
+    /// Kinds targeting WASI whose runner is a WebAssembly runtime discovered
+    /// on `PATH`, see [`Compilation::target_process`].
+    wasi_runtimes: HashSet<CompileKind>,
     /// The linker to use for each host or target.
     target_linkers: HashMap<CompileKind, Option<PathBuf>>,
 }
@@ -139,6 +150,27 @@ impl<'gctx> Compilation<'gctx> {
             }
         }
 
+        let mut target_runners = HashMap::new();
+        let mut wasi_runtimes = HashSet::new();
+        for kind in bcx
+            .build_config
+            .requested_kinds
+            .iter()
+            .chain(Some(&CompileKind::Host))
+        {
+            let runner = match target_runner(bcx, *kind)? {
+                Some(runner) => Some(runner),
+                None => {
+                    let runner = discovered_toolchain(bcx, *kind).runner;
+                    if runner.is_some() && is_wasi(bcx, *kind) {
+                        wasi_runtimes.insert(*kind);
+                    }
+                    runner
+                }
+            };
+            target_runners.insert(*kind, runner);
+        }
+
         Ok(Compilation {
             native_dirs: BTreeSet::new(),
             root_output: HashMap::new(),
@@ -147,21 +179,18 @@ impl<'gctx> Compilation<'gctx> {
             tests: Vec::new(),
             binaries: Vec::new(),
             cdylibs: Vec::new(),
+            debuginfo: Vec::new(),
             root_crate_names: Vec::new(),
             extra_env: HashMap::new(),
             to_doc_test: Vec::new(),
             gctx: bcx.gctx,
             host: bcx.host_triple().to_string(),
+            unused_dependencies: Vec::new(),
             rustc_process: rustc,
             rustc_workspace_wrapper_process,
             primary_rustc_process,
-            target_runners: bcx
-                .build_config
-                .requested_kinds
-                .iter()
-                .chain(Some(&CompileKind::Host))
-                .map(|kind| Ok((*kind, target_runner(bcx, *kind)?)))
-                .collect::<CargoResult<HashMap<_, _>>>()?,
+            target_runners,
+            wasi_runtimes,
             target_linkers: bcx
                 .build_config
                 .requested_kinds
@@ -245,6 +274,18 @@ impl<'gctx> Compilation<'gctx> {
         self.target_linkers.get(&kind).and_then(|x| x.clone())
     }
 
+    /// Returns the tests, binaries and cdylibs that were built for `kind`.
+    ///
+    /// This is how results are told apart when several `--target` flags
+    /// were passed.
+    pub fn outputs_for_kind(&self, kind: CompileKind) -> impl Iterator<Item = &UnitOutput> {
+        self.tests
+            .iter()
+            .chain(&self.binaries)
+            .chain(&self.cdylibs)
+            .filter(move |output| output.unit.kind == kind)
+    }
+
     /// Returns a [`ProcessBuilder`] appropriate for running a process for the
     /// target platform. This is typically used for `cargo run` and `cargo
     /// test`.
@@ -270,6 +311,29 @@ impl<'gctx> Compilation<'gctx> {
         let tool_kind = ToolKind::TargetProcess;
         let mut builder = self.fill_env(builder, pkg, script_meta, kind, tool_kind)?;
 
+        if self.wasi_runtimes.contains(&kind) {
+            // WASI programs only see the directories and environment variables
+            // the runtime grants them. Give them what a native program would
+            // see: the working directory, the package root for test fixtures
+            // found through `CARGO_MANIFEST_DIR`, and the environment set
+            // above. The module itself stays the last argument so that any
+            // further arguments are passed to it.
+            let mut args: Vec<OsString> = builder.get_args().cloned().collect();
+            let module = args.pop().unwrap();
+            args.push("--dir=.".into());
+            let mut root = OsString::from("--dir=");
+            root.push(pkg.root());
+            args.push(root);
+            for (key, value) in builder.get_envs() {
+                if value.is_some() {
+                    args.push("--env".into());
+                    args.push(key.into());
+                }
+            }
+            args.push(module);
+            builder.args_replace(&args);
+        }
+
         if let Some(client) = self.gctx.jobserver_from_env() {
             builder.inherit_jobserver(client);
         }
@@ -448,6 +512,7 @@ fn get_sysroot_target_libdir(
         .collect()
 }
 
+/// Gets the user-specified runner for a particular host or target from the configuration.
 fn target_runner(
     bcx: &BuildContext<'_, '_>,
     kind: CompileKind,
@@ -482,12 +547,14 @@ fn target_runner(
             runner.definition
         );
     }
-    Ok(matching_runner.map(|(_k, runner)| {
-        (
+    if let Some((_k, runner)) = matching_runner {
+        return Ok(Some((
             runner.val.path.clone().resolve_program(bcx.gctx),
             runner.val.args.clone(),
-        )
-    }))
+        )));
+    }
+
+    Ok(None)
 }
 
 /// Gets the user-specified linker for a particular host or target from the configuration.
@@ -523,5 +590,29 @@ fn target_linker(bcx: &BuildContext<'_, '_>, kind: CompileKind) -> CargoResult<O
             linker.definition
         );
     }
-    Ok(matching_linker.map(|(_k, linker)| linker.val.clone().resolve_program(bcx.gctx)))
+    if let Some((_k, linker)) = matching_linker {
+        return Ok(Some(linker.val.clone().resolve_program(bcx.gctx)));
+    }
+
+    Ok(discovered_toolchain(bcx, kind).linker)
+}
+
+/// Whether `kind` targets WASI, according to `rustc --print=cfg`.
+fn is_wasi(bcx: &BuildContext<'_, '_>, kind: CompileKind) -> bool {
+    bcx.target_data
+        .cfg(kind)
+        .iter()
+        .any(|cfg| matches!(cfg, Cfg::KeyPair(k, v) if k == "target_os" && v == "wasi"))
+}
+
+/// Gets the linker and runner found on `PATH` for a cross-compilation target.
+///
+/// Nothing is discovered for the host, which needs neither.
+fn discovered_toolchain(bcx: &BuildContext<'_, '_>, kind: CompileKind) -> DiscoveredToolchain {
+    match kind {
+        CompileKind::Target(target) if target.short_name() != bcx.host_triple().as_str() => {
+            bcx.gctx.discovered_toolchain(target.short_name())
+        }
+        _ => DiscoveredToolchain::default(),
+    }
 }
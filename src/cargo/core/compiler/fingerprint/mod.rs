@@ -390,7 +390,9 @@ use crate::util::{internal, path_args, StableHasher};
 use crate::{GlobalContext, CARGO_ENV};
 
 use super::custom_build::BuildDeps;
-use super::{BuildContext, BuildRunner, FileFlavor, Job, Unit, Work};
+use super::{
+    unused_dependencies, unused_features, BuildContext, BuildRunner, FileFlavor, Job, Unit, Work,
+};
 
 pub use self::dep_info::parse_dep_info;
 pub use self::dep_info::parse_rustc_dep_info;
@@ -1535,6 +1537,15 @@ fn calculate_normal(
     if let Some(allow_features) = &build_runner.bcx.gctx.cli_unstable().allow_features {
         allow_features.hash(&mut config);
     }
+    // Rebuild to get the report of unused dependencies, as it is only cached
+    // when it was requested.
+    if unused_dependencies::is_checked(build_runner.bcx, unit) {
+        "unused-externs".hash(&mut config);
+    }
+    // Rebuild to record the features read by the sources.
+    if unused_features::is_checked(build_runner.bcx, unit) {
+        "read-features".hash(&mut config);
+    }
     let compile_kind = unit.kind.fingerprint_hash();
     let mut declared_features = unit.pkg.summary().features().keys().collect::<Vec<_>>();
     declared_features.sort(); // to avoid useless rebuild if the user orders it's features
@@ -197,4 +197,11 @@ impl<'a, 'gctx> JobState<'a, 'gctx> {
         self.messages
             .push(Message::FutureIncompatReport(self.id, report));
     }
+
+    /// The extern crate names rustc reported as unused for this unit.
+    ///
+    /// See [`crate::core::compiler::unused_dependencies`].
+    pub fn unused_externs(&self, names: Vec<String>) {
+        self.messages.push(Message::UnusedExterns(self.id, names));
+    }
 }
@@ -134,11 +134,13 @@ pub use self::job_state::JobState;
 use super::build_runner::OutputFile;
 use super::custom_build::Severity;
 use super::timings::Timings;
-use super::{BuildContext, BuildPlan, BuildRunner, CompileMode, Unit};
+use super::{BuildContext, BuildPlan, BuildRunner, CompileKind, CompileMode, Unit};
 use crate::core::compiler::descriptive_pkg_name;
 use crate::core::compiler::future_incompat::{
     self, FutureBreakageItem, FutureIncompatReportPackage,
 };
+use crate::core::compiler::unused_dependencies;
+use crate::core::compiler::unused_features;
 use crate::core::resolver::ResolveBehavior;
 use crate::core::{PackageId, Shell, TargetKind};
 use crate::util::diagnostic_server::{self, DiagnosticPrinter};
@@ -201,6 +203,11 @@ struct DrainState<'gctx> {
     /// How many jobs we've finished
     finished: usize,
     per_package_future_incompat_reports: Vec<FutureIncompatReportPackage>,
+    /// Extern crate names rustc reported as unused for each unit, for the
+    /// `unused_dependencies` lint.
+    unused_externs: HashMap<Unit, Vec<String>>,
+    /// Kinds of the units that failed, for `-Zmultitarget-summary`.
+    failed_kinds: HashSet<CompileKind>,
 }
 
 /// Count of warnings, used to print a summary after the job succeeds
@@ -373,6 +380,7 @@ enum Message {
     Token(io::Result<Acquired>),
     Finish(JobId, Artifact, CargoResult<()>),
     FutureIncompatReport(JobId, Vec<FutureBreakageItem>),
+    UnusedExterns(JobId, Vec<String>),
 }
 
 impl<'gctx> JobQueue<'gctx> {
@@ -504,6 +512,8 @@ impl<'gctx> JobQueue<'gctx> {
             ),
             finished: 0,
             per_package_future_incompat_reports: Vec::new(),
+            unused_externs: HashMap::new(),
+            failed_kinds: HashSet::new(),
         };
 
         // Create a helper thread for acquiring jobserver tokens
@@ -685,6 +695,7 @@ impl<'gctx> DrainState<'gctx> {
                         self.queue.finish(&unit, &artifact);
                     }
                     Err(error) => {
+                        self.failed_kinds.insert(unit.kind);
                         let show_warnings = true;
                         self.emit_log_messages(&unit, build_runner, show_warnings)?;
                         self.back_compat_notice(build_runner, &unit)?;
@@ -700,6 +711,9 @@ impl<'gctx> DrainState<'gctx> {
                 self.per_package_future_incompat_reports
                     .push(FutureIncompatReportPackage { package_id, items });
             }
+            Message::UnusedExterns(id, names) => {
+                self.unused_externs.insert(self.active[&id].clone(), names);
+            }
             Message::Token(acquired_token) => {
                 let token = acquired_token.context("failed to acquire jobserver token")?;
                 self.tokens.push(token);
@@ -811,10 +825,30 @@ impl<'gctx> DrainState<'gctx> {
         if let Err(e) = self.timings.finished(build_runner, &errors.to_error()) {
             self.handle_error(&mut build_runner.bcx.gctx.shell(), &mut errors, e);
         }
+        let target_results = build_runner
+            .bcx
+            .build_config
+            .multitarget_summary
+            .then(|| self.target_results(build_runner.bcx));
+        if let Some(target_results) = &target_results {
+            if !build_runner.bcx.build_config.build_plan {
+                // It doesn't really matter if this fails.
+                let _ = report_target_results(build_runner.bcx.gctx, target_results);
+            }
+        }
         if build_runner.bcx.build_config.emit_json() {
             let mut shell = build_runner.bcx.gctx.shell();
             let msg = machine_message::BuildFinished {
                 success: errors.count == 0,
+                targets: target_results.map(|results| {
+                    results
+                        .into_iter()
+                        .map(|(target, success)| machine_message::BuildFinishedTarget {
+                            target,
+                            success,
+                        })
+                        .collect()
+                }),
             }
             .to_json_string();
             if let Err(e) = writeln!(shell.out(), "{}", msg) {
@@ -827,6 +861,15 @@ impl<'gctx> DrainState<'gctx> {
             // `display_error` inside `handle_error`.
             Some(anyhow::Error::new(AlreadyPrintedError::new(error)))
         } else if self.queue.is_empty() && self.pending_queue.is_empty() {
+            if !self.unused_externs.is_empty() {
+                match unused_dependencies::report(build_runner, &self.unused_externs) {
+                    Ok(unused) => build_runner.compilation.unused_dependencies = unused,
+                    Err(e) => return Some(e),
+                }
+            }
+            if let Err(e) = unused_features::report(build_runner) {
+                return Some(e);
+            }
             let profile_link = build_runner.bcx.gctx.shell().err_hyperlink(
                 "https://doc.rust-lang.org/cargo/reference/profiles.html#default-profiles",
             );
@@ -849,6 +892,27 @@ impl<'gctx> DrainState<'gctx> {
         }
     }
 
+    /// Determines which of the requested targets were built successfully.
+    ///
+    /// A target failed if any of its units failed, or if any of them never
+    /// ran because something it depends on failed.
+    fn target_results(&self, bcx: &BuildContext<'_, '_>) -> Vec<(String, bool)> {
+        let mut failed = self.failed_kinds.clone();
+        failed.extend(self.queue.pending().map(|unit| unit.kind));
+        failed.extend(self.pending_queue.iter().map(|(unit, _, _)| unit.kind));
+        bcx.build_config
+            .requested_kinds
+            .iter()
+            .map(|kind| {
+                let name = match kind {
+                    CompileKind::Host => bcx.host_triple().to_string(),
+                    CompileKind::Target(target) => target.short_name().to_string(),
+                };
+                (name, !failed.contains(kind))
+            })
+            .collect()
+    }
+
     fn handle_error(
         &self,
         shell: &mut Shell,
@@ -1222,3 +1286,27 @@ impl ErrorsDuringDrain {
         }
     }
 }
+
+/// Prints which requested targets built successfully and which failed, for
+/// `-Zmultitarget-summary`.
+fn report_target_results(gctx: &GlobalContext, results: &[(String, bool)]) -> CargoResult<()> {
+    let names = |success: bool| {
+        results
+            .iter()
+            .filter(|(_, s)| *s == success)
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+    };
+    let (succeeded, failed) = (names(true), names(false));
+    let mut summary = vec![];
+    if !succeeded.is_empty() {
+        summary.push(format!("Successfully built for {}!", succeeded.join(", ")));
+    }
+    if !failed.is_empty() {
+        summary.push(format!(
+            "Failed to build for {} (see error(s) above).",
+            failed.join(", ")
+        ));
+    }
+    gctx.shell().status("Summary", summary.join(" "))
+}
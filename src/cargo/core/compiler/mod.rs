@@ -53,6 +53,8 @@ mod timings;
 mod unit;
 pub mod unit_dependencies;
 pub mod unit_graph;
+pub(crate) mod unused_dependencies;
+pub(crate) mod unused_features;
 
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
@@ -287,6 +289,8 @@ fn rustc(
     };
     let rustc_dep_info_loc = root.join(dep_info_name);
     let dep_info_loc = fingerprint::dep_info_loc(build_runner, unit);
+    let read_features_path = unused_features::is_checked(build_runner.bcx, unit)
+        .then(|| unused_features::read_features_path(build_runner, unit));
 
     let mut output_options = OutputOptions::new(build_runner, unit);
     let package_id = unit.pkg.package_id();
@@ -470,6 +474,9 @@ fn rustc(
             // This mtime shift allows Cargo to detect if a source file was
             // modified in the middle of the build.
             paths::set_file_time_no_err(dep_info_loc, timestamp);
+            if let Some(read_features_path) = &read_features_path {
+                unused_features::save(&rustc_dep_info_loc, &cwd, read_features_path)?;
+            }
         }
 
         Ok(())
@@ -541,13 +548,18 @@ fn link_targets(
 ) -> CargoResult<Work> {
     let bcx = build_runner.bcx;
     let outputs = build_runner.outputs(unit)?;
-    let export_dir = build_runner.files().export_dir();
     let package_id = unit.pkg.package_id();
     let manifest_path = PathBuf::from(unit.pkg.manifest_path());
     let profile = unit.profile.clone();
     let unit_mode = unit.mode;
     let features = unit.features.iter().map(|s| s.to_string()).collect();
     let json_messages = bcx.build_config.emit_json();
+    let target_triple = match unit.kind {
+        CompileKind::Target(t) if bcx.build_config.multitarget_summary => {
+            Some(t.short_name().to_owned())
+        }
+        _ => None,
+    };
     let executable = build_runner.get_executable(unit)?;
     let mut target = Target::clone(&unit.target);
     if let TargetSourcePath::Metabuild = target.src_path() {
@@ -579,8 +591,7 @@ fn link_targets(
             destinations.push(dst.clone());
             paths::link_or_copy(src, dst)?;
             if let Some(ref path) = output.export_path {
-                let export_dir = export_dir.as_ref().unwrap();
-                paths::create_dir_all(export_dir)?;
+                paths::create_dir_all(path.parent().unwrap())?;
 
                 paths::link_or_copy(src, path)?;
             }
@@ -615,6 +626,7 @@ fn link_targets(
                 filenames: destinations,
                 executable,
                 fresh,
+                target_triple: target_triple.as_deref(),
             }
             .to_json_string();
             state.stdout(msg)?;
@@ -1506,6 +1518,10 @@ fn build_deps_args(
         cmd.arg(arg);
     }
 
+    if unused_dependencies::is_checked(bcx, unit) {
+        unused_dependencies::add_args(cmd);
+    }
+
     for (var, env) in artifact::get_env(build_runner, deps)? {
         cmd.env(&var, env);
     }
@@ -1747,6 +1763,18 @@ fn on_stderr_line_inner(
         return Ok(true);
     }
 
+    #[derive(serde::Deserialize)]
+    struct UnusedExterns {
+        unused_extern_names: Vec<String>,
+    }
+
+    // Requested by `unused_dependencies::add_args`, it is cached so the
+    // report is also available when the unit is fresh.
+    if let Ok(report) = serde_json::from_str::<UnusedExterns>(compiler_message.get()) {
+        state.unused_externs(report.unused_extern_names);
+        return Ok(true);
+    }
+
     // Depending on what we're emitting from Cargo itself, we figure out what to
     // do with this JSON message.
     match options.format {
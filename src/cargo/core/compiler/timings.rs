@@ -2,7 +2,7 @@
 //!
 //! This module implements some simple tracking information for timing of how
 //! long it takes for different units to compile.
-use super::{CompileMode, Unit};
+use super::{CompileKind, CompileMode, Unit};
 use crate::core::compiler::job_queue::JobId;
 use crate::core::compiler::{BuildContext, BuildRunner, TimingOutput};
 use crate::core::PackageId;
@@ -32,6 +32,9 @@ pub struct Timings<'gctx> {
     report_html: bool,
     /// If true, emits JSON information with timing information.
     report_json: bool,
+    /// If true, units are labeled with the `--target` they were built for
+    /// (`-Zmultitarget-summary`).
+    multitarget_summary: bool,
     /// When Cargo started.
     start: Instant,
     /// A rendered string of when compilation started.
@@ -136,6 +139,7 @@ impl<'gctx> Timings<'gctx> {
             enabled,
             report_html,
             report_json,
+            multitarget_summary: bcx.build_config.multitarget_summary,
             start: bcx.gctx.creation_time(),
             start_str,
             root_targets,
@@ -174,6 +178,9 @@ impl<'gctx> Timings<'gctx> {
             CompileMode::Docscrape => target.push_str(" (doc scrape)"),
             CompileMode::RunCustomBuild => target.push_str(" (run)"),
         }
+        if let Some(triple) = self.target_triple(&unit) {
+            target.push_str(&format!(" [{triple}]"));
+        }
         let unit_time = UnitTime {
             unit,
             target,
@@ -227,6 +234,7 @@ impl<'gctx> Timings<'gctx> {
                 mode: unit_time.unit.mode,
                 duration: unit_time.duration,
                 rmeta_time: unit_time.rmeta_time,
+                target_triple: self.target_triple(&unit_time.unit),
             }
             .to_json_string();
             crate::drop_println!(self.gctx, "{}", msg);
@@ -234,6 +242,15 @@ impl<'gctx> Timings<'gctx> {
         self.unit_times.push(unit_time);
     }
 
+    /// Returns the `--target` that `unit` was built for, if units are grouped
+    /// per target.
+    fn target_triple<'a>(&self, unit: &'a Unit) -> Option<&'a str> {
+        match &unit.kind {
+            CompileKind::Target(t) if self.multitarget_summary => Some(t.short_name()),
+            _ => None,
+        }
+    }
+
     /// This is called periodically to mark the concurrency of internal structures.
     pub fn mark_concurrency(&mut self, active: usize, waiting: usize, inactive: usize) {
         if !self.enabled {
@@ -0,0 +1,179 @@
+//! Support for the [`unused_dependencies`] lint.
+//!
+//! rustc can report which of the crates passed with `--extern` a crate did
+//! not use, see [`add_args`]. The reports of every unit of a package are
+//! collected while building, and a dependency is only reported once all the
+//! targets it applies to were checked and none of them used it, see
+//! [`report`].
+//!
+//! [`unused_dependencies`]: https://doc.rust-lang.org/nightly/cargo/reference/lints.html#unused_dependencies
+
+use std::collections::HashMap;
+
+use cargo_util::ProcessBuilder;
+
+use super::{BuildContext, BuildRunner, CompileMode, Unit};
+use crate::core::dependency::DepKind;
+use crate::core::{Dependency, Package, Target};
+use crate::util::errors::AlreadyPrintedError;
+use crate::util::lints::{
+    cargo_lints_table, check_unused_dependencies, unused_dependencies_enabled,
+};
+use crate::CargoResult;
+
+/// Returns `true` if rustc should report the unused dependencies of the unit.
+pub fn is_checked(bcx: &BuildContext<'_, '_>, unit: &Unit) -> bool {
+    matches!(
+        unit.mode,
+        CompileMode::Build | CompileMode::Check { .. } | CompileMode::Test | CompileMode::Bench
+    ) && bcx.gctx.cli_unstable().cargo_lints
+        && bcx.ws.is_member(&unit.pkg)
+        && unused_dependencies_enabled(&unit.pkg)
+}
+
+/// Asks rustc to report the unused `--extern` crates as a JSON message,
+/// instead of emitting the `unused_crate_dependencies` lint.
+pub fn add_args(cmd: &mut ProcessBuilder) {
+    // rustc only reports them if the lint is enabled.
+    cmd.arg("-W").arg("unused-crate-dependencies");
+    cmd.arg("--json=unused-externs-silent");
+}
+
+/// Emits the lint for the workspace members whose units were checked.
+///
+/// `unused_externs` has the extern crate names rustc reported as unused for
+/// each unit. Units missing from it, for example because
+/// `unused_crate_dependencies` is allowed in the crate, could have used any
+/// of their dependencies.
+///
+/// Returns the dependencies that were reported, so `cargo fix` can remove
+/// them.
+pub fn report(
+    build_runner: &BuildRunner<'_, '_>,
+    unused_externs: &HashMap<Unit, Vec<String>>,
+) -> CargoResult<Vec<(Package, Dependency)>> {
+    let mut packages: Vec<&Package> = unused_externs.keys().map(|unit| &unit.pkg).collect();
+    packages.sort_unstable_by_key(|pkg| pkg.package_id());
+    packages.dedup_by_key(|pkg| pkg.package_id());
+
+    let gctx = build_runner.bcx.gctx;
+    let mut result = Vec::new();
+    let mut error_count = 0;
+    for pkg in packages {
+        let checked: Vec<(&Unit, &Vec<String>)> = unused_externs
+            .iter()
+            .filter(|(unit, _)| unit.pkg == *pkg)
+            .collect();
+        let unused: Vec<Dependency> = pkg
+            .dependencies()
+            .iter()
+            .filter(|dep| is_unused(build_runner, pkg, dep, &checked))
+            .cloned()
+            .collect();
+        if unused.is_empty() {
+            continue;
+        }
+        check_unused_dependencies(
+            pkg,
+            pkg.manifest_path(),
+            &cargo_lints_table(pkg),
+            &unused,
+            &mut error_count,
+            gctx,
+        )?;
+        result.extend(unused.into_iter().map(|dep| (pkg.clone(), dep)));
+    }
+
+    if error_count > 0 {
+        Err(AlreadyPrintedError::new(anyhow::anyhow!(
+            "encountered {error_count} errors(s) while running lints"
+        ))
+        .into())
+    } else {
+        Ok(result)
+    }
+}
+
+/// Returns `true` if every target the dependency applies to was checked, and
+/// none of them used it.
+fn is_unused(
+    build_runner: &BuildRunner<'_, '_>,
+    pkg: &Package,
+    dep: &Dependency,
+    checked: &[(&Unit, &Vec<String>)],
+) -> bool {
+    if dep.kind() == DepKind::Development
+        && pkg
+            .targets()
+            .iter()
+            .any(|t| t.doctested() && t.doctestable())
+    {
+        // Doctests can use dev-dependencies, but rustdoc isn't asked about them.
+        return false;
+    }
+    let all_checked = pkg.targets().iter().all(|target| {
+        let Some(needs_test) = applicable_mode(dep.kind(), target) else {
+            return true;
+        };
+        checked
+            .iter()
+            .any(|(unit, _)| unit.target == *target && (!needs_test || unit.mode.is_rustc_test()))
+    });
+    if !all_checked {
+        return false;
+    }
+
+    let mut linked = false;
+    for (unit, unused) in checked {
+        if !applies_to(dep.kind(), &unit.target, unit.mode) {
+            continue;
+        }
+        for unit_dep in build_runner.unit_deps(unit) {
+            if !unit_dep.unit.target.is_linkable()
+                || unit_dep.unit.mode.is_doc()
+                || !dep.matches_ignoring_source(unit_dep.unit.pkg.package_id())
+            {
+                continue;
+            }
+            if !unused.contains(&unit_dep.extern_crate_name.to_string()) {
+                return false;
+            }
+            linked = true;
+        }
+    }
+    // Dependencies for other platforms, or optional ones that are not
+    // enabled, are not linked at all.
+    linked
+}
+
+/// Returns whether a dependency of the given kind applies to the target, and
+/// if so whether only its units built in test mode use it.
+fn applicable_mode(kind: DepKind, target: &Target) -> Option<bool> {
+    match kind {
+        DepKind::Build => target.is_custom_build().then_some(false),
+        DepKind::Normal => (!target.is_custom_build()).then_some(false),
+        DepKind::Development => {
+            if target.is_custom_build() {
+                None
+            } else if target.is_example() {
+                Some(false)
+            } else if target.is_test() || target.is_bench() || target.tested() {
+                Some(true)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Returns `true` if a unit of the target built in the given mode links the
+/// dependencies of the given kind.
+fn applies_to(kind: DepKind, target: &Target, mode: CompileMode) -> bool {
+    match kind {
+        DepKind::Build => target.is_custom_build(),
+        DepKind::Normal => !target.is_custom_build(),
+        DepKind::Development => {
+            !target.is_custom_build() && (mode.is_rustc_test() || target.is_example())
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Support for the [`unused_features`] lint.
+//!
+//! After rustc compiled a unit of a workspace member, the source files listed
+//! in its dep-info are scanned for the features they check, see [`save`].
+//! The result is kept next to the fingerprint of the unit, so fresh units
+//! don't read their sources again. Once the build is done, the features read
+//! by the units of each package are compared with its declared features and
+//! with the features enabled for its units, see [`report`].
+//!
+//! [`unused_features`]: https://doc.rust-lang.org/nightly/cargo/reference/lints.html#unused_features
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+
+use cargo_util::paths;
+
+use super::{BuildContext, BuildRunner, CompileMode, Unit};
+use crate::core::compiler::fingerprint;
+use crate::core::Package;
+use crate::util::errors::AlreadyPrintedError;
+use crate::util::interning::InternedString;
+use crate::util::lints::{cargo_lints_table, check_unused_features, unused_features_enabled};
+use crate::CargoResult;
+
+/// Prefix of the lines for features checked with `cfg(feature = "...")` in
+/// the file written by [`save`].
+const CFG_PREFIX: &str = "cfg:";
+/// Prefix of the lines for `CARGO_FEATURE_*` environment variables.
+const ENV_PREFIX: &str = "env:";
+
+/// Returns `true` if the features read by the unit are collected.
+pub fn is_checked(bcx: &BuildContext<'_, '_>, unit: &Unit) -> bool {
+    matches!(
+        unit.mode,
+        CompileMode::Build | CompileMode::Check { .. } | CompileMode::Test | CompileMode::Bench
+    ) && bcx.gctx.cli_unstable().cargo_lints
+        && bcx.ws.is_member(&unit.pkg)
+        && unused_features_enabled(&unit.pkg)
+}
+
+/// Path of the file listing the features read by the unit.
+pub fn read_features_path(build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> std::path::PathBuf {
+    build_runner
+        .files()
+        .fingerprint_file_path(unit, "read-features-")
+}
+
+/// Scans the source files listed in the dep-info written by rustc for the
+/// features they read, and records them at `dst`.
+///
+/// Paths in the dep-info are relative to `cwd`, the directory rustc ran in.
+pub fn save(rustc_dep_info: &Path, cwd: &Path, dst: &Path) -> CargoResult<()> {
+    let dep_info = fingerprint::parse_rustc_dep_info(rustc_dep_info)?;
+    let mut read = BTreeSet::new();
+    for file in dep_info.files.keys() {
+        if file.extension().map_or(true, |ext| ext != "rs") {
+            continue;
+        }
+        // Generated files may be gone already.
+        let Ok(contents) = paths::read(&cwd.join(file)) else {
+            continue;
+        };
+        scan(&contents, &mut read);
+    }
+    let mut contents = String::new();
+    for line in read {
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+    paths::write(dst, contents)
+}
+
+/// Emits the lint for the workspace members whose units were checked.
+pub fn report(build_runner: &BuildRunner<'_, '_>) -> CargoResult<()> {
+    let bcx = build_runner.bcx;
+    let checked: Vec<&Unit> = bcx
+        .unit_graph
+        .keys()
+        .filter(|unit| is_checked(bcx, unit))
+        .collect();
+    if checked.is_empty() {
+        return Ok(());
+    }
+    let mut packages: Vec<&Package> = checked.iter().map(|unit| &unit.pkg).collect();
+    packages.sort_unstable_by_key(|pkg| pkg.package_id());
+    packages.dedup_by_key(|pkg| pkg.package_id());
+
+    let mut error_count = 0;
+    for pkg in packages {
+        // Features enabled on the command-line or by any dependent in this
+        // build, as resolved for the units of the package.
+        let enabled: HashSet<InternedString> = bcx
+            .unit_graph
+            .keys()
+            .filter(|unit| unit.pkg == *pkg)
+            .flat_map(|unit| unit.features.iter().copied())
+            .collect();
+        let units: Vec<&Unit> = checked
+            .iter()
+            .copied()
+            .filter(|unit| unit.pkg == *pkg)
+            .collect();
+        let read = read_features(build_runner, pkg, &enabled, &units)?;
+        check_unused_features(
+            pkg,
+            pkg.manifest_path(),
+            &cargo_lints_table(pkg),
+            bcx.ws,
+            &enabled,
+            read.as_ref(),
+            &mut error_count,
+            bcx.gctx,
+        )?;
+    }
+
+    if error_count > 0 {
+        Err(AlreadyPrintedError::new(anyhow::anyhow!(
+            "encountered {error_count} errors(s) while running lints"
+        ))
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Collects the features read by the checked units of `pkg`, as the names in
+/// `cfg(feature = "...")` and the `CARGO_FEATURE_*` environment variables.
+///
+/// Returns `None` if a target that can be built with the `enabled` features
+/// wasn't checked, as it could still read any feature.
+fn read_features(
+    build_runner: &BuildRunner<'_, '_>,
+    pkg: &Package,
+    enabled: &HashSet<InternedString>,
+    units: &[&Unit],
+) -> CargoResult<Option<ReadFeatures>> {
+    let all_checked = pkg.targets().iter().all(|target| {
+        let buildable = target.required_features().map_or(true, |required| {
+            required
+                .iter()
+                .all(|f| f.contains('/') || enabled.contains(f.as_str()))
+        });
+        !buildable || units.iter().any(|unit| unit.target == *target)
+    });
+    if !all_checked {
+        return Ok(None);
+    }
+
+    let mut read = ReadFeatures::default();
+    for unit in units {
+        let path = read_features_path(build_runner, unit);
+        // Units that failed to build, or were built before the lint was
+        // enabled, have no record.
+        let Ok(contents) = paths::read(&path) else {
+            return Ok(None);
+        };
+        for line in contents.lines() {
+            if let Some(name) = line.strip_prefix(CFG_PREFIX) {
+                read.cfgs.insert(name.to_owned());
+            } else if let Some(name) = line.strip_prefix(ENV_PREFIX) {
+                read.envs.insert(name.to_owned());
+            }
+        }
+    }
+    Ok(Some(read))
+}
+
+/// The features read by the sources of a package.
+#[derive(Default)]
+pub struct ReadFeatures {
+    /// Names checked with `cfg(feature = "...")`.
+    pub cfgs: HashSet<String>,
+    /// Names of the `CARGO_FEATURE_*` environment variables.
+    pub envs: HashSet<String>,
+}
+
+impl ReadFeatures {
+    /// Checks if the feature is read by a `cfg` or its environment variable.
+    pub fn contains(&self, feature: &str) -> bool {
+        self.cfgs.contains(feature)
+            || self.envs.contains(&format!(
+                "CARGO_FEATURE_{}",
+                feature.to_uppercase().replace('-', "_")
+            ))
+    }
+}
+
+/// Collects the features checked with `feature = "..."`, as in `cfg`
+/// predicates, and the `CARGO_FEATURE_*` environment variables named in
+/// string literals. Comments are skipped.
+fn scan(contents: &str, read: &mut BTreeSet<String>) {
+    /// What precedes the current token.
+    #[derive(PartialEq)]
+    enum Prev {
+        Other,
+        Feature,
+        FeatureEq,
+    }
+
+    let bytes = contents.as_bytes();
+    let mut prev = Prev::Other;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"//") {
+            i += rest.iter().position(|b| *b == b'\n').unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with(b"/*") {
+            let mut depth = 0;
+            while i < bytes.len() {
+                if bytes[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if bytes[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            continue;
+        }
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if let Some((literal, end)) = string_literal(bytes, i) {
+            let literal = String::from_utf8_lossy(literal);
+            if prev == Prev::FeatureEq {
+                read.insert(format!("{CFG_PREFIX}{literal}"));
+            }
+            for (start, _) in literal.match_indices("CARGO_FEATURE_") {
+                let name: String = literal[start..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || *c == '_')
+                    .collect();
+                read.insert(format!("{ENV_PREFIX}{name}"));
+            }
+            prev = Prev::Other;
+            i = end;
+            continue;
+        }
+        if c == b'_' || c.is_ascii_alphabetic() {
+            let len = rest
+                .iter()
+                .position(|b| !(*b == b'_' || b.is_ascii_alphanumeric()))
+                .unwrap_or(rest.len());
+            prev = if &rest[..len] == b"feature" {
+                Prev::Feature
+            } else {
+                Prev::Other
+            };
+            i += len;
+            continue;
+        }
+        if c == b'\'' {
+            // A character literal, or else a lifetime.
+            if rest.get(1) == Some(&b'\\') {
+                i += rest
+                    .iter()
+                    .skip(3)
+                    .position(|b| *b == b'\'')
+                    .map_or(rest.len(), |p| p + 4);
+            } else {
+                let len = contents[i + 1..].chars().next().map_or(0, char::len_utf8);
+                i += if rest.get(1 + len) == Some(&b'\'') {
+                    len + 2
+                } else {
+                    1
+                };
+            }
+            prev = Prev::Other;
+            continue;
+        }
+        prev = if c == b'=' && prev == Prev::Feature && rest.get(1) != Some(&b'=') {
+            Prev::FeatureEq
+        } else {
+            Prev::Other
+        };
+        i += 1;
+    }
+}
+
+/// Parses the string literal starting at `start`, including raw and byte
+/// strings. Returns its contents and the index following it.
+fn string_literal(bytes: &[u8], start: usize) -> Option<(&[u8], usize)> {
+    let mut i = start;
+    if bytes.get(i) == Some(&b'b') || bytes.get(i) == Some(&b'c') {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b'r') {
+        i += 1;
+        let hashes = bytes[i..].iter().take_while(|b| **b == b'#').count();
+        i += hashes;
+        if bytes.get(i) != Some(&b'"') {
+            return None;
+        }
+        let content = i + 1;
+        let mut closing = vec![b'"'];
+        closing.extend(std::iter::repeat(b'#').take(hashes));
+        let len = bytes[content..]
+            .windows(closing.len())
+            .position(|w| w == closing.as_slice())
+            .unwrap_or(bytes.len() - content);
+        let end = (content + len + closing.len()).min(bytes.len());
+        return Some((&bytes[content..content + len], end));
+    }
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    let content = i + 1;
+    let mut j = content;
+    while j < bytes.len() && bytes[j] != b'"' {
+        j += if bytes[j] == b'\\' { 2 } else { 1 };
+    }
+    let j = j.min(bytes.len());
+    Some((&bytes[content..j], (j + 1).min(bytes.len())))
+}
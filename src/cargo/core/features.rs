@@ -517,6 +517,12 @@ features! {
 
     /// Allow paths that resolve relatively to a base specified in the config.
     (unstable, path_bases, "", "reference/unstable.html#path-bases"),
+
+    /// Allow declaring custom target specifications in `Cargo.toml`.
+    (unstable, target_specs, "", "reference/unstable.html#target-specs"),
+
+    /// Allow constraining the versions of any package in the dependency graph with `[workspace.constraints]`.
+    (unstable, workspace_constraints, "", "reference/unstable.html#workspace-constraints"),
 }
 
 /// Status and metadata for a single unstable feature.
@@ -753,6 +759,7 @@ unstable_cli_options!(
     // All other unstable features.
     // Please keep this list lexicographically ordered.
     advanced_env: bool,
+    advisory_fix: bool = ("Allow `cargo update --advisory-fix` to update away from vulnerable versions"),
     asymmetric_token: bool = ("Allows authenticating with asymmetric tokens"),
     avoid_dev_deps: bool = ("Avoid installing dev-dependencies if possible"),
     binary_dep_depinfo: bool = ("Track changes to dependency artifacts"),
@@ -764,35 +771,49 @@ unstable_cli_options!(
     checksum_freshness: bool = ("Use a checksum to determine if output is fresh rather than filesystem mtime"),
     codegen_backend: bool = ("Enable the `codegen-backend` option in profiles in .cargo/config.toml file"),
     config_include: bool = ("Enable the `include` key in config files"),
+    cross_toolchains: bool = ("Discover linkers and runners for cross-compilation targets on PATH"),
+    debug_symbols: bool = ("Allow `cargo install --debug-symbols` to install split debuginfo files"),
     direct_minimal_versions: bool = ("Resolve minimal dependency versions instead of maximum (direct dependencies only)"),
     doctest_xcompile: bool = ("Compile and run doctests for non-host target using runner config"),
     dual_proc_macros: bool = ("Build proc-macros for both the host and the target"),
+    explain_conflicts: bool = ("Explain dependency resolution failures as a chain of conflicting requirements"),
+    feature_unification: bool = ("Enable new feature unification modes in workspaces"),
     features: Option<Vec<String>>,
     gc: bool = ("Track cache usage and \"garbage collect\" unused files"),
     #[serde(deserialize_with = "deserialize_git_features")]
-    git: Option<GitFeatures> = ("Enable support for shallow git fetch operations"),
+    git: Option<GitFeatures> = ("Enable support for shallow git fetch operations and sparse checkouts"),
     #[serde(deserialize_with = "deserialize_gitoxide_features")]
     gitoxide: Option<GitoxideFeatures> = ("Use gitoxide for the given git interactions, or all of them if no argument is given"),
     host_config: bool = ("Enable the `[host]` section in the .cargo/config.toml file"),
+    index_signing: bool = ("Verify the signatures of index files of sparse registries that advertise a signing key"),
+    index_snapshot: bool = ("Enable `cargo fetch --export-index` and `--import-index` for offline registry snapshots"),
+    merge_lockfile: bool = ("Enable the `cargo merge-lockfile` git merge driver for Cargo.lock"),
     minimal_versions: bool = ("Resolve minimal dependency versions instead of maximum"),
+    msrv_per_member: bool = ("Resolve each workspace member's dependencies against its own `rust-version`"),
     msrv_policy: bool = ("Enable rust-version aware policy within cargo"),
     mtime_on_use: bool = ("Configure Cargo to update the mtime of used files"),
+    multitarget_summary: bool = ("Build every requested `--target` to completion and summarize the results per target"),
     next_lockfile_bump: bool,
     no_index_update: bool = ("Do not update the registry index even if the cache is outdated"),
     package_workspace: bool = ("Handle intra-workspace dependencies when packaging"),
     panic_abort_tests: bool = ("Enable support to run tests with -Cpanic=abort"),
     profile_rustflags: bool = ("Enable the `rustflags` option in profiles in .cargo/config.toml file"),
     public_dependency: bool = ("Respect a dependency's `public` field in Cargo.toml to control public/private dependencies"),
+    publish_time: bool = ("Enable resolving to versions published before a given time"),
     publish_timeout: bool = ("Enable the `publish.timeout` key in .cargo/config.toml file"),
+    registry_mirrors: bool = ("Enable the `mirrors` key of `[source]` tables to download crates from mirrors with fallback"),
     root_dir: Option<PathBuf> = ("Set the root directory relative to which paths are printed (defaults to workspace root)"),
     rustdoc_map: bool = ("Allow passing external documentation mappings to rustdoc"),
     rustdoc_scrape_examples: bool = ("Allows Rustdoc to scrape code examples from reverse-dependencies"),
     script: bool = ("Enable support for single-file, `.rs` packages"),
     separate_nightlies: bool,
     skip_rustdoc_fingerprint: bool,
+    sparse_index_changes: bool = ("Use the change log of sparse registries to only revalidate changed index files"),
     target_applies_to_host: bool = ("Enable the `target-applies-to-host` key in the .cargo/config.toml file"),
     trim_paths: bool = ("Enable the `trim-paths` option in profiles"),
     unstable_options: bool = ("Allow the usage of unstable options"),
+    update_report: bool = ("Allow `cargo update --report` to summarize lockfile changes by SemVer class"),
+    why_feature: bool = ("Allow `cargo tree --why-feature` to explain why a feature is enabled"),
 );
 
 const STABILIZED_COMPILE_PROGRESS: &str = "The progress bar is now always \
@@ -890,6 +911,8 @@ pub struct GitFeatures {
     pub shallow_index: bool,
     /// When cloning git dependencies, perform a shallow clone and maintain shallowness on subsequent fetches.
     pub shallow_deps: bool,
+    /// When checking out git dependencies, only materialize the directories of the packages in use.
+    pub sparse_checkout: bool,
 }
 
 impl GitFeatures {
@@ -897,11 +920,12 @@ impl GitFeatures {
         GitFeatures {
             shallow_index: true,
             shallow_deps: true,
+            sparse_checkout: true,
         }
     }
 
     fn expecting() -> String {
-        let fields = vec!["`shallow-index`", "`shallow-deps`"];
+        let fields = vec!["`shallow-index`", "`shallow-deps`", "`sparse-checkout`"];
         format!(
             "unstable 'git' only takes {} as valid inputs",
             fields.join(" and ")
@@ -965,12 +989,14 @@ fn parse_git(it: impl Iterator<Item = impl AsRef<str>>) -> CargoResult<Option<Gi
     let GitFeatures {
         shallow_index,
         shallow_deps,
+        sparse_checkout,
     } = &mut out;
 
     for e in it {
         match e.as_ref() {
             "shallow-index" => *shallow_index = true,
             "shallow-deps" => *shallow_deps = true,
+            "sparse-checkout" => *sparse_checkout = true,
             _ => {
                 bail!(GitFeatures::expecting())
             }
@@ -1249,6 +1275,7 @@ impl CliUnstable {
             // Unstable features
             // Sorted alphabetically:
             "advanced-env" => self.advanced_env = parse_empty(k, v)?,
+            "advisory-fix" => self.advisory_fix = parse_empty(k, v)?,
             "asymmetric-token" => self.asymmetric_token = parse_empty(k, v)?,
             "avoid-dev-deps" => self.avoid_dev_deps = parse_empty(k, v)?,
             "binary-dep-depinfo" => self.binary_dep_depinfo = parse_empty(k, v)?,
@@ -1260,9 +1287,13 @@ impl CliUnstable {
             "cargo-lints" => self.cargo_lints = parse_empty(k, v)?,
             "codegen-backend" => self.codegen_backend = parse_empty(k, v)?,
             "config-include" => self.config_include = parse_empty(k, v)?,
+            "cross-toolchains" => self.cross_toolchains = parse_empty(k, v)?,
+            "debug-symbols" => self.debug_symbols = parse_empty(k, v)?,
             "direct-minimal-versions" => self.direct_minimal_versions = parse_empty(k, v)?,
             "doctest-xcompile" => self.doctest_xcompile = parse_empty(k, v)?,
             "dual-proc-macros" => self.dual_proc_macros = parse_empty(k, v)?,
+            "explain-conflicts" => self.explain_conflicts = parse_empty(k, v)?,
+            "feature-unification" => self.feature_unification = parse_empty(k, v)?,
             "gc" => self.gc = parse_empty(k, v)?,
             "git" => {
                 self.git = v.map_or_else(
@@ -1277,18 +1308,25 @@ impl CliUnstable {
                 )?
             }
             "host-config" => self.host_config = parse_empty(k, v)?,
+            "index-signing" => self.index_signing = parse_empty(k, v)?,
+            "index-snapshot" => self.index_snapshot = parse_empty(k, v)?,
             "next-lockfile-bump" => self.next_lockfile_bump = parse_empty(k, v)?,
+            "merge-lockfile" => self.merge_lockfile = parse_empty(k, v)?,
             "minimal-versions" => self.minimal_versions = parse_empty(k, v)?,
+            "msrv-per-member" => self.msrv_per_member = parse_empty(k, v)?,
             "msrv-policy" => self.msrv_policy = parse_empty(k, v)?,
             // can also be set in .cargo/config or with and ENV
             "mtime-on-use" => self.mtime_on_use = parse_empty(k, v)?,
+            "multitarget-summary" => self.multitarget_summary = parse_empty(k, v)?,
             "no-index-update" => self.no_index_update = parse_empty(k, v)?,
             "package-workspace" => self.package_workspace= parse_empty(k, v)?,
             "panic-abort-tests" => self.panic_abort_tests = parse_empty(k, v)?,
             "public-dependency" => self.public_dependency = parse_empty(k, v)?,
+            "publish-time" => self.publish_time = parse_empty(k, v)?,
             "profile-rustflags" => self.profile_rustflags = parse_empty(k, v)?,
             "trim-paths" => self.trim_paths = parse_empty(k, v)?,
             "publish-timeout" => self.publish_timeout = parse_empty(k, v)?,
+            "registry-mirrors" => self.registry_mirrors = parse_empty(k, v)?,
             "root-dir" => self.root_dir = v.map(|v| v.into()),
             "rustdoc-map" => self.rustdoc_map = parse_empty(k, v)?,
             "rustdoc-scrape-examples" => self.rustdoc_scrape_examples = parse_empty(k, v)?,
@@ -1296,8 +1334,11 @@ impl CliUnstable {
             "checksum-freshness" => self.checksum_freshness = parse_empty(k, v)?,
             "skip-rustdoc-fingerprint" => self.skip_rustdoc_fingerprint = parse_empty(k, v)?,
             "script" => self.script = parse_empty(k, v)?,
+            "sparse-index-changes" => self.sparse_index_changes = parse_empty(k, v)?,
             "target-applies-to-host" => self.target_applies_to_host = parse_empty(k, v)?,
             "unstable-options" => self.unstable_options = parse_empty(k, v)?,
+            "update-report" => self.update_report = parse_empty(k, v)?,
+            "why-feature" => self.why_feature = parse_empty(k, v)?,
             _ => bail!("\
             unknown `-Z` flag specified: {k}\n\n\
             For available unstable features, see https://doc.rust-lang.org/nightly/cargo/reference/unstable.html\n\
@@ -1313,31 +1354,32 @@ impl CliUnstable {
         self.fail_if_stable_opt_custom_z(flag, issue, "unstable-options", self.unstable_options)
     }
 
+    /// Generates an error if `enabled` is false, naming the `-Z` flag that
+    /// enables `flag`. The tracking `issue` is linked if there is one.
     pub fn fail_if_stable_opt_custom_z(
         &self,
         flag: &str,
-        issue: u32,
+        issue: impl Into<Option<u32>>,
         z_name: &str,
         enabled: bool,
     ) -> CargoResult<()> {
         if !enabled {
-            let see = format!(
-                "See https://github.com/rust-lang/cargo/issues/{issue} for more \
-                 information about the `{flag}` flag."
-            );
+            let see = match issue.into() {
+                Some(issue) => format!(
+                    "\nSee https://github.com/rust-lang/cargo/issues/{issue} for more \
+                     information about the `{flag}` flag."
+                ),
+                None => String::new(),
+            };
             // NOTE: a `config` isn't available here, check the channel directly
             let channel = channel();
             if channel == "nightly" || channel == "dev" {
-                bail!(
-                    "the `{flag}` flag is unstable, pass `-Z {z_name}` to enable it\n\
-                     {see}"
-                );
+                bail!("the `{flag}` flag is unstable, pass `-Z {z_name}` to enable it{see}");
             } else {
                 bail!(
                     "the `{flag}` flag is unstable, and only available on the nightly channel \
                      of Cargo, but this is the `{channel}` channel\n\
-                     {SEE_CHANNELS}\n\
-                     {see}"
+                     {SEE_CHANNELS}{see}"
                 );
             }
         }
@@ -14,7 +14,7 @@ use serde::Serialize;
 use url::Url;
 
 use crate::core::compiler::rustdoc::RustdocScrapeExamples;
-use crate::core::compiler::{CompileKind, CrateType};
+use crate::core::compiler::{CompileKind, CompileTarget, CrateType};
 use crate::core::resolver::ResolveBehavior;
 use crate::core::{Dependency, PackageId, PackageIdSpec, SourceId, Summary};
 use crate::core::{Edition, Feature, Features, WorkspaceConfig};
@@ -71,6 +71,7 @@ pub struct Manifest {
     targets: Vec<Target>,
     default_kind: Option<CompileKind>,
     forced_kind: Option<CompileKind>,
+    target_specs: Vec<CompileTarget>,
     links: Option<String>,
     warnings: Warnings,
     exclude: Vec<String>,
@@ -427,6 +428,7 @@ impl Manifest {
 
         default_kind: Option<CompileKind>,
         forced_kind: Option<CompileKind>,
+        target_specs: Vec<CompileTarget>,
         targets: Vec<Target>,
         exclude: Vec<String>,
         include: Vec<String>,
@@ -456,6 +458,7 @@ impl Manifest {
 
             default_kind,
             forced_kind,
+            target_specs,
             targets,
             warnings: Warnings::new(),
             exclude,
@@ -521,6 +524,10 @@ impl Manifest {
     pub fn forced_kind(&self) -> Option<CompileKind> {
         self.forced_kind
     }
+    /// Custom target specifications declared in `package.target-specs`.
+    pub fn target_specs(&self) -> &[CompileTarget] {
+        &self.target_specs
+    }
     pub fn exclude(&self) -> &[String] {
         &self.exclude
     }
@@ -1,6 +1,6 @@
 use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt;
 use std::hash;
 use std::mem;
@@ -27,7 +27,7 @@ use crate::core::{
     SourceId, Target,
 };
 use crate::core::{Summary, Workspace};
-use crate::sources::source::{MaybePackage, SourceMap};
+use crate::sources::source::{DownloadFallback, MaybePackage, SourceMap};
 use crate::util::cache_lock::{CacheLock, CacheLockMode};
 use crate::util::errors::{CargoResult, HttpNotSuccessful};
 use crate::util::interning::InternedString;
@@ -390,6 +390,9 @@ struct Download<'gctx> {
 
     /// Logic used to track retrying this download if it's a spurious failure.
     retry: Retry<'gctx>,
+
+    /// Locations to download from instead if downloading from `url` fails.
+    fallbacks: VecDeque<DownloadFallback>,
 }
 
 impl<'gctx> PackageSet<'gctx> {
@@ -718,7 +721,7 @@ impl<'a, 'gctx> Downloads<'a, 'gctx> {
         let pkg = source
             .download(id)
             .context("unable to get packages from source")?;
-        let (url, descriptor, authorization) = match pkg {
+        let (url, descriptor, authorization, fallbacks) = match pkg {
             MaybePackage::Ready(pkg) => {
                 debug!("{} doesn't need a download", id);
                 assert!(slot.fill(pkg).is_ok());
@@ -728,7 +731,8 @@ impl<'a, 'gctx> Downloads<'a, 'gctx> {
                 url,
                 descriptor,
                 authorization,
-            } => (url, descriptor, authorization),
+                fallbacks,
+            } => (url, descriptor, authorization, fallbacks),
         };
 
         // Ok we're going to download this crate, so let's set up all our
@@ -747,9 +751,7 @@ impl<'a, 'gctx> Downloads<'a, 'gctx> {
 
         // Add authorization header.
         if let Some(authorization) = authorization {
-            let mut headers = curl::easy::List::new();
-            headers.append(&format!("Authorization: {}", authorization))?;
-            handle.http_headers(headers)?;
+            set_authorization(&mut handle, &authorization)?;
         }
 
         // Enable HTTP/2 if possible.
@@ -810,6 +812,7 @@ impl<'a, 'gctx> Downloads<'a, 'gctx> {
             start: Instant::now(),
             timed_out: Cell::new(None),
             retry: Retry::new(self.set.gctx)?,
+            fallbacks: fallbacks.into(),
         };
         self.enqueue(dl, handle)?;
         self.tick(WhyTick::DownloadStarted)?;
@@ -852,7 +855,7 @@ impl<'a, 'gctx> Downloads<'a, 'gctx> {
             let ret = {
                 let timed_out = &dl.timed_out;
                 let url = &dl.url;
-                dl.retry.r#try(|| {
+                let result: CargoResult<Vec<u8>> = (|| {
                     if let Err(e) = result {
                         // If this error is "aborted by callback" then that's
                         // probably because our progress callback aborted due to
@@ -888,7 +891,33 @@ impl<'a, 'gctx> Downloads<'a, 'gctx> {
                         .into());
                     }
                     Ok(data)
-                })
+                })();
+
+                // Any failure moves on to the next location, if there is
+                // one, without retrying.
+                if let Err(e) = &result {
+                    if let Some(next) = dl.fallbacks.pop_front() {
+                        let err_msg = e
+                            .downcast_ref::<HttpNotSuccessful>()
+                            .map(|http_err| http_err.display_short())
+                            .unwrap_or_else(|| e.root_cause().to_string());
+                        self.set.gctx.shell().warn(format!(
+                            "failed to download `{}`, trying the next location: {err_msg}",
+                            dl.descriptor
+                        ))?;
+                        handle.url(&next.url)?;
+                        match &next.authorization {
+                            Some(authorization) => set_authorization(&mut handle, authorization)?,
+                            None => handle.http_headers(curl::easy::List::new())?,
+                        }
+                        dl.url = next.url;
+                        dl.descriptor = next.descriptor;
+                        self.pending_ids.insert(dl.id);
+                        self.enqueue(dl, handle)?;
+                        continue;
+                    }
+                }
+                dl.retry.r#try(|| result)
             };
             match ret {
                 RetryResult::Success(data) => break (dl, data),
@@ -1180,6 +1209,14 @@ impl<'a, 'gctx> Drop for Downloads<'a, 'gctx> {
     }
 }
 
+/// Sets the `Authorization` header of a download request.
+fn set_authorization(handle: &mut Easy, authorization: &str) -> CargoResult<()> {
+    let mut headers = curl::easy::List::new();
+    headers.append(&format!("Authorization: {}", authorization))?;
+    handle.http_headers(headers)?;
+    Ok(())
+}
+
 mod tls {
     use std::cell::Cell;
 
@@ -23,6 +23,7 @@ use crate::util::errors::CargoResult;
 use crate::util::interning::InternedString;
 use crate::util::{CanonicalUrl, GlobalContext};
 use anyhow::{bail, Context as _};
+use semver::VersionReq;
 use tracing::{debug, trace};
 use url::Url;
 
@@ -131,6 +132,10 @@ pub struct PackageRegistry<'gctx> {
     /// This is constructed during calls to [`PackageRegistry::patch`],
     /// along with the `patches` field, thoough these entries never get locked.
     patches_available: HashMap<CanonicalUrl, Vec<PackageId>>,
+    /// Versions hidden from resolution, keyed by package name and source.
+    ///
+    /// See [`PackageRegistry::exclude_versions`].
+    excluded_versions: HashMap<(InternedString, SourceId), Vec<VersionReq>>,
 }
 
 /// A map of all "locked packages" which is filled in when parsing a lock file
@@ -206,6 +211,7 @@ impl<'gctx> PackageRegistry<'gctx> {
             source_config,
             locked: HashMap::new(),
             yanked_whitelist: HashSet::new(),
+            excluded_versions: HashMap::new(),
             patches: HashMap::new(),
             patches_locked: false,
             patches_available: HashMap::new(),
@@ -289,6 +295,18 @@ impl<'gctx> PackageRegistry<'gctx> {
         self.add_source(source, Kind::Override);
     }
 
+    /// Hides the versions of `name` from `source_id` matching `req` from
+    /// queries, so they are never selected by the resolver.
+    ///
+    /// This is how `cargo update --advisory-fix` steers resolution away from
+    /// vulnerable versions. Patches and path overrides are not affected.
+    pub fn exclude_versions(&mut self, name: InternedString, source_id: SourceId, req: VersionReq) {
+        self.excluded_versions
+            .entry((name, source_id))
+            .or_default()
+            .push(req);
+    }
+
     /// Allows a group of package to be available to query even if they are yanked.
     pub fn add_to_yanked_whitelist(&mut self, iter: impl Iterator<Item = PackageId>) {
         let pkgs = iter.collect::<Vec<_>>();
@@ -647,8 +665,16 @@ impl<'gctx> Registry for PackageRegistry<'gctx> {
         f: &mut dyn FnMut(IndexSummary),
     ) -> Poll<CargoResult<()>> {
         assert!(self.patches_locked);
+        // Overrides and patches are never rejected, so only the real source has
+        // anything to report about rejected versions.
+        let rejected = kind == QueryKind::RejectedVersions;
+
         // Look for an override and get ready to query the real source.
-        let override_summary = ready!(self.query_overrides(dep))?;
+        let override_summary = if rejected {
+            None
+        } else {
+            ready!(self.query_overrides(dep))?
+        };
 
         // Next up on our list of candidates is to check the `[patch]` section
         // of the manifest. Here we look through all patches relevant to the
@@ -657,7 +683,11 @@ impl<'gctx> Registry for PackageRegistry<'gctx> {
         // come from a different source. This means that `dep.matches(..)` will
         // always return false, when what we really care about is the name/version match.
         let mut patches = Vec::<Summary>::new();
-        if let Some(extra) = self.patches.get(dep.source_id().canonical_url()) {
+        if let Some(extra) = self
+            .patches
+            .get(dep.source_id().canonical_url())
+            .filter(|_| !rejected)
+        {
             patches.extend(
                 extra
                     .iter()
@@ -726,7 +756,17 @@ impl<'gctx> Registry for PackageRegistry<'gctx> {
                 // then we skip this `summary`.
                 let locked = &self.locked;
                 let all_patches = &self.patches_available;
+                let excluded = &self.excluded_versions;
                 let callback = &mut |summary: IndexSummary| {
+                    if rejected {
+                        return f(summary);
+                    }
+                    let id = summary.package_id();
+                    if let Some(reqs) = excluded.get(&(id.name(), id.source_id())) {
+                        if reqs.iter().any(|req| req.matches(id.version())) {
+                            return;
+                        }
+                    }
                     for patch in patches.iter() {
                         let patch = patch.package_id().version();
                         if summary.package_id().version() == patch {
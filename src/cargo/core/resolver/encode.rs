@@ -120,7 +120,7 @@ use anyhow::{bail, Context as _};
 use serde::de;
 use serde::ser;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::str::FromStr;
 use tracing::debug;
@@ -436,6 +436,138 @@ impl EncodableResolve {
             })
         }
     }
+
+    /// Merges the lock files of two branches, `ours` and `theirs`, that
+    /// diverged from `base`.
+    ///
+    /// Packages are merged as sets: a package added by either side is kept,
+    /// one removed by either side is dropped, and an entry changed by only one
+    /// side takes that change. Also returned are the names of packages both
+    /// sides changed in different ways. Those can't be merged structurally
+    /// and need to be resolved again, which is why the merged lock file may
+    /// list several versions of them.
+    pub fn merge(
+        base: EncodableResolve,
+        ours: EncodableResolve,
+        theirs: EncodableResolve,
+    ) -> (EncodableResolve, BTreeSet<String>) {
+        type Key = (String, String, Option<EncodableSourceId>);
+
+        fn packages(resolve: &mut EncodableResolve) -> BTreeMap<Key, EncodableDependency> {
+            let root = resolve.root.take();
+            resolve
+                .package
+                .take()
+                .unwrap_or_default()
+                .into_iter()
+                .chain(root)
+                .map(|pkg| {
+                    (
+                        (pkg.name.clone(), pkg.version.clone(), pkg.source.clone()),
+                        pkg,
+                    )
+                })
+                .collect()
+        }
+
+        fn versions<'a>(
+            pkgs: &'a BTreeMap<Key, EncodableDependency>,
+            name: &str,
+        ) -> BTreeSet<&'a Key> {
+            pkgs.keys().filter(|key| key.0 == name).collect()
+        }
+
+        let (mut base, mut ours, mut theirs) = (base, ours, theirs);
+        let base_pkgs = packages(&mut base);
+        let ours_pkgs = packages(&mut ours);
+        let mut theirs_pkgs = packages(&mut theirs);
+
+        let mut conflicts = BTreeSet::new();
+
+        // A package whose set of versions was changed differently by both
+        // sides, for example both upgraded it to a different version.
+        let names: BTreeSet<_> = ours_pkgs.keys().map(|key| key.0.clone()).collect();
+        for name in names {
+            let base_versions = versions(&base_pkgs, &name);
+            let ours_versions = versions(&ours_pkgs, &name);
+            let theirs_versions = versions(&theirs_pkgs, &name);
+            if ours_versions != base_versions
+                && theirs_versions != base_versions
+                && ours_versions != theirs_versions
+            {
+                conflicts.insert(name);
+            }
+        }
+
+        let mut merged = BTreeMap::new();
+        for (key, ours_pkg) in ours_pkgs {
+            let base_pkg = base_pkgs.get(&key);
+            match theirs_pkgs.remove(&key) {
+                Some(theirs_pkg) => {
+                    let pkg = if ours_pkg == theirs_pkg || base_pkg == Some(&theirs_pkg) {
+                        ours_pkg
+                    } else if base_pkg == Some(&ours_pkg) {
+                        theirs_pkg
+                    } else {
+                        conflicts.insert(key.0.clone());
+                        ours_pkg
+                    };
+                    merged.insert(key, pkg);
+                }
+                // Added by us.
+                None if base_pkg.is_none() => {
+                    merged.insert(key, ours_pkg);
+                }
+                // Removed by them, but we changed it.
+                None if base_pkg != Some(&ours_pkg) => {
+                    conflicts.insert(key.0.clone());
+                    merged.insert(key, ours_pkg);
+                }
+                // Removed by them.
+                None => {}
+            }
+        }
+        for (key, theirs_pkg) in theirs_pkgs {
+            match base_pkgs.get(&key) {
+                // Added by them.
+                None => {
+                    merged.insert(key, theirs_pkg);
+                }
+                // Removed by us, but they changed it.
+                Some(base_pkg) if *base_pkg != theirs_pkg => {
+                    conflicts.insert(key.0.clone());
+                    merged.insert(key, theirs_pkg);
+                }
+                // Removed by us.
+                Some(_) => {}
+            }
+        }
+
+        let metadata = match (ours.metadata, theirs.metadata) {
+            (Some(mut ours), Some(theirs)) => {
+                for (k, v) in theirs {
+                    ours.entry(k).or_insert(v);
+                }
+                Some(ours)
+            }
+            (ours, theirs) => ours.or(theirs),
+        };
+        let mut unused = ours.patch.unused;
+        for pkg in theirs.patch.unused {
+            if !unused.contains(&pkg) {
+                unused.push(pkg);
+            }
+        }
+
+        let resolve = EncodableResolve {
+            version: ours.version.max(theirs.version),
+            package: Some(merged.into_values().collect()),
+            root: None,
+            metadata,
+            patch: Patch { unused },
+        };
+        (resolve, conflicts)
+    }
 }
 
 fn build_path_deps(
@@ -4,16 +4,20 @@ use std::task::Poll;
 use crate::core::{Dependency, PackageId, Registry, Summary};
 use crate::sources::source::QueryKind;
 use crate::util::edit_distance::edit_distance;
+use crate::util::interning::InternedString;
 use crate::util::{GlobalContext, OptVersionReq, VersionExt};
 use anyhow::Error;
+use time::format_description::well_known::Rfc3339;
 
 use super::context::ResolverContext;
 use super::types::{ConflictMap, ConflictReason};
+use super::VersionPreferences;
 
 /// Error during resolution providing a path of `PackageId`s.
 pub struct ResolveError {
     cause: Error,
     package_path: Vec<PackageId>,
+    details: Option<Box<ResolveErrorDetails>>,
 }
 
 impl ResolveError {
@@ -21,6 +25,7 @@ impl ResolveError {
         Self {
             cause: cause.into(),
             package_path,
+            details: None,
         }
     }
 
@@ -29,6 +34,54 @@ impl ResolveError {
     pub fn package_path(&self) -> &[PackageId] {
         &self.package_path
     }
+
+    /// Returns what the resolver knew about the dependency it failed to
+    /// select a version for, if the failure was about a single dependency.
+    pub fn details(&self) -> Option<&ResolveErrorDetails> {
+        self.details.as_deref()
+    }
+}
+
+/// The dependency the resolver failed to select a version for, and what ruled
+/// out each of its versions.
+#[derive(Clone, Debug)]
+pub struct ResolveErrorDetails {
+    /// The dependency that couldn't be satisfied.
+    pub dependency: Dependency,
+    /// The package declaring [`ResolveErrorDetails::dependency`].
+    pub parent: PackageId,
+    /// Versions matching the requirement that were considered, newest first.
+    pub candidates: Vec<Summary>,
+    /// Previously selected packages that ruled out the candidates.
+    pub conflicts: Vec<ResolveConflict>,
+    /// Candidates that need a newer Rust than the `rust-version` being
+    /// resolved for.
+    pub incompatible_rust_versions: Vec<semver::Version>,
+    /// Whether `--offline` limited the candidates to downloaded versions.
+    pub offline: bool,
+}
+
+impl ResolveErrorDetails {
+    /// Queries `registry` for the versions matching the requirement that were
+    /// never considered because they are yanked, newest first.
+    ///
+    /// This is only needed for reporting, so it isn't done while resolving.
+    pub fn yanked(&self, registry: &mut dyn Registry) -> Vec<semver::Version> {
+        rejected_versions(registry, &self.dependency)
+    }
+}
+
+/// A previously selected package that ruled out candidates of a dependency.
+#[derive(Clone, Debug)]
+pub struct ResolveConflict {
+    pub package_id: PackageId,
+    /// The kind of conflict, like `semver` or `links`.
+    pub reason: &'static str,
+    /// The `links` value or the features involved in the conflict.
+    pub detail: Option<InternedString>,
+    /// The package and dependency that caused `package_id` to be selected,
+    /// unless it is a root of the dependency graph.
+    pub required_by: Option<(PackageId, Dependency)>,
 }
 
 impl std::error::Error for ResolveError {
@@ -72,14 +125,27 @@ impl From<(PackageId, ConflictReason)> for ActivateError {
 pub(super) fn activation_error(
     resolver_ctx: &ResolverContext,
     registry: &mut dyn Registry,
+    version_prefs: &VersionPreferences,
     parent: &Summary,
     dep: &Dependency,
     conflicting_activations: &ConflictMap,
     candidates: &[Summary],
     gctx: Option<&GlobalContext>,
 ) -> ResolveError {
+    let details = ResolveErrorDetails {
+        dependency: dep.clone(),
+        parent: parent.package_id(),
+        candidates: candidates.to_vec(),
+        conflicts: describe_conflicts(resolver_ctx, conflicting_activations),
+        incompatible_rust_versions: candidates
+            .iter()
+            .filter(|c| version_prefs.is_rust_version_incompatible(c))
+            .map(|c| c.version().clone())
+            .collect(),
+        offline: gctx.map_or(false, |gctx| gctx.offline()),
+    };
     let to_resolve_err = |err| {
-        ResolveError::new(
+        let mut err = ResolveError::new(
             err,
             resolver_ctx
                 .parents
@@ -88,7 +154,9 @@ pub(super) fn activation_error(
                 .map(|(node, _)| node)
                 .cloned()
                 .collect(),
-        )
+        );
+        err.details = Some(Box::new(details.clone()));
+        err
     };
 
     if !candidates.is_empty() {
@@ -199,10 +267,23 @@ pub(super) fn activation_error(
         if has_semver {
             // Group these errors together.
             msg.push_str("\n\nall possible versions conflict with previously selected packages.");
-            for (p, r) in &conflicting_activations {
-                if let ConflictReason::Semver = r {
+            let explain = gctx.map_or(false, |gctx| gctx.cli_unstable().explain_conflicts);
+            let semver_conflicts = conflicting_activations
+                .iter()
+                .filter(|(_, r)| matches!(r, ConflictReason::Semver))
+                .map(|(p, _)| **p);
+            if explain {
+                msg.push_str(&explain_semver_conflicts(
+                    resolver_ctx,
+                    parent,
+                    dep,
+                    candidates,
+                    semver_conflicts,
+                ));
+            } else {
+                for p in semver_conflicts {
                     msg.push_str("\n\n  previously selected ");
-                    msg.push_str(&describe_path_in_context(resolver_ctx, p));
+                    msg.push_str(&describe_path_in_context(resolver_ctx, &p));
                 }
             }
         }
@@ -299,6 +380,44 @@ pub(super) fn activation_error(
             msg.push_str("\nperhaps a crate was updated and forgotten to be re-vendored?");
         }
 
+        let constrained = candidates
+            .iter()
+            .filter(|c| dep.matches(c))
+            .filter_map(|c| Some((version_prefs.violated_constraint(c)?, c.version())))
+            .collect::<Vec<_>>();
+        if let Some((constraint, _)) = constrained.first() {
+            let versions = constrained
+                .iter()
+                .map(|(_, v)| v.to_string())
+                .collect::<Vec<_>>();
+            msg.push_str(&format!(
+                "\nnote: versions matching the requirement are excluded by \
+                 `{} = \"{constraint}\"` in `[workspace.constraints]`: {}",
+                dep.package_name(),
+                versions.join(", ")
+            ));
+        }
+
+        if let Some(cutoff) = version_prefs.publish_time_cutoff() {
+            let too_new = candidates
+                .iter()
+                .filter(|c| dep.matches(c) && version_prefs.is_published_after_cutoff(c))
+                .map(|c| c.version().to_string())
+                .collect::<Vec<_>>();
+            if !too_new.is_empty() {
+                let cutoff = cutoff
+                    .replace_nanosecond(0)
+                    .unwrap_or(cutoff)
+                    .format(&Rfc3339)
+                    .unwrap_or_else(|_| cutoff.to_string());
+                msg.push_str(&format!(
+                    "\nnote: versions matching the requirement were filtered out \
+                     for being published after {cutoff}: {}",
+                    too_new.join(", ")
+                ));
+            }
+        }
+
         msg
     } else {
         // Maybe the user mistyped the name? Like `dep-thing` when `Dep_Thing`
@@ -383,6 +502,205 @@ pub(super) fn activation_error(
     to_resolve_err(anyhow::format_err!("{}", msg))
 }
 
+/// Lists the previously selected packages in `conflicting_activations`
+/// along with the requirement that selected each of them.
+fn describe_conflicts(
+    cx: &ResolverContext,
+    conflicting_activations: &ConflictMap,
+) -> Vec<ResolveConflict> {
+    conflicting_activations
+        .iter()
+        .map(|(&package_id, reason)| {
+            let (reason, detail) = match reason {
+                ConflictReason::Semver => ("semver", None),
+                ConflictReason::Links(link) => ("links", Some(*link)),
+                ConflictReason::MissingFeatures(f) => ("missing-features", Some(*f)),
+                ConflictReason::RequiredDependencyAsFeature(f) => {
+                    ("required-dependency-as-feature", Some(*f))
+                }
+                ConflictReason::NonImplicitDependencyAsFeature(f) => {
+                    ("non-implicit-dependency-as-feature", Some(*f))
+                }
+            };
+            let required_by = match cx.parents.path_to_bottom(&package_id).get(1) {
+                Some((requirer, Some(deps))) => deps
+                    .iter()
+                    .find(|d| d.package_name() == package_id.name())
+                    .map(|d| (**requirer, d.clone())),
+                _ => None,
+            };
+            ResolveConflict {
+                package_id,
+                reason,
+                detail,
+                required_by,
+            }
+        })
+        .collect()
+}
+
+/// Returns the versions matching `dep` that the source never offered to the
+/// resolver, like yanked ones, newest first.
+fn rejected_versions(registry: &mut dyn Registry, dep: &Dependency) -> Vec<semver::Version> {
+    let rejected = loop {
+        match registry.query_vec(dep, QueryKind::RejectedVersions) {
+            Poll::Ready(Ok(rejected)) => break rejected,
+            // This only adds detail to another error, don't shadow that one.
+            Poll::Ready(Err(_)) => return Vec::new(),
+            Poll::Pending => {
+                if registry.block_until_ready().is_err() {
+                    return Vec::new();
+                }
+            }
+        }
+    };
+    let mut versions: Vec<_> = rejected
+        .iter()
+        .map(|s| s.as_summary().version().clone())
+        .collect();
+    versions.sort_unstable_by(|a, b| b.cmp(a));
+    versions
+}
+
+/// Explains why none of `candidates` could be activated for `dep` because
+/// each semver-compatible slot was already taken by one of `conflicts`.
+///
+/// Every conflict is reduced to the two requirements that can't both hold,
+/// in the style of
+///
+/// ```text
+/// because `a v1.0.0` requires `c = "^1.1"` (satisfied by c v1.2.0, c v1.1.0)
+/// and `b v1.0.0` requires `c = "=1.0.5"`, which selected `c v1.0.5`,
+/// and only one version of `c` compatible with 1.x may be selected,
+/// `a v1.0.0` and `b v1.0.0` are incompatible.
+/// ```
+///
+/// followed by the changes most likely to make them compatible again.
+fn explain_semver_conflicts(
+    cx: &ResolverContext,
+    parent: &Summary,
+    dep: &Dependency,
+    candidates: &[Summary],
+    conflicts: impl Iterator<Item = PackageId>,
+) -> String {
+    use std::fmt::Write;
+
+    let mut msg = String::new();
+    let mut suggestions = Vec::new();
+    let parent_id = parent.package_id();
+    let name = dep.package_name();
+
+    for selected in conflicts {
+        let compat = describe_semver_compat(selected.version());
+        let compatible = candidates
+            .iter()
+            .map(|c| c.version())
+            .filter(|v| describe_semver_compat(v) == compat)
+            .collect::<Vec<_>>();
+
+        // The first step up from the selected package is the one whose
+        // requirement caused it to be activated.
+        let path = cx.parents.path_to_bottom(&selected);
+        let Some((requirer, Some(requirer_deps))) = path.get(1) else {
+            // The package is the root of the graph, nobody else can be blamed.
+            write!(
+                msg,
+                "\n\nbecause `{parent_id}` requires {} and `{selected}` was already selected \
+                 as a root of the dependency graph, they are incompatible.",
+                describe_requirement(dep),
+            )
+            .unwrap();
+            continue;
+        };
+        let requirer = **requirer;
+        // Without the requirement on `name` there is nothing to explain.
+        let Some(requirer_dep) = requirer_deps.iter().find(|d| d.package_name() == name) else {
+            continue;
+        };
+
+        write!(
+            msg,
+            "\n\nbecause `{parent_id}` requires {}",
+            describe_requirement(dep)
+        )
+        .unwrap();
+        if !compatible.is_empty() {
+            let satisfied_by = compatible
+                .iter()
+                .map(|v| format!("{name} v{v}"))
+                .collect::<Vec<_>>();
+            write!(msg, " (satisfied by {})", satisfied_by.join(", ")).unwrap();
+        }
+        write!(
+            msg,
+            "\nand `{requirer}` requires {}, which selected `{selected}`,\
+             \nand only one version of `{name}` compatible with {compat} may be selected,\
+             \n`{parent_id}` and `{requirer}` are incompatible.",
+            describe_requirement(requirer_dep),
+        )
+        .unwrap();
+        if path.len() > 2 {
+            let chain = path[1..]
+                .iter()
+                .rev()
+                .map(|(p, _)| format!("`{}`", p))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            write!(msg, "\n(`{requirer}` is required through {chain})").unwrap();
+        }
+
+        // Each side can give way to the versions the other side accepts.
+        let selected_version = [selected.version()];
+        for (pkg, d, others) in [
+            (requirer, requirer_dep, &compatible[..]),
+            (parent_id, dep, &selected_version[..]),
+        ] {
+            let suggestion = match d.version_req() {
+                // A lock file entry is in the way, which `cargo update` can move.
+                OptVersionReq::Locked(locked, req) if others.iter().any(|v| req.matches(v)) => {
+                    format!("run `cargo update -p {name}@{locked}` to unlock `{name}`")
+                }
+                _ if pkg.source_id().is_path() => format!(
+                    "relax the requirement {} in `{pkg}`",
+                    describe_requirement(d)
+                ),
+                // Every version of a registry or git package has already been
+                // tried, so there is nothing to change on our side.
+                _ => continue,
+            };
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+    }
+
+    if !suggestions.is_empty() {
+        msg.push_str("\n\nto resolve this conflict, consider:");
+        for suggestion in suggestions {
+            write!(msg, "\n  - {suggestion}").unwrap();
+        }
+    }
+
+    msg
+}
+
+/// Formats a dependency requirement the way it is written in a manifest.
+fn describe_requirement(dep: &Dependency) -> String {
+    format!("`{} = \"{}\"`", dep.package_name(), dep.version_req())
+}
+
+/// Describes the range of versions the resolver considers compatible with
+/// `version`, for example `1.x` for `1.2.3` or `0.2.x` for `0.2.3`.
+fn describe_semver_compat(version: &semver::Version) -> String {
+    if version.major != 0 {
+        format!("{}.x", version.major)
+    } else if version.minor != 0 {
+        format!("0.{}.x", version.minor)
+    } else {
+        format!("0.0.{}", version.patch)
+    }
+}
+
 /// Returns String representation of dependency chain for a particular `pkgid`
 /// within given context.
 pub(super) fn describe_path_in_context(cx: &ResolverContext, id: &PackageId) -> String {
@@ -43,6 +43,7 @@ use crate::core::dependency::{ArtifactTarget, DepKind, Dependency};
 use crate::core::resolver::types::FeaturesSet;
 use crate::core::resolver::{Resolve, ResolveBehavior};
 use crate::core::{FeatureValue, PackageId, PackageIdSpec, PackageSet, Workspace};
+use crate::util::context::FeatureUnification;
 use crate::util::interning::{InternedString, INTERNED_DEFAULT};
 use crate::util::CargoResult;
 use anyhow::{bail, Context};
@@ -67,7 +68,7 @@ pub struct ResolvedFeatures {
 }
 
 /// Options for how the feature resolver works.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct FeatureOpts {
     /// Build deps and proc-macros will not share features with other dep kinds,
     /// and so won't artifact targets.
@@ -82,6 +83,8 @@ pub struct FeatureOpts {
     ignore_inactive_targets: bool,
     /// If enabled, compare against old resolver (for testing).
     compare: bool,
+    /// Which packages the features of dependencies are unified across.
+    unification: FeatureUnification,
 }
 
 /// Flag to indicate if Cargo is building *any* dev units (tests, examples, etc.).
@@ -198,6 +201,9 @@ impl FeatureOpts {
             ResolveBehavior::V1 => {}
             ResolveBehavior::V2 | ResolveBehavior::V3 => {
                 enable(&vec!["all".to_string()]).unwrap();
+                // The old resolver unifies features in the dependency
+                // resolver, so only the new one can unify them differently.
+                opts.unification = ws.resolve_feature_unification();
             }
         }
         if let HasDevUnits::Yes = has_dev_units {
@@ -219,9 +225,15 @@ impl FeatureOpts {
                 decouple_dev_deps: has_dev_units == HasDevUnits::No,
                 ignore_inactive_targets: true,
                 compare: false,
+                unification: FeatureUnification::Selected,
             },
         }
     }
+
+    /// Which packages the features of dependencies are unified across.
+    pub fn unification(&self) -> FeatureUnification {
+        self.unification
+    }
 }
 
 /// Features flags requested for a package.
@@ -487,7 +499,11 @@ impl<'a, 'gctx> FeatureResolver<'a, 'gctx> {
         specs: &[PackageIdSpec],
         cli_features: &CliFeatures,
     ) -> CargoResult<()> {
-        let member_features = self.ws.members_with_features(specs, cli_features)?;
+        // Members that are not selected may still unify their default
+        // features with the selected ones.
+        let member_features =
+            self.ws
+                .members_with_unified_features(specs, cli_features, self.opts.unification)?;
         for (member, cli_features) in &member_features {
             let fvs = self.fvs_from_requested(member.package_id(), cli_features);
             let fk = if self.track_for_host && self.has_any_proc_macro(member.package_id()) {
@@ -79,7 +79,9 @@ use self::types::{FeaturesSet, RcVecIter, RemainingDeps, ResolverProgress};
 
 pub use self::encode::Metadata;
 pub use self::encode::{EncodableDependency, EncodablePackageId, EncodableResolve};
-pub use self::errors::{ActivateError, ActivateResult, ResolveError};
+pub use self::errors::{
+    ActivateError, ActivateResult, ResolveConflict, ResolveError, ResolveErrorDetails,
+};
 pub use self::features::{CliFeatures, ForceAllTargets, HasDevUnits};
 pub use self::resolve::{Resolve, ResolveVersion};
 pub use self::types::{ResolveBehavior, ResolveOpts};
@@ -115,7 +117,9 @@ mod version_prefs;
 ///   `PackageRegistry`.
 ///
 /// * `version_prefs` - this represents a preference for some versions over others,
-///   based on the lock file or other reasons such as `[patch]`es.
+///   based on the lock file or other reasons such as `[patch]`es. It also
+///   carries the `[workspace.constraints]`, which act as an extra requirement
+///   on every dependency on a constrained package.
 ///
 /// * `resolve_version` - this controls how the lockfile will be serialized.
 ///
@@ -353,9 +357,11 @@ fn activate_deps_loop(
                     }
                     None => {
                         debug!("no candidates found");
+                        let version_prefs = registry.version_prefs();
                         Err(errors::activation_error(
                             &resolver_ctx,
                             registry.registry,
+                            version_prefs,
                             &parent,
                             &dep,
                             &conflicting_activations,
@@ -5,9 +5,11 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
 use cargo_util_schemas::core::PartialVersion;
+use time::OffsetDateTime;
 
 use crate::core::{Dependency, PackageId, Summary};
 use crate::util::interning::InternedString;
+use crate::util::VersionConstraint;
 
 /// A collection of preferences for particular package versions.
 ///
@@ -22,6 +24,9 @@ pub struct VersionPreferences {
     prefer_patch_deps: HashMap<InternedString, HashSet<Dependency>>,
     version_ordering: VersionOrdering,
     rust_versions: Vec<PartialVersion>,
+    package_rust_versions: HashMap<InternedString, Vec<PartialVersion>>,
+    max_publish_time: Option<OffsetDateTime>,
+    constraints: HashMap<InternedString, VersionConstraint>,
 }
 
 #[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
@@ -53,6 +58,57 @@ impl VersionPreferences {
         self.rust_versions = vers;
     }
 
+    /// Resolve `name` against `vers` instead of
+    /// [`VersionPreferences::rust_versions`].
+    ///
+    /// This is used to only hold a package to the MSRVs of the workspace
+    /// members it is reachable from.
+    pub fn package_rust_versions(&mut self, name: InternedString, vers: Vec<PartialVersion>) {
+        self.package_rust_versions.insert(name, vers);
+    }
+
+    /// Only consider versions published at or before `time`, unless they are
+    /// preferred.
+    pub fn max_publish_time(&mut self, time: OffsetDateTime) {
+        self.max_publish_time = Some(time);
+    }
+
+    /// The cutoff set by [`VersionPreferences::max_publish_time`].
+    pub fn publish_time_cutoff(&self) -> Option<OffsetDateTime> {
+        self.max_publish_time
+    }
+
+    /// Whether `summary` was published after the
+    /// [`VersionPreferences::max_publish_time`] cutoff.
+    ///
+    /// Versions without a known publish time are never too new.
+    pub fn is_published_after_cutoff(&self, summary: &Summary) -> bool {
+        match (self.max_publish_time, summary.pubtime()) {
+            (Some(max), Some(pubtime)) => pubtime > max,
+            _ => false,
+        }
+    }
+
+    /// Only consider versions of `name` that satisfy `constraint`, even if
+    /// they are preferred.
+    pub fn constrain(&mut self, name: InternedString, constraint: VersionConstraint) {
+        self.constraints.insert(name, constraint);
+    }
+
+    /// The constraint from [`VersionPreferences::constrain`] that `summary`
+    /// violates, if any.
+    pub fn violated_constraint(&self, summary: &Summary) -> Option<&VersionConstraint> {
+        self.constraints
+            .get(&summary.name())
+            .filter(|constraint| !constraint.matches(summary.version()))
+    }
+
+    /// Whether `summary` requires a newer Rust than one of the
+    /// [`VersionPreferences::rust_versions`] being resolved for.
+    pub fn is_rust_version_incompatible(&self, summary: &Summary) -> bool {
+        self.msrv_compat_count(summary) < self.rust_versions_for(summary.name()).len()
+    }
+
     /// Sort (and filter) the given vector of summaries in-place
     ///
     /// Note: all summaries presumed to be for the same package.
@@ -63,6 +119,8 @@ impl VersionPreferences {
     /// 3. `first_version`, falling back to [`VersionPreferences::version_ordering`] when `None`
     ///
     /// Filtering:
+    /// - [`VersionPreferences::constrain`]
+    /// - [`VersionPreferences::max_publish_time`], except for preferred packages
     /// - `first_version`
     pub fn sort_summaries(
         &self,
@@ -77,6 +135,13 @@ impl VersionPreferences {
                     .map(|deps| deps.iter().any(|d| d.matches_id(*pkg_id)))
                     .unwrap_or(false)
         };
+        if !self.constraints.is_empty() {
+            summaries.retain(|s| self.violated_constraint(s).is_none());
+        }
+        if self.max_publish_time.is_some() {
+            summaries
+                .retain(|s| should_prefer(&s.package_id()) || !self.is_published_after_cutoff(s));
+        }
         summaries.sort_unstable_by(|a, b| {
             let prefer_a = should_prefer(&a.package_id());
             let prefer_b = should_prefer(&b.package_id());
@@ -85,7 +150,7 @@ impl VersionPreferences {
                 return previous_cmp;
             }
 
-            if !self.rust_versions.is_empty() {
+            if !self.rust_versions_for(a.name()).is_empty() {
                 let a_compat_count = self.msrv_compat_count(a);
                 let b_compat_count = self.msrv_compat_count(b);
                 if b_compat_count != a_compat_count {
@@ -104,12 +169,19 @@ impl VersionPreferences {
         }
     }
 
+    fn rust_versions_for(&self, name: InternedString) -> &[PartialVersion] {
+        self.package_rust_versions
+            .get(&name)
+            .unwrap_or(&self.rust_versions)
+    }
+
     fn msrv_compat_count(&self, summary: &Summary) -> usize {
+        let rust_versions = self.rust_versions_for(summary.name());
         let Some(rust_version) = summary.rust_version() else {
-            return self.rust_versions.len();
+            return rust_versions.len();
         };
 
-        self.rust_versions
+        rust_versions
             .iter()
             .filter(|max| rust_version.is_compatible_with(max))
             .count()
@@ -301,6 +373,83 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_package_rust_versions() {
+        let mut vp = VersionPreferences::default();
+        vp.rust_versions(vec!["1.45".parse().unwrap(), "1.55".parse().unwrap()]);
+        vp.package_rust_versions("foo".into(), vec!["1.55".parse().unwrap()]);
+
+        let mut summaries = vec![
+            summ("foo", "1.2.4", Some("1.60")),
+            summ("foo", "1.2.3", Some("1.50")),
+            summ("foo", "1.2.2", Some("1.40")),
+        ];
+        vp.version_ordering(VersionOrdering::MaximumVersionsFirst);
+        vp.sort_summaries(&mut summaries, None);
+        assert_eq!(
+            describe(&summaries),
+            "foo/1.2.3, foo/1.2.2, foo/1.2.4".to_string()
+        );
+
+        let mut summaries = vec![
+            summ("bar", "1.2.4", Some("1.60")),
+            summ("bar", "1.2.3", Some("1.50")),
+            summ("bar", "1.2.2", Some("1.40")),
+        ];
+        vp.sort_summaries(&mut summaries, None);
+        assert_eq!(
+            describe(&summaries),
+            "bar/1.2.2, bar/1.2.3, bar/1.2.4".to_string()
+        );
+    }
+
+    #[test]
+    fn test_constraints() {
+        let mut vp = VersionPreferences::default();
+        vp.prefer_package_id(pkgid("foo", "1.2.4"));
+        vp.constrain("foo".into(), "<1.2.4, !=1.2.2".parse().unwrap());
+
+        let mut summaries = vec![
+            summ("foo", "1.2.4", None),
+            summ("foo", "1.2.3", None),
+            summ("foo", "1.2.2", None),
+            summ("foo", "1.2.1", None),
+        ];
+
+        vp.version_ordering(VersionOrdering::MaximumVersionsFirst);
+        vp.sort_summaries(&mut summaries, None);
+        assert_eq!(describe(&summaries), "foo/1.2.3, foo/1.2.1".to_string());
+    }
+
+    #[test]
+    fn test_max_publish_time() {
+        let mut vp = VersionPreferences::default();
+        vp.prefer_package_id(pkgid("foo", "1.2.4"));
+        vp.max_publish_time(OffsetDateTime::from_unix_timestamp(200).unwrap());
+
+        let published = |version, time| {
+            let mut summary = summ("foo", version, None);
+            if let Some(time) = time {
+                summary.set_pubtime(OffsetDateTime::from_unix_timestamp(time).unwrap());
+            }
+            summary
+        };
+        let mut summaries = vec![
+            published("1.2.5", Some(400)),
+            published("1.2.4", Some(300)),
+            published("1.2.3", Some(200)),
+            published("1.1.0", None),
+            published("1.0.9", Some(100)),
+        ];
+
+        vp.version_ordering(VersionOrdering::MaximumVersionsFirst);
+        vp.sort_summaries(&mut summaries, None);
+        assert_eq!(
+            describe(&summaries),
+            "foo/1.2.4, foo/1.2.3, foo/1.1.0, foo/1.0.9".to_string()
+        );
+    }
+
     #[test]
     fn test_empty_summaries() {
         let vp = VersionPreferences::default();
@@ -10,8 +10,9 @@ use itertools::Itertools;
 use tracing::debug;
 use url::Url;
 
-use crate::core::compiler::Unit;
+use crate::core::compiler::{CompileKind, CompileTarget, Unit};
 use crate::core::features::Features;
+use crate::core::gc::parse_time_span;
 use crate::core::registry::PackageRegistry;
 use crate::core::resolver::features::CliFeatures;
 use crate::core::resolver::ResolveBehavior;
@@ -20,22 +21,23 @@ use crate::core::{
 };
 use crate::core::{EitherManifest, Package, SourceId, VirtualManifest};
 use crate::ops;
+use crate::ops::lockfile::{LOCKFILE_NAME, MINIMAL_LOCKFILE_NAME};
 use crate::sources::{PathSource, SourceConfigMap, CRATES_IO_INDEX, CRATES_IO_REGISTRY};
 use crate::util::edit_distance;
 use crate::util::errors::{CargoResult, ManifestError};
 use crate::util::interning::InternedString;
-use crate::util::lints::{analyze_cargo_lints_table, check_im_a_teapot};
+use crate::util::lints::{analyze_cargo_lints_table, cargo_lints_table, check_im_a_teapot};
 use crate::util::toml::{read_manifest, InheritableFields};
 use crate::util::{
-    context::CargoResolverConfig, context::ConfigRelativePath, context::IncompatibleRustVersions,
-    Filesystem, GlobalContext, IntoUrl,
+    context::CargoResolverConfig, context::ConfigRelativePath, context::FeatureUnification,
+    context::IncompatibleRustVersions, Filesystem, GlobalContext, IntoUrl, VersionConstraint,
 };
 use cargo_util::paths;
 use cargo_util::paths::normalize_path;
-use cargo_util_schemas::manifest;
 use cargo_util_schemas::manifest::RustVersion;
 use cargo_util_schemas::manifest::{TomlDependency, TomlProfiles};
 use pathdiff::diff_paths;
+use time::OffsetDateTime;
 
 /// The core abstraction in Cargo for working with a workspace of crates.
 ///
@@ -105,6 +107,10 @@ pub struct Workspace<'gctx> {
 
     /// Requested path of the lockfile (i.e. passed as the cli flag)
     requested_lockfile_path: Option<PathBuf>,
+    /// Resolve to the minimal versions allowed and keep them in
+    /// `Cargo.minimal.lock`, see [`Workspace::lockfile_name`].
+    /// This is set from the `resolver.minimal-versions` config or CLI flags.
+    resolve_minimal_versions: bool,
 
     /// The resolver behavior specified with the `resolver` field.
     resolve_behavior: ResolveBehavior,
@@ -112,10 +118,23 @@ pub struct Workspace<'gctx> {
     /// and other places that use rust version.
     /// This is set based on the resolver version, config settings, and CLI flags.
     resolve_honors_rust_version: bool,
+    /// Versions published after this time are not selected by the resolver.
+    /// This is set from the `resolver.min-release-age` config or CLI flags.
+    resolve_publish_time: Option<OffsetDateTime>,
+    /// Which packages the features of dependencies are unified across.
+    /// This is set from the `resolver.feature-unification` config.
+    resolve_feature_unification: FeatureUnification,
+    /// If `true`, a failed resolution is also reported as a `resolve-error`
+    /// JSON message on stdout.
+    resolve_errors_as_json: bool,
 
     /// Workspace-level custom metadata
     custom_metadata: Option<toml::Value>,
 
+    /// Version requirements from `[workspace.constraints]`, applied to every
+    /// package of that name in the dependency graph.
+    constraints: BTreeMap<InternedString, VersionConstraint>,
+
     /// Local overlay configuration. See [`crate::sources::overlay`].
     local_overlays: HashMap<SourceId, PathBuf>,
 }
@@ -195,6 +214,7 @@ pub struct WorkspaceRootConfig {
     exclude: Vec<String>,
     inheritable_fields: InheritableFields,
     custom_metadata: Option<toml::Value>,
+    constraints: BTreeMap<InternedString, VersionConstraint>,
 }
 
 impl<'gctx> Workspace<'gctx> {
@@ -217,9 +237,10 @@ impl<'gctx> Workspace<'gctx> {
             ws.root_manifest = ws.find_root(manifest_path)?;
         }
 
-        ws.custom_metadata = ws
-            .load_workspace_config()?
-            .and_then(|cfg| cfg.custom_metadata);
+        if let Some(cfg) = ws.load_workspace_config()? {
+            ws.custom_metadata = cfg.custom_metadata;
+            ws.constraints = cfg.constraints;
+        }
         ws.find_members()?;
         ws.set_resolve_behavior()?;
         ws.validate()?;
@@ -244,9 +265,14 @@ impl<'gctx> Workspace<'gctx> {
             loaded_packages: RefCell::new(HashMap::new()),
             ignore_lock: false,
             requested_lockfile_path: None,
+            resolve_minimal_versions: false,
             resolve_behavior: ResolveBehavior::V1,
             resolve_honors_rust_version: false,
+            resolve_publish_time: None,
+            resolve_feature_unification: FeatureUnification::Selected,
+            resolve_errors_as_json: false,
             custom_metadata: None,
+            constraints: BTreeMap::new(),
             local_overlays: HashMap::new(),
         }
     }
@@ -306,13 +332,26 @@ impl<'gctx> Workspace<'gctx> {
                 }
             }
         }
-        if let CargoResolverConfig {
-            incompatible_rust_versions: Some(incompatible_rust_versions),
-        } = self.gctx().get::<CargoResolverConfig>("resolver")?
-        {
+        let config = self.gctx().get::<CargoResolverConfig>("resolver")?;
+        if let Some(incompatible_rust_versions) = config.incompatible_rust_versions {
             self.resolve_honors_rust_version =
                 incompatible_rust_versions == IncompatibleRustVersions::Fallback;
         }
+        if let Some(age) = config.min_release_age {
+            if self.gctx().cli_unstable().publish_time {
+                let age = parse_time_span(&age)
+                    .context("failed to parse config option `resolver.min-release-age`")?;
+                self.resolve_publish_time = Some(OffsetDateTime::now_utc() - age);
+            }
+        }
+        if let Some(feature_unification) = config.feature_unification {
+            if self.gctx().cli_unstable().feature_unification {
+                self.resolve_feature_unification = feature_unification;
+            }
+        }
+        if let Some(minimal_versions) = config.minimal_versions {
+            self.resolve_minimal_versions = minimal_versions;
+        }
 
         Ok(())
     }
@@ -535,6 +574,41 @@ impl<'gctx> Workspace<'gctx> {
             })
     }
 
+    /// Replaces requested targets that name a custom target spec declared by
+    /// a member in `package.target-specs` with that spec.
+    pub fn resolve_target_specs(&self, kinds: &[CompileKind]) -> CargoResult<Vec<CompileKind>> {
+        kinds
+            .iter()
+            .map(|kind| {
+                let CompileKind::Target(target) = kind else {
+                    return Ok(*kind);
+                };
+                let mut found: Option<(&Package, CompileTarget)> = None;
+                for pkg in self.members() {
+                    let Some(spec) = pkg
+                        .manifest()
+                        .target_specs()
+                        .iter()
+                        .find(|spec| spec.short_name() == target.rustc_target().as_str())
+                    else {
+                        continue;
+                    };
+                    match found {
+                        Some((other, other_spec)) if other_spec != *spec => bail!(
+                            "target `{}` is declared with different specs by `{}` and `{}`",
+                            target.rustc_target(),
+                            other.name(),
+                            pkg.name(),
+                        ),
+                        Some(_) => {}
+                        None => found = Some((pkg, *spec)),
+                    }
+                }
+                Ok(found.map_or(*kind, |(_, spec)| CompileKind::Target(spec)))
+            })
+            .collect()
+    }
+
     /// Returns a mutable iterator over all packages in this workspace
     pub fn members_mut(&mut self) -> impl Iterator<Item = &mut Package> {
         let packages = &mut self.packages.packages;
@@ -645,6 +719,36 @@ impl<'gctx> Workspace<'gctx> {
         self.requested_lockfile_path.as_deref()
     }
 
+    /// Resolve to the minimal versions allowed by the dependency
+    /// requirements, kept in `Cargo.minimal.lock` unless another lock file
+    /// was requested.
+    pub fn set_resolve_minimal_versions(&mut self, minimal: bool) {
+        self.resolve_minimal_versions = minimal;
+    }
+
+    /// The file name of the lock file in [`Workspace::lock_root`].
+    pub fn lockfile_name(&self) -> &str {
+        if let Some(name) = self
+            .requested_lockfile_path
+            .as_deref()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+        {
+            name
+        } else if self.resolve_minimal_versions {
+            MINIMAL_LOCKFILE_NAME
+        } else {
+            LOCKFILE_NAME
+        }
+    }
+
+    /// Whether the resolver picks the minimal versions allowed instead of the
+    /// maximum, either with `-Zminimal-versions` or
+    /// [`Workspace::set_resolve_minimal_versions`].
+    pub fn resolve_minimal_versions(&self) -> bool {
+        self.gctx.cli_unstable().minimal_versions || self.resolve_minimal_versions
+    }
+
     /// Get the lowest-common denominator `package.rust-version` within the workspace, if specified
     /// anywhere
     pub fn lowest_rust_version(&self) -> Option<&RustVersion> {
@@ -661,10 +765,38 @@ impl<'gctx> Workspace<'gctx> {
         self.resolve_honors_rust_version
     }
 
+    /// Only resolve to versions published at or before `time`.
+    pub fn set_resolve_publish_time(&mut self, time: OffsetDateTime) {
+        self.resolve_publish_time = Some(time);
+    }
+
+    pub fn resolve_publish_time(&self) -> Option<OffsetDateTime> {
+        self.resolve_publish_time
+    }
+
+    pub fn resolve_feature_unification(&self) -> FeatureUnification {
+        self.resolve_feature_unification
+    }
+
+    /// Also report resolution failures as `resolve-error` JSON messages, for
+    /// commands whose output is JSON.
+    pub fn set_resolve_errors_as_json(&mut self, as_json: bool) {
+        self.resolve_errors_as_json = as_json;
+    }
+
+    pub fn resolve_errors_as_json(&self) -> bool {
+        self.resolve_errors_as_json
+    }
+
     pub fn custom_metadata(&self) -> Option<&toml::Value> {
         self.custom_metadata.as_ref()
     }
 
+    /// The `[workspace.constraints]` of this workspace.
+    pub fn constraints(&self) -> &BTreeMap<InternedString, VersionConstraint> {
+        &self.constraints
+    }
+
     pub fn load_workspace_config(&mut self) -> CargoResult<Option<WorkspaceRootConfig>> {
         // If we didn't find a root, it must mean there is no [workspace] section, and thus no
         // metadata.
@@ -1187,17 +1319,7 @@ impl<'gctx> Workspace<'gctx> {
 
     pub fn emit_lints(&self, pkg: &Package, path: &Path) -> CargoResult<()> {
         let mut error_count = 0;
-        let toml_lints = pkg
-            .manifest()
-            .normalized_toml()
-            .lints
-            .clone()
-            .map(|lints| lints.lints)
-            .unwrap_or(manifest::TomlLints::default());
-        let cargo_lints = toml_lints
-            .get("cargo")
-            .cloned()
-            .unwrap_or(manifest::TomlToolLints::default());
+        let cargo_lints = cargo_lints_table(pkg);
 
         let ws_contents = match self.root_maybe() {
             MaybePackage::Package(pkg) => pkg.manifest().contents(),
@@ -1264,6 +1386,29 @@ impl<'gctx> Workspace<'gctx> {
         }
     }
 
+    /// Like [`Workspace::members_with_features`], but when `unification`
+    /// spans the whole workspace, the members that aren't selected are
+    /// returned as well, with only their default features.
+    pub fn members_with_unified_features(
+        &self,
+        specs: &[PackageIdSpec],
+        cli_features: &CliFeatures,
+        unification: FeatureUnification,
+    ) -> CargoResult<Vec<(&Package, CliFeatures)>> {
+        let mut member_features = self.members_with_features(specs, cli_features)?;
+        if unification == FeatureUnification::Workspace {
+            for member in self.members() {
+                if !member_features
+                    .iter()
+                    .any(|(m, _)| m.package_id() == member.package_id())
+                {
+                    member_features.push((member, CliFeatures::new_all(false)));
+                }
+            }
+        }
+        Ok(member_features)
+    }
+
     /// Returns the requested features for the given member.
     /// This filters out any named features that the member does not have.
     fn collect_matching_features(
@@ -1834,6 +1979,7 @@ impl WorkspaceRootConfig {
         exclude: &Option<Vec<String>>,
         inheritable: &Option<InheritableFields>,
         custom_metadata: &Option<toml::Value>,
+        constraints: BTreeMap<InternedString, VersionConstraint>,
     ) -> WorkspaceRootConfig {
         WorkspaceRootConfig {
             root_dir: root_dir.to_path_buf(),
@@ -1842,6 +1988,7 @@ impl WorkspaceRootConfig {
             exclude: exclude.clone().unwrap_or_default(),
             inheritable_fields: inheritable.clone().unwrap_or_default(),
             custom_metadata: custom_metadata.clone(),
+            constraints,
         }
     }
     /// Checks the path against the `excluded` list.
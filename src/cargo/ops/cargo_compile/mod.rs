@@ -46,13 +46,15 @@ use crate::core::compiler::{BuildConfig, BuildContext, BuildRunner, Compilation}
 use crate::core::compiler::{CompileKind, CompileMode, CompileTarget, RustcTargetData, Unit};
 use crate::core::compiler::{DefaultExecutor, Executor, UnitInterner};
 use crate::core::profiles::Profiles;
-use crate::core::resolver::features::{self, CliFeatures, FeaturesFor};
+use crate::core::resolver::features::{
+    self, CliFeatures, FeatureOpts, FeatureResolver, FeaturesFor, ForceAllTargets,
+};
 use crate::core::resolver::{HasDevUnits, Resolve};
 use crate::core::{PackageId, PackageSet, SourceId, TargetKind, Workspace};
 use crate::drop_println;
 use crate::ops;
 use crate::ops::resolve::WorkspaceResolve;
-use crate::util::context::GlobalContext;
+use crate::util::context::{FeatureUnification, GlobalContext};
 use crate::util::interning::InternedString;
 use crate::util::{CargoResult, StableHasher};
 
@@ -275,7 +277,7 @@ pub fn create_bcx<'a, 'gctx>(
         cli_features,
         &specs,
         has_dev_units,
-        crate::core::resolver::features::ForceAllTargets::No,
+        ForceAllTargets::No,
         dry_run,
     )?;
     let WorkspaceResolve {
@@ -323,6 +325,31 @@ pub fn create_bcx<'a, 'gctx>(
         }
     }
 
+    // With `resolver.feature-unification = "package"`, each package is built
+    // with the features it would have if it were the only one selected.
+    let mut package_features = HashMap::new();
+    let feature_opts = FeatureOpts::new(ws, has_dev_units, ForceAllTargets::No)?;
+    if feature_opts.unification() == FeatureUnification::Package && to_builds.len() > 1 {
+        let member_features = ws.members_with_features(&specs, cli_features)?;
+        for pkg in to_builds.iter() {
+            let pkg_cli_features = member_features
+                .iter()
+                .find(|(member, _)| member.package_id() == pkg.package_id())
+                .map_or_else(|| CliFeatures::new_all(false), |(_, fts)| fts.clone());
+            let resolved = FeatureResolver::resolve(
+                ws,
+                &mut target_data,
+                &resolve,
+                &pkg_set,
+                &pkg_cli_features,
+                &[pkg.package_id().to_spec()],
+                &build_config.requested_kinds,
+                feature_opts.clone(),
+            )?;
+            package_features.insert(pkg.package_id(), resolved);
+        }
+    }
+
     let (extra_args, extra_args_name) = match (target_rustc_args, target_rustdoc_args) {
         (Some(args), _) => (Some(args.clone()), "rustc"),
         (_, Some(args)) => (Some(args.clone()), "rustdoc"),
@@ -372,6 +399,7 @@ pub fn create_bcx<'a, 'gctx>(
         resolve: &resolve,
         workspace_resolve: &workspace_resolve,
         resolved_features: &resolved_features,
+        package_features: &package_features,
         package_set: &pkg_set,
         profiles: &profiles,
         interner,
@@ -410,20 +438,55 @@ pub fn create_bcx<'a, 'gctx>(
         Default::default()
     };
 
-    let mut unit_graph = build_unit_dependencies(
-        ws,
-        &pkg_set,
-        &resolve,
-        &resolved_features,
-        std_resolve_features.as_ref(),
-        &units,
-        &scrape_units,
-        &std_roots,
-        build_config.mode,
-        &target_data,
-        &profiles,
-        interner,
-    )?;
+    let mut unit_graph = if package_features.is_empty() {
+        build_unit_dependencies(
+            ws,
+            &pkg_set,
+            &resolve,
+            &resolved_features,
+            std_resolve_features.as_ref(),
+            &units,
+            &scrape_units,
+            &std_roots,
+            build_config.mode,
+            &target_data,
+            &profiles,
+            interner,
+        )?
+    } else {
+        // Build the dependencies of each package with its own features, and
+        // merge them into one graph. Units with different features are
+        // distinct, so shared dependencies are only built once per set of
+        // features.
+        let mut unit_graph = HashMap::new();
+        for pkg in to_builds.iter() {
+            let pkg_units: Vec<_> = units
+                .iter()
+                .filter(|unit| unit.pkg.package_id() == pkg.package_id())
+                .cloned()
+                .collect();
+            let pkg_scrape_units: Vec<_> = scrape_units
+                .iter()
+                .filter(|unit| unit.pkg.package_id() == pkg.package_id())
+                .cloned()
+                .collect();
+            unit_graph.extend(build_unit_dependencies(
+                ws,
+                &pkg_set,
+                &resolve,
+                &package_features[&pkg.package_id()],
+                std_resolve_features.as_ref(),
+                &pkg_units,
+                &pkg_scrape_units,
+                &std_roots,
+                build_config.mode,
+                &target_data,
+                &profiles,
+                interner,
+            )?);
+        }
+        unit_graph
+    };
 
     // TODO: In theory, Cargo should also dedupe the roots, but I'm uncertain
     // what heuristics to use in that case.
@@ -10,7 +10,7 @@ use crate::core::dependency::DepKind;
 use crate::core::profiles::{Profiles, UnitFor};
 use crate::core::resolver::features::{self, FeaturesFor};
 use crate::core::resolver::{HasDevUnits, Resolve};
-use crate::core::{FeatureValue, Package, PackageSet, Summary, Target};
+use crate::core::{FeatureValue, Package, PackageId, PackageSet, Summary, Target};
 use crate::core::{TargetKind, Workspace};
 use crate::util::restricted_names::is_glob_pattern;
 use crate::util::{closest_msg, CargoResult};
@@ -55,6 +55,9 @@ pub(super) struct UnitGenerator<'a, 'gctx> {
     pub resolve: &'a Resolve,
     pub workspace_resolve: &'a Option<Resolve>,
     pub resolved_features: &'a features::ResolvedFeatures,
+    /// Features resolved separately for each package, used instead of
+    /// `resolved_features` with `resolver.feature-unification = "package"`.
+    pub package_features: &'a HashMap<PackageId, features::ResolvedFeatures>,
     pub package_set: &'a PackageSet<'gctx>,
     pub profiles: &'a Profiles,
     pub interner: &'a UnitInterner,
@@ -62,6 +65,13 @@ pub(super) struct UnitGenerator<'a, 'gctx> {
 }
 
 impl<'a> UnitGenerator<'a, '_> {
+    /// The features resolved for the units of `pkg_id`.
+    fn resolved_features(&self, pkg_id: PackageId) -> &'a features::ResolvedFeatures {
+        self.package_features
+            .get(&pkg_id)
+            .unwrap_or(self.resolved_features)
+    }
+
     /// Helper for creating a list of `Unit` structures
     fn new_units(
         &self,
@@ -103,7 +113,7 @@ impl<'a> UnitGenerator<'a, '_> {
         // No need to worry about build-dependencies, roots are never build dependencies.
         let features_for = FeaturesFor::from_for_host(target.proc_macro());
         let features = self
-            .resolved_features
+            .resolved_features(pkg.package_id())
             .activated_features(pkg.package_id(), features_for);
 
         // If `--target` has not been specified, then the unit
@@ -662,7 +672,7 @@ Rustdoc did not scrape the following examples because they require dev-dependenc
                     let features = features_map.entry(pkg).or_insert_with(|| {
                         super::resolve_all_features(
                             self.resolve,
-                            self.resolved_features,
+                            self.resolved_features(pkg.package_id()),
                             self.package_set,
                             pkg.package_id(),
                         )
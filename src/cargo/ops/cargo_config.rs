@@ -62,9 +62,16 @@ pub fn get(gctx: &GlobalContext, opts: &GetOptions<'_>) -> CargoResult<()> {
         None => ConfigKey::new(),
     };
     if opts.merged {
-        let cv = gctx
-            .get_cv_with_env(&key)?
-            .ok_or_else(|| format_err!("config value `{}` is not set", key))?;
+        let discovered = match opts.format {
+            ConfigFormat::Toml => discovered_toolchains(gctx, &key)?,
+            ConfigFormat::Json | ConfigFormat::JsonValue => Vec::new(),
+        };
+        let cv = gctx.get_cv_with_env(&key)?;
+        if cv.is_none() && !discovered.is_empty() {
+            print_toml_discovered(gctx, &discovered);
+            return Ok(());
+        }
+        let cv = cv.ok_or_else(|| format_err!("config value `{}` is not set", key))?;
         match opts.format {
             ConfigFormat::Toml => print_toml(gctx, opts, &key, &cv),
             ConfigFormat::Json => print_json(gctx, &key, &cv, true),
@@ -76,6 +83,9 @@ pub fn get(gctx: &GlobalContext, opts: &GetOptions<'_>) -> CargoResult<()> {
                 ConfigFormat::Json | ConfigFormat::JsonValue => print_json_env(gctx, &env),
             }
         }
+        if !discovered.is_empty() {
+            print_toml_discovered(gctx, &discovered);
+        }
     } else {
         match &opts.format {
             ConfigFormat::Toml => print_toml_unmerged(gctx, opts, &key)?,
@@ -112,6 +122,70 @@ fn maybe_env<'gctx>(
     }
 }
 
+/// Finds the `target.<triple>.linker` and `runner` values that
+/// `-Zcross-toolchains` would discover on `PATH` for the requested key.
+///
+/// For the `target` table itself this covers the triples from `build.target`.
+/// Values that are already configured are skipped.
+fn discovered_toolchains(
+    gctx: &GlobalContext,
+    key: &ConfigKey,
+) -> CargoResult<Vec<(ConfigKey, String)>> {
+    if !gctx.cli_unstable().cross_toolchains {
+        return Ok(Vec::new());
+    }
+    let parts: Vec<_> = key.parts().collect();
+    let (triples, field) = match parts.as_slice() {
+        ["target"] => {
+            let triples = match &gctx.build_config()?.target {
+                Some(target) => target.values(gctx)?,
+                None => Vec::new(),
+            };
+            (triples, None)
+        }
+        ["target", triple] => (vec![triple.to_string()], None),
+        ["target", triple, field @ ("linker" | "runner")] => {
+            (vec![triple.to_string()], Some(*field))
+        }
+        _ => return Ok(Vec::new()),
+    };
+    let triples: Vec<_> = triples
+        .into_iter()
+        .filter(|triple| !triple.starts_with("cfg(") && !triple.ends_with(".json"))
+        .collect();
+    if triples.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let host = &gctx.load_global_rustc(None)?.host;
+    let mut discovered = Vec::new();
+    for triple in triples.iter().filter(|triple| *triple != host.as_str()) {
+        let configured = gctx.target_cfg_triple(triple)?;
+        let toolchain = gctx.discovered_toolchain(triple);
+        let mut key = ConfigKey::from_str("target");
+        key.push(triple);
+        if let (None, Some(linker), None | Some("linker")) =
+            (&configured.linker, &toolchain.linker, field)
+        {
+            key.push("linker");
+            let val = toml_edit::Value::from(linker.display().to_string());
+            discovered.push((key.clone(), val.to_string()));
+            key.pop();
+        }
+        if let (None, Some((runner, args)), None | Some("runner")) =
+            (&configured.runner, &toolchain.runner, field)
+        {
+            key.push("runner");
+            let val: toml_edit::Array = std::iter::once(runner.display().to_string())
+                .chain(args.iter().cloned())
+                .collect();
+            discovered.push((key.clone(), val.to_string()));
+            key.pop();
+        }
+    }
+    Ok(discovered)
+}
+
 fn print_toml(gctx: &GlobalContext, opts: &GetOptions<'_>, key: &ConfigKey, cv: &CV) {
     let origin = |def: &Definition| -> String {
         if !opts.show_origin {
@@ -172,6 +246,16 @@ fn print_toml_env(gctx: &GlobalContext, env: &[(&str, &str)]) {
     }
 }
 
+fn print_toml_discovered(gctx: &GlobalContext, discovered: &[(ConfigKey, String)]) {
+    drop_println!(
+        gctx,
+        "# The following values were discovered on PATH and are used when not configured."
+    );
+    for (key, val) in discovered {
+        drop_println!(gctx, "# {} = {}", key, val);
+    }
+}
+
 fn print_json_env(gctx: &GlobalContext, env: &[(&str, &str)]) {
     drop_eprintln!(
         gctx,
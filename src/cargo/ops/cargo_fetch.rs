@@ -1,16 +1,25 @@
 use crate::core::compiler::standard_lib;
 use crate::core::compiler::{BuildConfig, CompileMode, RustcTargetData};
-use crate::core::{PackageSet, Resolve, Workspace};
+use crate::core::{PackageId, PackageSet, Resolve, SourceId, Workspace};
 use crate::ops;
+use crate::sources::registry::snapshot;
+use crate::sources::SourceConfigMap;
+use crate::util::cache_lock::CacheLockMode;
 use crate::util::context::JobsConfig;
 use crate::util::CargoResult;
 use crate::util::GlobalContext;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 pub struct FetchOptions<'a> {
     pub gctx: &'a GlobalContext,
     /// The target arch triple to fetch dependencies for
     pub targets: Vec<String>,
+    /// Directory to export the registry packages of the lockfile to
+    pub export_index: Option<PathBuf>,
+    /// Directory to import the registry packages of the lockfile from before
+    /// fetching
+    pub import_index: Option<PathBuf>,
 }
 
 /// Executes `cargo fetch`.
@@ -19,6 +28,9 @@ pub fn fetch<'a>(
     options: &FetchOptions<'a>,
 ) -> CargoResult<(Resolve, PackageSet<'a>)> {
     ws.emit_warnings()?;
+    if let Some(src) = &options.import_index {
+        import_index(ws, src)?;
+    }
     let dry_run = false;
     let (mut packages, resolve) = ops::resolve_ws(ws, dry_run)?;
 
@@ -71,8 +83,82 @@ pub fn fetch<'a>(
         packages.add_set(std_package_set);
     }
 
-    packages.get_many(to_download)?;
+    packages.get_many(to_download.iter().copied())?;
+    if let Some(dst) = &options.export_index {
+        export_index(gctx, &to_download, dst)?;
+    }
     crate::core::gc::auto_gc(gctx);
 
     Ok((resolve, packages))
 }
+
+/// Exports the index entries and `.crate` files of the downloaded registry
+/// packages into a snapshot at `dst`.
+fn export_index(gctx: &GlobalContext, pkgs: &[PackageId], dst: &Path) -> CargoResult<()> {
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::Shared)?;
+    let registries = group_by_registry(gctx, pkgs.iter().copied())?;
+    let mut num_pkgs = 0;
+    for (source_id, pkgs) in &registries {
+        snapshot::export(gctx, *source_id, pkgs, dst)?;
+        num_pkgs += pkgs.len();
+    }
+    let plural = if num_pkgs == 1 { "" } else { "s" };
+    gctx.shell().status(
+        "Exported",
+        format!("{num_pkgs} package{plural} to `{}`", dst.display()),
+    )
+}
+
+/// Seeds the registry caches with the packages of the lockfile found in the
+/// snapshot at `src`.
+fn import_index(ws: &Workspace<'_>, src: &Path) -> CargoResult<()> {
+    let gctx = ws.gctx();
+    let Some(resolve) = ops::load_pkg_lockfile(ws)? else {
+        anyhow::bail!("a Cargo.lock must exist to import an index snapshot");
+    };
+    if !src.is_dir() {
+        anyhow::bail!("index snapshot `{}` does not exist", src.display());
+    }
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    let registries = group_by_registry(gctx, resolve.iter())?;
+    let mut num_pkgs = 0;
+    for (source_id, pkgs) in &registries {
+        num_pkgs += snapshot::import(gctx, *source_id, pkgs, resolve.checksums(), src)?;
+    }
+    let plural = if num_pkgs == 1 { "" } else { "s" };
+    gctx.shell().status(
+        "Imported",
+        format!("{num_pkgs} package{plural} from `{}`", src.display()),
+    )
+}
+
+/// Groups the registry packages by the remote registry they are downloaded
+/// from, taking source replacement into account.
+fn group_by_registry(
+    gctx: &GlobalContext,
+    pkgs: impl Iterator<Item = PackageId>,
+) -> CargoResult<BTreeMap<SourceId, Vec<PackageId>>> {
+    let source_config = SourceConfigMap::new(gctx)?;
+    let mut replacements = HashMap::new();
+    let mut registries: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for pkg in pkgs {
+        let source_id = pkg.source_id();
+        if !source_id.is_registry() {
+            continue;
+        }
+        let replaced = match replacements.get(&source_id) {
+            Some(replaced) => *replaced,
+            None => {
+                let replaced = source_config
+                    .load(source_id, &HashSet::new())?
+                    .replaced_source_id();
+                replacements.insert(source_id, replaced);
+                replaced
+            }
+        };
+        if replaced.is_remote_registry() {
+            registries.entry(replaced).or_default().push(pkg);
+        }
+    }
+    Ok(registries)
+}
@@ -46,6 +46,7 @@ struct InstallablePackage<'gctx> {
     vers: Option<VersionReq>,
     force: bool,
     no_track: bool,
+    debug_symbols: bool,
     pkg: Package,
     ws: Workspace<'gctx>,
     rustc: Rustc,
@@ -65,6 +66,7 @@ impl<'gctx> InstallablePackage<'gctx> {
         original_opts: &ops::CompileOptions,
         force: bool,
         no_track: bool,
+        debug_symbols: bool,
         needs_update_if_source_is_index: bool,
         current_rust_version: Option<&PartialVersion>,
         lockfile_path: Option<&Path>,
@@ -200,7 +202,12 @@ impl<'gctx> InstallablePackage<'gctx> {
                 }
             // If we're installing in --locked mode and there's no `Cargo.lock` published
             // ie. the bin was published before https://github.com/rust-lang/cargo/pull/7026
-            } else if !ws.root().join("Cargo.lock").exists() {
+            } else if !ws
+                .lock_root()
+                .as_path_unlocked()
+                .join(ws.lockfile_name())
+                .exists()
+            {
                 gctx.shell().warn(format!(
                     "no Cargo.lock file published in {}",
                     pkg.to_string()
@@ -264,6 +271,7 @@ impl<'gctx> InstallablePackage<'gctx> {
             vers: vers.cloned(),
             force,
             no_track,
+            debug_symbols,
             pkg,
             ws,
             rustc,
@@ -274,7 +282,7 @@ impl<'gctx> InstallablePackage<'gctx> {
         // of concurrent installs.
         if no_track {
             // Check for conflicts.
-            ip.no_track_duplicates(&dst)?;
+            ip.no_track_duplicates(&dst, &BTreeSet::new())?;
         } else if is_installed(
             &ip.pkg, gctx, &ip.opts, &ip.rustc, &ip.target, &ip.root, &dst, force,
         )? {
@@ -289,11 +297,16 @@ impl<'gctx> InstallablePackage<'gctx> {
         Ok(Some(ip))
     }
 
-    fn no_track_duplicates(&self, dst: &Path) -> CargoResult<BTreeMap<String, Option<PackageId>>> {
+    fn no_track_duplicates(
+        &self,
+        dst: &Path,
+        debuginfo: &BTreeSet<String>,
+    ) -> CargoResult<BTreeMap<String, Option<PackageId>>> {
         // Helper for --no-track flag to make sure it doesn't overwrite anything.
         let duplicates: BTreeMap<String, Option<PackageId>> =
             exe_names(&self.pkg, &self.opts.filter)
                 .into_iter()
+                .chain(debuginfo.iter().cloned())
                 .filter(|name| dst.join(name).exists())
                 .map(|name| (name, None))
                 .collect();
@@ -301,8 +314,13 @@ impl<'gctx> InstallablePackage<'gctx> {
             let mut msg: Vec<String> = duplicates
                 .iter()
                 .map(|(name, _)| {
+                    let kind = if is_debuginfo_name(name) {
+                        "debuginfo"
+                    } else {
+                        "binary"
+                    };
                     format!(
-                        "binary `{}` already exists in destination `{}`",
+                        "{kind} `{}` already exists in destination `{}`",
                         name,
                         dst.join(name).to_string_lossy()
                     )
@@ -414,13 +432,38 @@ impl<'gctx> InstallablePackage<'gctx> {
         // This is primarily to make testing easier.
         binaries.sort_unstable();
 
+        // Split debuginfo is only installed on request, and only for the
+        // binaries being installed (not cdylibs).
+        let mut debuginfo: Vec<(&str, &Path)> = Vec::new();
+        if self.debug_symbols {
+            for UnitOutput { unit, path, .. } in compile.debuginfo.iter() {
+                if !unit.target.is_executable() {
+                    continue;
+                }
+                let name = path.file_name().unwrap();
+                let Some(s) = name.to_str() else {
+                    bail!(
+                        "Debuginfo `{:?}` name can't be serialized into string",
+                        name
+                    )
+                };
+                debuginfo.push((s, path.as_ref()));
+            }
+            debuginfo.sort_unstable();
+        }
+
+        let debuginfo_names: BTreeSet<String> = debuginfo
+            .iter()
+            .map(|&(name, _)| name.to_string())
+            .collect();
         let (tracker, duplicates) = if self.no_track {
-            (None, self.no_track_duplicates(&dst)?)
+            (None, self.no_track_duplicates(&dst, &debuginfo_names)?)
         } else {
             let tracker = InstallTracker::load(self.gctx, &self.root)?;
             let (_freshness, duplicates) = tracker.check_upgrade(
                 &dst,
                 &self.pkg,
+                &debuginfo_names,
                 self.force,
                 &self.opts,
                 &self.target,
@@ -446,18 +489,25 @@ impl<'gctx> InstallablePackage<'gctx> {
                 }
                 paths::copy(src, &dst)?;
             }
+            for &(name, src) in debuginfo.iter() {
+                copy_debuginfo(src, &staging_dir.path().join(name))?;
+            }
         }
 
         let (to_replace, to_install): (Vec<&str>, Vec<&str>) = binaries
             .iter()
             .map(|&(bin, _)| bin)
             .partition(|&bin| duplicates.contains_key(bin));
+        let (debuginfo_to_replace, debuginfo_to_install): (Vec<&str>, Vec<&str>) = debuginfo
+            .iter()
+            .map(|&(name, _)| name)
+            .partition(|&name| duplicates.contains_key(name));
 
         let mut installed = Transaction { bins: Vec::new() };
         let mut successful_bins = BTreeSet::new();
 
         // Move the temporary copies into `dst` starting with new binaries.
-        for bin in to_install.iter() {
+        for bin in to_install.iter().chain(debuginfo_to_install.iter()) {
             let src = staging_dir.path().join(bin);
             let dst = dst.join(bin);
             self.gctx.shell().status("Installing", dst.display())?;
@@ -474,11 +524,19 @@ impl<'gctx> InstallablePackage<'gctx> {
         // up until after updating metadata.
         let replace_result = {
             let mut try_install = || -> CargoResult<()> {
-                for &bin in to_replace.iter() {
+                for &bin in to_replace.iter().chain(debuginfo_to_replace.iter()) {
                     let src = staging_dir.path().join(bin);
                     let dst = dst.join(bin);
                     self.gctx.shell().status("Replacing", dst.display())?;
                     if !dry_run {
+                        // A debuginfo bundle directory can't be renamed over,
+                        // so move the old one out of the way into staging.
+                        if dst.is_dir() {
+                            let old = staging_dir.path().join(format!("{bin}.old"));
+                            fs::rename(&dst, &old).with_context(|| {
+                                format!("failed to move `{}` to `{}`", dst.display(), old.display())
+                            })?;
+                        }
                         fs::rename(&src, &dst).with_context(|| {
                             format!("failed to move `{}` to `{}`", src.display(), dst.display())
                         })?;
@@ -505,6 +563,7 @@ impl<'gctx> InstallablePackage<'gctx> {
                 &mut tracker,
                 &duplicates,
                 &self.pkg,
+                &debuginfo_names,
                 &dst,
                 dry_run,
             ) {
@@ -520,6 +579,10 @@ impl<'gctx> InstallablePackage<'gctx> {
             }
         }
 
+        // Debuginfo is tracked along with the binaries, but only the
+        // executables are worth reporting.
+        successful_bins.retain(|bin| !debuginfo_names.contains(bin));
+
         // Reaching here means all actions have succeeded. Clean up.
         installed.success();
         if needs_cleanup {
@@ -566,7 +629,11 @@ impl<'gctx> InstallablePackage<'gctx> {
             }
             // Invert the duplicate map.
             let mut pkg_map = BTreeMap::new();
-            for (bin_name, opt_pkg_id) in &duplicates {
+            // Debuginfo is only reported along with its binary.
+            for (bin_name, opt_pkg_id) in duplicates
+                .iter()
+                .filter(|(name, _)| !debuginfo_names.contains(*name))
+            {
                 let key =
                     opt_pkg_id.map_or_else(|| "unknown".to_string(), |pkg_id| pkg_id.to_string());
                 pkg_map.entry(key).or_insert_with(Vec::new).push(bin_name);
@@ -587,7 +654,12 @@ impl<'gctx> InstallablePackage<'gctx> {
     }
 
     fn check_yanked_install(&self) -> CargoResult<()> {
-        if self.ws.ignore_lock() || !self.ws.root().join("Cargo.lock").exists() {
+        let lockfile_path = self
+            .ws
+            .lock_root()
+            .as_path_unlocked()
+            .join(self.ws.lockfile_name());
+        if self.ws.ignore_lock() || !lockfile_path.exists() {
             return Ok(());
         }
         // It would be best if `source` could be passed in here to avoid a
@@ -604,6 +676,25 @@ impl<'gctx> InstallablePackage<'gctx> {
     }
 }
 
+/// Copies a split debuginfo file, or a bundle directory of them such as a
+/// `.dSYM`, following the symlink Cargo leaves in the target directory.
+fn copy_debuginfo(src: &Path, dst: &Path) -> CargoResult<()> {
+    if !src.is_dir() {
+        paths::copy(src, dst)?;
+        return Ok(());
+    }
+    for entry in walkdir::WalkDir::new(src).follow_links(true) {
+        let entry = entry?;
+        let path = dst.join(entry.path().strip_prefix(src).unwrap());
+        if entry.file_type().is_dir() {
+            paths::create_dir_all(&path)?;
+        } else {
+            paths::copy(entry.path(), &path)?;
+        }
+    }
+    Ok(())
+}
+
 fn make_warning_about_missing_features(binaries: &[&Target]) -> String {
     let max_targets_listed = 7;
     let target_features_message = binaries
@@ -652,6 +743,7 @@ pub fn install(
     opts: &ops::CompileOptions,
     force: bool,
     no_track: bool,
+    debug_symbols: bool,
     dry_run: bool,
     lockfile_path: Option<&Path>,
 ) -> CargoResult<()> {
@@ -683,6 +775,7 @@ pub fn install(
             opts,
             force,
             no_track,
+            debug_symbols,
             true,
             current_rust_version.as_ref(),
             lockfile_path,
@@ -715,6 +808,7 @@ pub fn install(
                     opts,
                     force,
                     no_track,
+                    debug_symbols,
                     !did_update,
                     current_rust_version.as_ref(),
                     lockfile_path,
@@ -808,8 +902,15 @@ fn is_installed(
     force: bool,
 ) -> CargoResult<bool> {
     let tracker = InstallTracker::load(gctx, root)?;
-    let (freshness, _duplicates) =
-        tracker.check_upgrade(dst, pkg, force, opts, target, &rustc.verbose_version)?;
+    let (freshness, _duplicates) = tracker.check_upgrade(
+        dst,
+        pkg,
+        &BTreeSet::new(),
+        force,
+        opts,
+        target,
+        &rustc.verbose_version,
+    )?;
     Ok(freshness.is_fresh())
 }
 
@@ -864,6 +965,7 @@ fn make_ws_rustc_target<'gctx>(
     };
     ws.set_ignore_lock(gctx.lock_update_allowed());
     ws.set_requested_lockfile_path(lockfile_path.map(|p| p.to_path_buf()));
+    ws.set_resolve_errors_as_json(opts.build_config.emit_json());
     // if --lockfile-path is set, imply --locked
     if ws.requested_lockfile_path().is_some() {
         ws.set_ignore_lock(false);
@@ -892,18 +994,20 @@ pub fn install_list(dst: Option<&str>, gctx: &GlobalContext) -> CargoResult<()>
     Ok(())
 }
 
-/// Removes executables that are no longer part of a package that was
-/// previously installed.
+/// Removes executables, and their debuginfo, that are no longer part of a
+/// package that was previously installed.
 fn remove_orphaned_bins(
     ws: &Workspace<'_>,
     tracker: &mut InstallTracker,
     duplicates: &BTreeMap<String, Option<PackageId>>,
     pkg: &Package,
+    debuginfo: &BTreeSet<String>,
     dst: &Path,
     dry_run: bool,
 ) -> CargoResult<()> {
     let filter = ops::CompileFilter::new_all_targets();
-    let all_self_names = exe_names(pkg, &filter);
+    let mut all_self_names = exe_names(pkg, &filter);
+    all_self_names.extend(debuginfo.iter().cloned());
     let mut to_remove: HashMap<PackageId, BTreeSet<String>> = HashMap::new();
     // For each package that we stomped on.
     for other_pkg in duplicates.values().flatten() {
@@ -928,19 +1032,28 @@ fn remove_orphaned_bins(
     for (old_pkg, bins) in to_remove {
         tracker.remove(old_pkg, &bins);
         for bin in bins {
-            let full_path = dst.join(bin);
+            let full_path = dst.join(&bin);
             if full_path.exists() {
+                let kind = if is_debuginfo_name(&bin) {
+                    "debuginfo"
+                } else {
+                    "executable"
+                };
                 ws.gctx().shell().status(
                     "Removing",
                     format!(
-                        "executable `{}` from previous version {}",
+                        "{kind} `{}` from previous version {}",
                         full_path.display(),
                         old_pkg
                     ),
                 )?;
                 if !dry_run {
-                    paths::remove_file(&full_path)
-                        .with_context(|| format!("failed to remove {:?}", full_path))?;
+                    if full_path.is_dir() {
+                        paths::remove_dir_all(&full_path)
+                    } else {
+                        paths::remove_file(&full_path)
+                    }
+                    .with_context(|| format!("failed to remove {:?}", full_path))?;
                 }
             }
         }
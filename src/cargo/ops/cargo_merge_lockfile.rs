@@ -0,0 +1,85 @@
+//! Implementation of `cargo merge-lockfile`, a git merge driver for `Cargo.lock`.
+//!
+//! Git hands a merge driver three files: the common ancestor (`%O`), our
+//! version (`%A`) and their version (`%B`), and expects the result to be
+//! written to our version. The package sets of the three lock files are
+//! merged with [`EncodableResolve::merge`] and the result is handed to the
+//! resolver as the previous resolve, with every package locked except those
+//! both sides changed in different ways. That way only the disagreements are
+//! resolved again and everything else stays exactly as one of the sides had
+//! it.
+
+use std::path::Path;
+
+use anyhow::Context as _;
+
+use crate::core::resolver::features::{CliFeatures, HasDevUnits};
+use crate::core::resolver::EncodableResolve;
+use crate::core::{PackageId, Workspace};
+use crate::ops;
+use crate::util::cache_lock::CacheLockMode;
+use crate::util::errors::CargoResult;
+
+pub struct MergeLockfileOptions<'a> {
+    /// The lock file of the common ancestor.
+    pub base: &'a Path,
+    /// Our lock file, which is overwritten with the result.
+    pub ours: &'a Path,
+    /// Their lock file.
+    pub theirs: &'a Path,
+}
+
+pub fn merge_lockfile(ws: &Workspace<'_>, opts: &MergeLockfileOptions<'_>) -> CargoResult<()> {
+    let gctx = ws.gctx();
+    let base = read_lockfile(opts.base)?;
+    let ours = read_lockfile(opts.ours)?;
+    let theirs = read_lockfile(opts.theirs)?;
+    let (merged, conflicts) = EncodableResolve::merge(base, ours, theirs);
+
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+
+    let merged_str = toml::to_string(&merged)?;
+    let previous = merged
+        .into_resolve(&merged_str, ws)
+        .context("failed to interpret the merged lock file")?;
+
+    let mut registry = ws.package_registry()?;
+    let keep = |id: &PackageId| !conflicts.contains(id.name().as_str());
+    let resolve = ops::resolve_with_previous(
+        &mut registry,
+        ws,
+        &CliFeatures::new_all(true),
+        HasDevUnits::Yes,
+        Some(&previous),
+        Some(&keep),
+        &[],
+        true,
+    )?;
+
+    let out = ops::resolve_to_string(ws, &resolve)?;
+    cargo_util::paths::write(opts.ours, out.as_bytes())?;
+
+    let mut shell = gctx.shell();
+    if conflicts.is_empty() {
+        shell.status("Merged", opts.ours.display())?;
+    } else {
+        let names = conflicts
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        shell.status(
+            "Merged",
+            format!("{}, re-resolving {names}", opts.ours.display()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Reads one side of the merge. Git passes an empty file for the ancestor
+/// when both sides added `Cargo.lock` independently.
+fn read_lockfile(path: &Path) -> CargoResult<EncodableResolve> {
+    let contents = cargo_util::paths::read(path)?;
+    toml::from_str(&contents)
+        .with_context(|| format!("failed to parse lock file at: {}", path.display()))
+}
@@ -85,6 +85,10 @@ struct MetadataResolveNode {
     dependencies: Vec<PackageIdSpec>,
     deps: Vec<Dep>,
     features: Vec<InternedString>,
+    /// Workspace members whose `rust-version` is older than this package's,
+    /// with `-Zmsrv-per-member`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rust_version_exceeds: Vec<PackageIdSpec>,
 }
 
 #[derive(Serialize)]
@@ -177,6 +181,17 @@ fn build_resolve_graph(
             &requested_kinds,
         )?;
     }
+    if ws.gctx().cli_unstable().msrv_per_member {
+        for (pkg_id, members) in ops::member_rust_versions(ws, &ws_resolve.targeted_resolve) {
+            let (Some(node), Some(pkg)) = (node_map.get_mut(&pkg_id), package_map.get(&pkg_id))
+            else {
+                continue;
+            };
+            node.rust_version_exceeds = ops::rust_version_exceeded(pkg.rust_version(), &members)
+                .map(|member_id| member_id.to_spec())
+                .collect();
+        }
+    }
     // Get a Vec of Packages.
     let actual_packages = package_map
         .into_iter()
@@ -340,6 +355,7 @@ fn build_resolve_graph_r(
         dependencies: to_visit.iter().map(|id| id.to_spec()).collect(),
         deps,
         features,
+        rust_version_exceeds: Vec::new(),
     };
     node_map.insert(pkg_id, node);
     for dep_id in to_visit {
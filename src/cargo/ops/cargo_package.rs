@@ -13,7 +13,7 @@ use crate::core::resolver::CliFeatures;
 use crate::core::resolver::HasDevUnits;
 use crate::core::{Feature, PackageIdSpecQuery, Shell, Verbosity, Workspace};
 use crate::core::{Package, PackageId, PackageSet, Resolve, SourceId};
-use crate::ops::lockfile::LOCKFILE_NAME;
+use crate::ops::lockfile::{is_lockfile_name, LOCKFILE_NAME};
 use crate::ops::registry::{infer_registry, RegistryOrIndex};
 use crate::sources::registry::index::{IndexPackage, RegistryDependency};
 use crate::sources::{PathSource, CRATES_IO_REGISTRY};
@@ -200,7 +200,7 @@ fn do_package<'a>(
     if ws
         .lock_root()
         .as_path_unlocked()
-        .join(LOCKFILE_NAME)
+        .join(ws.lockfile_name())
         .exists()
     {
         // Make sure the Cargo.lock is up-to-date and valid.
@@ -415,7 +415,9 @@ fn build_ar_list(
             anyhow::format_err!("non-utf8 path in source directory: {}", rel_path.display())
         })?;
         match rel_str {
-            "Cargo.lock" => continue,
+            // Named lock files stay local, and a `Cargo.lock` is generated
+            // below if needed.
+            name if is_lockfile_name(name) => continue,
             VCS_INFO_FILE | ORIGINAL_MANIFEST_FILE => anyhow::bail!(
                 "invalid inclusion of reserved file name {} in package source",
                 rel_str
@@ -459,8 +461,10 @@ fn build_ar_list(
         ))?;
     }
 
+    // The packaged lock file is always `Cargo.lock`, whichever lock file the
+    // workspace uses.
     if pkg.include_lockfile() {
-        let rel_str = "Cargo.lock";
+        let rel_str = LOCKFILE_NAME;
         result
             .entry(UncasedAscii::new(rel_str))
             .or_insert_with(Vec::new)
@@ -855,8 +859,10 @@ fn check_repo_state(
         let workdir = repo.workdir().unwrap();
         let this_dirty = repo_statuses.iter().filter_map(|entry| {
             let path = entry.path().expect("valid utf-8 path");
-            if path.ends_with("Cargo.lock") && entry.status() == git2::Status::IGNORED {
-                // It is OK to include Cargo.lock even if it is ignored.
+            let file_name = path.rsplit('/').next().unwrap_or(path);
+            if is_lockfile_name(file_name) && entry.status() == git2::Status::IGNORED {
+                // It is OK to include Cargo.lock even if it is ignored, and
+                // named lock files aren't packaged.
                 return None;
             }
             // Use an absolute path, so that comparing paths is easier
@@ -1384,6 +1390,7 @@ impl<'a> TmpRegistry<'a> {
             yanked: None,
             links: new_crate.links.map(|x| x.into()),
             rust_version: None,
+            pubtime: None,
             v: Some(2),
         })?;
 
@@ -1,4 +1,6 @@
-use crate::core::compiler::{Compilation, CompileKind, Doctest, Metadata, Unit, UnitOutput};
+use crate::core::compiler::{
+    BuildConfig, Compilation, CompileKind, CompileMode, Doctest, Metadata, Unit, UnitOutput,
+};
 use crate::core::profiles::PanicStrategy;
 use crate::core::shell::ColorChoice;
 use crate::core::shell::Verbosity;
@@ -69,7 +71,7 @@ pub fn run_tests(ws: &Workspace<'_>, options: &TestOptions, test_args: &[&str])
 
     if options.no_run {
         if !options.compile_opts.build_config.emit_json() {
-            display_no_run_information(ws, test_args, &compilation, "unittests")?;
+            display_no_run_information(ws, options, test_args, &compilation, "unittests")?;
         }
         return Ok(());
     }
@@ -89,7 +91,7 @@ pub fn run_benches(ws: &Workspace<'_>, options: &TestOptions, args: &[&str]) ->
 
     if options.no_run {
         if !options.compile_opts.build_config.emit_json() {
-            display_no_run_information(ws, args, &compilation, "benches")?;
+            display_no_run_information(ws, options, args, &compilation, "benches")?;
         }
         return Ok(());
     }
@@ -107,6 +109,32 @@ fn compile_tests<'a>(ws: &Workspace<'a>, options: &TestOptions) -> CargoResult<C
     Ok(compilation)
 }
 
+/// Returns the test executables in the order they should be run.
+///
+/// With `-Zmultitarget-summary` the tests of each requested `--target` are
+/// run together, in the order the targets were passed.
+fn test_outputs<'a>(
+    compilation: &'a Compilation<'_>,
+    build_config: &'a BuildConfig,
+) -> Vec<&'a UnitOutput> {
+    if !build_config.multitarget_summary {
+        return compilation.tests.iter().collect();
+    }
+    let requested_kinds = &build_config.requested_kinds;
+    requested_kinds
+        .iter()
+        .flat_map(|kind| compilation.outputs_for_kind(*kind))
+        .filter(|output| output.unit.mode == CompileMode::Test)
+        // Units with a `forced-target` are not grouped under any `--target`.
+        .chain(
+            compilation
+                .tests
+                .iter()
+                .filter(|output| !requested_kinds.contains(&output.unit.kind)),
+        )
+        .collect()
+}
+
 /// Runs the unit and integration tests of a package.
 ///
 /// Returns a `Vec` of tests that failed when `--no-fail-fast` is used.
@@ -126,7 +154,7 @@ fn run_unit_tests(
         unit,
         path,
         script_meta,
-    } in compilation.tests.iter()
+    } in test_outputs(compilation, &options.compile_opts.build_config)
     {
         let (exe_display, mut cmd) = cmd_builds(
             gctx,
@@ -314,6 +342,7 @@ fn run_doc_tests(
 /// This is used when `cargo test --no-run` is used.
 fn display_no_run_information(
     ws: &Workspace<'_>,
+    options: &TestOptions,
     test_args: &[&str],
     compilation: &Compilation<'_>,
     exec_type: &str,
@@ -324,7 +353,7 @@ fn display_no_run_information(
         unit,
         path,
         script_meta,
-    } in compilation.tests.iter()
+    } in test_outputs(compilation, &options.compile_opts.build_config)
     {
         let (exe_display, cmd) = cmd_builds(
             gctx,
@@ -145,7 +145,12 @@ fn uninstall_pkgid(
 
     for bin in to_remove {
         let bin_path = dst.join(&bin);
-        gctx.shell().status("Removing", bin_path.display())?;
+        if is_debuginfo_name(&bin) {
+            gctx.shell()
+                .status("Removing", format!("{} (debuginfo)", bin_path.display()))?;
+        } else {
+            gctx.shell().status("Removing", bin_path.display())?;
+        }
         tracker.remove_bin_then_save(pkgid, &bin, &bin_path)?;
     }
 
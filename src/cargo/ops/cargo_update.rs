@@ -7,9 +7,10 @@ use crate::core::{PackageId, PackageIdSpec, PackageIdSpecQuery};
 use crate::core::{Resolve, SourceId, Workspace};
 use crate::ops;
 use crate::sources::source::QueryKind;
-use crate::sources::IndexSummary;
+use crate::sources::{GitSource, IndexSummary};
 use crate::util::cache_lock::CacheLockMode;
 use crate::util::context::GlobalContext;
+use crate::util::interning::InternedString;
 use crate::util::toml_mut::dependency::{MaybeWorkspace, Source};
 use crate::util::toml_mut::manifest::LocalManifest;
 use crate::util::toml_mut::upgrade::upgrade_requirement;
@@ -20,8 +21,10 @@ use cargo_util_schemas::core::PartialVersion;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use semver::{Op, Version, VersionReq};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
 use tracing::{debug, trace};
 
 pub type UpgradeMap = HashMap<(String, SourceId), Version>;
@@ -33,6 +36,17 @@ pub struct UpdateOptions<'a> {
     pub recursive: bool,
     pub dry_run: bool,
     pub workspace: bool,
+    /// Report the changes by SemVer class instead of updating the lockfile.
+    pub report: Option<UpdateReportFormat>,
+    /// An advisory file listing vulnerable versions to update away from.
+    pub advisories: Option<&'a Path>,
+}
+
+/// The output format of `cargo update --report`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UpdateReportFormat {
+    Human,
+    Json,
 }
 
 pub fn generate_lockfile(ws: &Workspace<'_>) -> CargoResult<()> {
@@ -68,8 +82,18 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
         .gctx()
         .acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
 
+    if let Some(path) = opts.advisories {
+        return fix_advisories(ws, opts, path);
+    }
+
     let previous_resolve = match ops::load_pkg_lockfile(ws)? {
         Some(resolve) => resolve,
+        None if opts.report.is_some() => {
+            anyhow::bail!(
+                "there is no `Cargo.lock` to report changes against\n\
+                 help: run `cargo generate-lockfile` to create one"
+            )
+        }
         None => {
             match opts.precise {
                 None => return generate_lockfile(ws),
@@ -171,7 +195,7 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
 
     let keep = |p: &PackageId| !to_avoid_sources.contains(&p.source_id()) && !to_avoid.contains(p);
 
-    let mut resolve = ops::resolve_with_previous(
+    let resolve = ops::resolve_with_previous(
         &mut registry,
         ws,
         &CliFeatures::new_all(true),
@@ -182,12 +206,29 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
         true,
     )?;
 
+    finish_update(ws, opts, &previous_resolve, resolve, &mut registry)
+}
+
+/// Reports or writes the result of an update.
+fn finish_update(
+    ws: &Workspace<'_>,
+    opts: &UpdateOptions<'_>,
+    previous_resolve: &Resolve,
+    mut resolve: Resolve,
+    registry: &mut PackageRegistry<'_>,
+) -> CargoResult<()> {
+    if let Some(format) = opts.report {
+        // A report never touches the lockfile.
+        return print_update_report(ws, previous_resolve, &resolve, format);
+    }
+
     print_lockfile_updates(
         ws,
-        &previous_resolve,
+        previous_resolve,
         &resolve,
         opts.precise.is_some(),
-        &mut registry,
+        opts.dry_run,
+        registry,
     )?;
     if opts.dry_run {
         opts.gctx
@@ -199,6 +240,137 @@ pub fn update_lockfile(ws: &Workspace<'_>, opts: &UpdateOptions<'_>) -> CargoRes
     Ok(())
 }
 
+/// A local advisory database, as read by `cargo update --advisory-fix`.
+#[derive(Deserialize)]
+struct AdvisoryFile {
+    #[serde(default)]
+    advisory: Vec<Advisory>,
+}
+
+#[derive(Deserialize)]
+struct Advisory {
+    id: Option<String>,
+    package: InternedString,
+    /// The registry the package is published to, crates.io if not set.
+    registry: Option<String>,
+    /// The affected version ranges.
+    vulnerable: Vec<VersionReq>,
+}
+
+impl Advisory {
+    fn source_id(&self, gctx: &GlobalContext) -> CargoResult<SourceId> {
+        match &self.registry {
+            Some(registry) => SourceId::alt_registry(gctx, registry),
+            None => SourceId::crates_io(gctx),
+        }
+    }
+
+    fn affects(&self, source_id: SourceId, pkg_id: PackageId) -> bool {
+        pkg_id.name() == self.package
+            && pkg_id.source_id() == source_id
+            && self
+                .vulnerable
+                .iter()
+                .any(|req| req.matches(pkg_id.version()))
+    }
+}
+
+/// Updates the smallest set of locked packages that moves every package off
+/// the vulnerable versions listed in the advisory file at `path`.
+///
+/// At first only the affected packages are unlocked. If they can't move while
+/// everything else stays locked, the packages depending on them are unlocked
+/// too, one level at a time, up to the workspace members. If the resolver
+/// still fails at that point, only a manifest change can fix it.
+fn fix_advisories(ws: &Workspace<'_>, opts: &UpdateOptions<'_>, path: &Path) -> CargoResult<()> {
+    let contents = cargo_util::paths::read(path)?;
+    let advisories: AdvisoryFile = toml::from_str(&contents)
+        .with_context(|| format!("failed to parse advisory file at: {}", path.display()))?;
+    let advisories = advisories
+        .advisory
+        .into_iter()
+        .map(|advisory| Ok((advisory.source_id(opts.gctx)?, advisory)))
+        .collect::<CargoResult<Vec<_>>>()?;
+
+    let Some(previous_resolve) = ops::load_pkg_lockfile(ws)? else {
+        anyhow::bail!(
+            "there is no `Cargo.lock` to fix\n\
+             help: run `cargo generate-lockfile` to create one"
+        )
+    };
+
+    let is_member = |id: &PackageId| {
+        ws.members()
+            .any(|m| m.name() == id.name() && m.package_id().source_id() == id.source_id())
+    };
+    let mut unlocked = HashSet::new();
+    for id in previous_resolve.iter().filter(|id| !is_member(id)) {
+        for (_, advisory) in advisories.iter().filter(|(s, a)| a.affects(*s, id)) {
+            let msg = match &advisory.id {
+                Some(advisory_id) => format!("{id} is affected by {advisory_id}"),
+                None => format!("{id} is affected by an advisory"),
+            };
+            opts.gctx.shell().note(msg)?;
+            unlocked.insert(id);
+        }
+    }
+    if unlocked.is_empty() {
+        return opts
+            .gctx
+            .shell()
+            .note("no locked package is affected by the advisories");
+    }
+    let affected: BTreeSet<_> = unlocked.iter().map(|id| id.name()).collect();
+
+    loop {
+        let mut registry = ws.package_registry()?;
+        for (source_id, advisory) in &advisories {
+            for req in &advisory.vulnerable {
+                registry.exclude_versions(advisory.package, *source_id, req.clone());
+            }
+        }
+        registry.add_sources(unlocked.iter().map(|id| id.source_id().without_precise()))?;
+
+        let keep = |id: &PackageId| !unlocked.contains(id);
+        let err = match ops::resolve_with_previous(
+            &mut registry,
+            ws,
+            &CliFeatures::new_all(true),
+            HasDevUnits::Yes,
+            Some(&previous_resolve),
+            Some(&keep),
+            &[],
+            true,
+        ) {
+            Ok(resolve) => {
+                return finish_update(ws, opts, &previous_resolve, resolve, &mut registry)
+            }
+            Err(err) => err,
+        };
+
+        // Unlock the packages that depend on what we tried to move, as their
+        // requirements may be what held it back.
+        let dependents: Vec<_> = previous_resolve
+            .iter()
+            .filter(|id| !unlocked.contains(id) && !is_member(id))
+            .filter(|&id| {
+                previous_resolve
+                    .deps_not_replaced(id)
+                    .any(|(dep, _)| unlocked.contains(&dep))
+            })
+            .collect();
+        if dependents.is_empty() {
+            let names = affected.iter().map(|name| format!("`{name}`")).join(", ");
+            return Err(err.context(format!(
+                "cannot update {names} to a version outside the advisory ranges \
+                 without changing a manifest"
+            )));
+        }
+        debug!("advisory fix: also unlocking {dependents:?}");
+        unlocked.extend(dependents);
+    }
+}
+
 /// Prints lockfile change statuses.
 ///
 /// This would acquire the package-cache lock, as it may update the index to
@@ -623,6 +795,7 @@ fn print_lockfile_updates(
     previous_resolve: &Resolve,
     resolve: &Resolve,
     precise: bool,
+    dry_run: bool,
     registry: &mut PackageRegistry<'_>,
 ) -> CargoResult<()> {
     let mut changes = PackageChange::diff(ws, previous_resolve, resolve);
@@ -636,6 +809,8 @@ fn print_lockfile_updates(
         status_locking(ws, num_pkgs)?;
     }
     let mut unchanged_behind = 0;
+    // Packages of the same git repository share their log.
+    let mut git_logs_printed = HashSet::new();
     for change in changes.values() {
         let possibilities = if let Some(query) = change.alternatives_query() {
             loop {
@@ -656,13 +831,25 @@ fn print_lockfile_updates(
             | PackageChangeKind::Downgraded => {
                 let required_rust_version = report_required_rust_version(resolve, change);
                 let latest = report_latest(&possibilities, change);
-                let note = required_rust_version.or(latest).unwrap_or_default();
+                let mut note = required_rust_version.or(latest).unwrap_or_default();
+                let git_log = change.git_log(ws.gctx());
+                if let (Some(log), true) = (git_log.as_ref().filter(|log| !log.is_empty()), dry_run)
+                {
+                    // Nothing is written, so tell how far upstream moved.
+                    let plural = if log.len() == 1 { "" } else { "s" };
+                    note = format!(" ({} commit{plural} ahead)", log.len());
+                }
 
                 ws.gctx().shell().status_with_color(
                     change.kind.status(),
                     format!("{change}{note}"),
                     &change.kind.style(),
                 )?;
+                if let Some(log) = git_log {
+                    if git_logs_printed.insert(change.package_id.source_id()) {
+                        print_git_log(ws.gctx(), &log)?;
+                    }
+                }
             }
             PackageChangeKind::Removed => {
                 ws.gctx().shell().status_with_color(
@@ -707,6 +894,290 @@ fn print_lockfile_updates(
     Ok(())
 }
 
+/// Prints the summaries of the commits a git dependency moved by, below its
+/// status line.
+fn print_git_log(gctx: &GlobalContext, log: &[(git2::Oid, String)]) -> CargoResult<()> {
+    const MAX_COMMITS: usize = 10;
+
+    let mut shell = gctx.shell();
+    if shell.verbosity() == Verbosity::Quiet {
+        return Ok(());
+    }
+    let max = if shell.verbosity() == Verbosity::Verbose {
+        log.len()
+    } else {
+        MAX_COMMITS
+    };
+    for (oid, summary) in log.iter().take(max) {
+        writeln!(
+            shell.err(),
+            "{:>12} {} {summary}",
+            "",
+            &oid.to_string()[..8]
+        )?;
+    }
+    if log.len() > max {
+        writeln!(shell.err(), "{:>12} ... and {} more", "", log.len() - max)?;
+    }
+    Ok(())
+}
+
+/// Prints the lockfile changes grouped by [`SemverClass`], along with the
+/// workspace members depending on each changed package and the packages that
+/// end up with more versions in the dependency graph than before.
+fn print_update_report(
+    ws: &Workspace<'_>,
+    previous_resolve: &Resolve,
+    resolve: &Resolve,
+    format: UpdateReportFormat,
+) -> CargoResult<()> {
+    #[derive(Serialize)]
+    struct Report {
+        changes: Vec<ReportChange>,
+        duplicates: Vec<ReportDuplicate>,
+    }
+
+    #[derive(Serialize)]
+    struct ReportChange {
+        name: String,
+        source: String,
+        from: Option<String>,
+        to: Option<String>,
+        class: SemverClass,
+        members: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        git: Option<ReportGit>,
+        #[serde(skip)]
+        description: String,
+    }
+
+    /// How a git dependency moved between two revisions.
+    #[derive(Serialize)]
+    struct ReportGit {
+        from: String,
+        to: String,
+        /// How many commits `to` is ahead of `from`, if known.
+        ahead: Option<usize>,
+        commits: Vec<ReportCommit>,
+    }
+
+    #[derive(Serialize)]
+    struct ReportCommit {
+        id: String,
+        summary: String,
+    }
+
+    #[derive(Serialize)]
+    struct ReportDuplicate {
+        name: String,
+        versions: Vec<String>,
+    }
+
+    let previous_users = members_depending_on(ws, previous_resolve);
+    let users = members_depending_on(ws, resolve);
+
+    let mut changes = Vec::new();
+    for change in PackageChange::diff(ws, previous_resolve, resolve).into_values() {
+        let (from, to, class, users) = match change.kind {
+            PackageChangeKind::Unchanged => continue,
+            PackageChangeKind::Added => (None, Some(change.package_id), SemverClass::Added, &users),
+            PackageChangeKind::Removed => (
+                Some(change.package_id),
+                None,
+                SemverClass::Removed,
+                &previous_users,
+            ),
+            PackageChangeKind::Upgraded | PackageChangeKind::Downgraded => {
+                let previous_id = change
+                    .previous_id
+                    .expect("a changed package has a previous id");
+                let class =
+                    SemverClass::classify(previous_id.version(), change.package_id.version());
+                (Some(previous_id), Some(change.package_id), class, &users)
+            }
+        };
+        let git = from
+            .zip(to)
+            .and_then(|(from, to)| {
+                let from = from.source_id().precise_git_fragment()?;
+                let to = to.source_id().precise_git_fragment()?;
+                Some((from, to))
+            })
+            .map(|(from, to)| {
+                let log = change.git_log(ws.gctx());
+                ReportGit {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                    ahead: log.as_ref().map(|log| log.len()),
+                    commits: log
+                        .into_iter()
+                        .flatten()
+                        .map(|(oid, summary)| ReportCommit {
+                            id: oid.to_string(),
+                            summary,
+                        })
+                        .collect(),
+                }
+            });
+        let mut description = change.to_string();
+        if let Some(ahead) = git.as_ref().and_then(|git| git.ahead) {
+            let plural = if ahead == 1 { "" } else { "s" };
+            description.push_str(&format!(" ({ahead} commit{plural} ahead)"));
+        }
+        changes.push(ReportChange {
+            name: change.package_id.name().to_string(),
+            source: change.package_id.source_id().as_url().to_string(),
+            from: from.map(|id| id.version().to_string()),
+            to: to.map(|id| id.version().to_string()),
+            class,
+            members: users
+                .get(&change.package_id)
+                .into_iter()
+                .flatten()
+                .map(|name| name.to_string())
+                .collect(),
+            git,
+            description,
+        });
+    }
+    changes.sort_by_key(|change| change.class);
+
+    let count_versions = |resolve: &Resolve| {
+        let mut versions: BTreeMap<InternedString, Vec<&Version>> = BTreeMap::new();
+        for id in resolve.iter() {
+            versions.entry(id.name()).or_default().push(id.version());
+        }
+        versions
+    };
+    let previous_versions = count_versions(previous_resolve);
+    let duplicates: Vec<_> = count_versions(resolve)
+        .into_iter()
+        .filter(|(name, versions)| {
+            let previous = previous_versions.get(name).map_or(0, |v| v.len());
+            versions.len() > 1 && versions.len() > previous
+        })
+        .map(|(name, mut versions)| {
+            versions.sort();
+            ReportDuplicate {
+                name: name.to_string(),
+                versions: versions.iter().map(|v| v.to_string()).collect(),
+            }
+        })
+        .collect();
+
+    let report = Report {
+        changes,
+        duplicates,
+    };
+    let mut shell = ws.gctx().shell();
+    match format {
+        UpdateReportFormat::Json => shell.print_json(&report)?,
+        UpdateReportFormat::Human => {
+            let out = shell.out();
+            if report.changes.is_empty() {
+                writeln!(out, "no changes")?;
+            }
+            for (class, changes) in &report.changes.iter().chunk_by(|change| change.class) {
+                let changes: Vec<_> = changes.collect();
+                writeln!(out, "{} ({}):", class.as_str(), changes.len())?;
+                for change in changes {
+                    write!(out, "  {}", change.description)?;
+                    if !change.members.is_empty() {
+                        write!(out, " (used by {})", change.members.join(", "))?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+            if !report.duplicates.is_empty() {
+                writeln!(out, "new duplicates ({}):", report.duplicates.len())?;
+                for duplicate in &report.duplicates {
+                    let versions = duplicate.versions.iter().map(|v| format!("v{v}"));
+                    writeln!(out, "  {} {}", duplicate.name, versions.format(", "))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps every package in `resolve` to the names of the workspace members
+/// that depend on it, directly or not.
+fn members_depending_on(
+    ws: &Workspace<'_>,
+    resolve: &Resolve,
+) -> HashMap<PackageId, BTreeSet<InternedString>> {
+    let mut users: HashMap<PackageId, BTreeSet<InternedString>> = HashMap::new();
+    for member in ws.members() {
+        let mut seen = HashSet::new();
+        let mut stack = vec![member.package_id()];
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            users.entry(id).or_default().insert(member.name());
+            stack.extend(resolve.deps(id).map(|(dep_id, _)| dep_id));
+        }
+    }
+    users
+}
+
+/// How a lockfile change is classified by `cargo update --report`.
+///
+/// The variants are ordered from the most to the least disruptive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum SemverClass {
+    /// A SemVer-incompatible version, like `1.2.3` to `2.0.0` or `0.1.0` to `0.2.0`.
+    Major,
+    /// A compatible version with new features, like `1.2.3` to `1.3.0`.
+    Minor,
+    /// A compatible bugfix version, like `1.2.3` to `1.2.4` or `0.1.0` to `0.1.1`.
+    Patch,
+    /// Either side of the change is a pre-release.
+    PreRelease,
+    /// The version stayed the same, like a new git revision.
+    Revision,
+    Added,
+    Removed,
+}
+
+impl SemverClass {
+    fn classify(from: &Version, to: &Version) -> Self {
+        // The leftmost non-zero component decides compatibility.
+        fn compat(v: &Version) -> (u64, u64, u64) {
+            match (v.major, v.minor) {
+                (0, 0) => (0, 0, v.patch),
+                (0, minor) => (0, minor, 0),
+                (major, _) => (major, 0, 0),
+            }
+        }
+
+        if !from.pre.is_empty() || !to.pre.is_empty() {
+            SemverClass::PreRelease
+        } else if from.cmp_precedence(to) == Ordering::Equal {
+            SemverClass::Revision
+        } else if compat(from) != compat(to) {
+            SemverClass::Major
+        } else if (from.major, from.minor) != (to.major, to.minor) {
+            SemverClass::Minor
+        } else {
+            SemverClass::Patch
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SemverClass::Major => "major",
+            SemverClass::Minor => "minor",
+            SemverClass::Patch => "patch",
+            SemverClass::PreRelease => "pre-release",
+            SemverClass::Revision => "revision",
+            SemverClass::Added => "added",
+            SemverClass::Removed => "removed",
+        }
+    }
+}
+
 fn status_locking(ws: &Workspace<'_>, num_pkgs: usize) -> CargoResult<()> {
     use std::fmt::Write as _;
 
@@ -716,7 +1187,7 @@ fn status_locking(ws: &Workspace<'_>, num_pkgs: usize) -> CargoResult<()> {
     // Don't have a good way to describe `direct_minimal_versions` atm
     if !ws.gctx().cli_unstable().direct_minimal_versions {
         write!(&mut cfg, " to")?;
-        if ws.gctx().cli_unstable().minimal_versions {
+        if ws.resolve_minimal_versions() {
             write!(&mut cfg, " earliest")?;
         } else {
             write!(&mut cfg, " latest")?;
@@ -998,6 +1469,27 @@ impl PackageChange {
         changes
     }
 
+    /// For a git dependency that moved to another revision, lists the commits
+    /// it moved by, newest first.
+    ///
+    /// Returns `None` for other changes, or if the local database of the git
+    /// source doesn't have both revisions.
+    fn git_log(&self, gctx: &GlobalContext) -> Option<Vec<(git2::Oid, String)>> {
+        let source_id = self.package_id.source_id();
+        if !source_id.is_git() {
+            return None;
+        }
+        let from = self.previous_id?.source_id().precise_git_fragment()?;
+        let to = source_id.precise_git_fragment()?;
+        match GitSource::new(source_id, gctx).and_then(|source| source.log(from, to)) {
+            Ok(log) => Some(log),
+            Err(e) => {
+                debug!("failed to list the commits of {source_id} from {from} to {to}: {e:#}");
+                None
+            }
+        }
+    }
+
     /// For querying [`PackageRegistry`] for alternative versions to report to the user
     fn alternatives_query(&self) -> Option<crate::core::dependency::Dependency> {
         if !self.package_id.source_id().is_registry() {
@@ -161,11 +161,15 @@ impl InstallTracker {
     /// `force=true` will always be considered `Dirty` (i.e., it will always
     /// be rebuilt/reinstalled).
     ///
+    /// The names of split `debuginfo` files to install next to the binaries
+    /// are checked for duplicates as well.
+    ///
     /// Returns an error if there is a duplicate and `--force` is not used.
     pub fn check_upgrade(
         &self,
         dst: &Path,
         pkg: &Package,
+        debuginfo: &BTreeSet<String>,
         force: bool,
         opts: &CompileOptions,
         target: &str,
@@ -173,7 +177,7 @@ impl InstallTracker {
     ) -> CargoResult<(Freshness, BTreeMap<String, Option<PackageId>>)> {
         let exes = exe_names(pkg, &opts.filter);
         // Check if any tracked exe's are already installed.
-        let duplicates = self.find_duplicates(dst, &exes);
+        let duplicates = self.find_duplicates(dst, &exes.union(debuginfo).cloned().collect());
         if force || duplicates.is_empty() {
             return Ok((Freshness::Dirty(DirtyReason::Forced), duplicates));
         }
@@ -235,7 +239,12 @@ impl InstallTracker {
             // Format the error message.
             let mut msg = String::new();
             for (bin, p) in duplicates.iter() {
-                msg.push_str(&format!("binary `{}` already exists in destination", bin));
+                let kind = if is_debuginfo_name(bin) {
+                    "debuginfo"
+                } else {
+                    "binary"
+                };
+                msg.push_str(&format!("{kind} `{bin}` already exists in destination"));
                 if let Some(p) = p.as_ref() {
                     msg.push_str(&format!(" as part of `{}`\n", p));
                 } else {
@@ -328,7 +337,12 @@ impl InstallTracker {
         bin: &str,
         bin_path: &PathBuf,
     ) -> CargoResult<()> {
-        paths::remove_file(bin_path)?;
+        // Split debuginfo may be a bundle directory, such as a `.dSYM`.
+        if bin_path.is_dir() {
+            paths::remove_dir_all(bin_path)?;
+        } else {
+            paths::remove_file(bin_path)?;
+        }
         self.v1.remove_bin(pkg_id, bin);
         self.v2.remove_bin(pkg_id, bin);
         self.save()?;
@@ -767,6 +781,14 @@ fn feature_set(features: &Rc<BTreeSet<FeatureValue>>) -> BTreeSet<String> {
     features.iter().map(|s| s.to_string()).collect()
 }
 
+/// Whether an installed file is split debuginfo installed with
+/// `--debug-symbols`, rather than an executable.
+pub fn is_debuginfo_name(name: &str) -> bool {
+    [".dwp", ".dSYM", ".pdb"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
 /// Helper to get the executable names from a filter.
 pub fn exe_names(pkg: &Package, filter: &ops::CompileFilter) -> BTreeSet<String> {
     let to_exe = |name| format!("{}{}", name, env::consts::EXE_SUFFIX);
@@ -36,7 +36,7 @@
 //!   break anything. The change will be backed out if it fails (unless
 //!   `--broken-code` is used).
 
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::OsString;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -56,11 +56,12 @@ use crate::core::compiler::RustcTargetData;
 use crate::core::resolver::features::{DiffMap, FeatureOpts, FeatureResolver, FeaturesFor};
 use crate::core::resolver::{HasDevUnits, Resolve, ResolveBehavior};
 use crate::core::PackageIdSpecQuery as _;
-use crate::core::{Edition, MaybePackage, Package, PackageId, Workspace};
+use crate::core::{Dependency, Edition, MaybePackage, Package, PackageId, Workspace};
 use crate::ops::resolve::WorkspaceResolve;
 use crate::ops::{self, CompileOptions};
 use crate::util::diagnostic_server::{Message, RustfixDiagnosticServer};
 use crate::util::errors::CargoResult;
+use crate::util::toml_mut::manifest::{DepTable, LocalManifest};
 use crate::util::GlobalContext;
 use crate::util::{existing_vcs_repo, LockServer, LockServerClient};
 use crate::{drop_eprint, drop_eprintln};
@@ -173,7 +174,51 @@ pub fn fix(
     // repeating build until there are no more changes to be applied
     opts.compile_opts.build_config.primary_unit_rustc = Some(wrapper);
 
-    ops::compile(&ws, &opts.compile_opts)?;
+    let compilation = ops::compile(&ws, &opts.compile_opts)?;
+    remove_unused_dependencies(gctx, &compilation.unused_dependencies)?;
+    Ok(())
+}
+
+/// Removes the dependencies reported by the `unused_dependencies` lint from
+/// the manifests of their package.
+fn remove_unused_dependencies(
+    gctx: &GlobalContext,
+    unused: &[(Package, Dependency)],
+) -> CargoResult<()> {
+    let mut per_package: BTreeMap<PackageId, (&Package, Vec<&Dependency>)> = BTreeMap::new();
+    for (pkg, dep) in unused {
+        per_package
+            .entry(pkg.package_id())
+            .or_insert_with(|| (pkg, Vec::new()))
+            .1
+            .push(dep);
+    }
+    for (pkg, deps) in per_package.into_values() {
+        let mut manifest = LocalManifest::try_new(pkg.manifest_path())?;
+        for dep in deps {
+            let mut table = DepTable::from(dep.kind());
+            if let Some(platform) = dep.platform() {
+                table = table.set_target(platform.to_string());
+            }
+            let table_path = table
+                .to_table()
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>();
+            let section = match table.target() {
+                Some(target) => format!("{} for target `{target}`", dep.kind().kind_table()),
+                None => dep.kind().kind_table().to_string(),
+            };
+            let name = dep.name_in_toml();
+            gctx.shell().status(
+                "Removing",
+                format!("unused {name} from {section} of {}", pkg.name()),
+            )?;
+            manifest.remove_from_table(&table_path, &name)?;
+            manifest.gc_dep(&name);
+        }
+        manifest.write()?;
+    }
     Ok(())
 }
 
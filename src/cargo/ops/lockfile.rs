@@ -7,15 +7,32 @@ use crate::util::Filesystem;
 use anyhow::Context as _;
 
 pub const LOCKFILE_NAME: &str = "Cargo.lock";
+/// The lock file holding the minimal versions allowed by the dependency
+/// requirements, written by `cargo update --minimal-versions`.
+pub const MINIMAL_LOCKFILE_NAME: &str = "Cargo.minimal.lock";
+
+/// Whether `file_name` names a lock file, either `Cargo.lock` or a named
+/// lock file like `Cargo.msrv.lock`.
+///
+/// Named lock files are tracked independently of each other, allowing
+/// several dependency sets to be tested from one checkout.
+pub fn is_lockfile_name(file_name: &str) -> bool {
+    file_name == LOCKFILE_NAME
+        || file_name
+            .strip_prefix("Cargo.")
+            .and_then(|name| name.strip_suffix(".lock"))
+            .is_some_and(|name| !name.is_empty() && !name.contains(['/', '\\']))
+}
 
 #[tracing::instrument(skip_all)]
 pub fn load_pkg_lockfile(ws: &Workspace<'_>) -> CargoResult<Option<Resolve>> {
     let lock_root = ws.lock_root();
-    if !lock_root.as_path_unlocked().join(LOCKFILE_NAME).exists() {
+    let lockfile_name = ws.lockfile_name();
+    if !lock_root.as_path_unlocked().join(lockfile_name).exists() {
         return Ok(None);
     }
 
-    let mut f = lock_root.open_ro_shared(LOCKFILE_NAME, ws.gctx(), "Cargo.lock file")?;
+    let mut f = lock_root.open_ro_shared(lockfile_name, ws.gctx(), "Cargo.lock file")?;
 
     let mut s = String::new();
     f.read_to_string(&mut s)
@@ -60,7 +77,10 @@ pub fn write_pkg_lockfile(ws: &Workspace<'_>, resolve: &mut Resolve) -> CargoRes
             "the lock file {} needs to be updated but {} was passed to prevent this\n\
              If you want to try to generate the lock file without accessing the network, \
              remove the {} flag and use --offline instead.",
-            lock_root.as_path_unlocked().join(LOCKFILE_NAME).display(),
+            lock_root
+                .as_path_unlocked()
+                .join(ws.lockfile_name())
+                .display(),
             flag,
             flag
         );
@@ -90,7 +110,7 @@ pub fn write_pkg_lockfile(ws: &Workspace<'_>, resolve: &mut Resolve) -> CargoRes
 
     // Ok, if that didn't work just write it out
     lock_root
-        .open_rw_exclusive_create(LOCKFILE_NAME, ws.gctx(), "Cargo.lock file")
+        .open_rw_exclusive_create(ws.lockfile_name(), ws.gctx(), "Cargo.lock file")
         .and_then(|mut f| {
             f.file().set_len(0)?;
             f.write_all(out.as_bytes())?;
@@ -99,7 +119,10 @@ pub fn write_pkg_lockfile(ws: &Workspace<'_>, resolve: &mut Resolve) -> CargoRes
         .with_context(|| {
             format!(
                 "failed to write {}",
-                lock_root.as_path_unlocked().join(LOCKFILE_NAME).display()
+                lock_root
+                    .as_path_unlocked()
+                    .join(ws.lockfile_name())
+                    .display()
             )
         })?;
     Ok(true)
@@ -111,7 +134,7 @@ fn resolve_to_string_orig(
 ) -> (Option<String>, String, Filesystem) {
     // Load the original lock file if it exists.
     let lock_root = ws.lock_root();
-    let orig = lock_root.open_ro_shared(LOCKFILE_NAME, ws.gctx(), "Cargo.lock file");
+    let orig = lock_root.open_ro_shared(ws.lockfile_name(), ws.gctx(), "Cargo.lock file");
     let orig = orig.and_then(|mut f| {
         let mut s = String::new();
         f.read_to_string(&mut s)?;
@@ -8,6 +8,7 @@ pub use self::cargo_compile::{CompileFilter, FilterRule, LibRule, Packages};
 pub use self::cargo_doc::{doc, DocOptions, OutputFormat};
 pub use self::cargo_fetch::{fetch, FetchOptions};
 pub use self::cargo_install::{install, install_list};
+pub use self::cargo_merge_lockfile::{merge_lockfile, MergeLockfileOptions};
 pub use self::cargo_new::{init, new, NewOptions, NewProjectKind, VersionControl};
 pub use self::cargo_output_metadata::{output_metadata, ExportInfo, OutputMetadataOptions};
 pub use self::cargo_package::{check_yanked, package, PackageOpts};
@@ -22,6 +23,7 @@ pub use self::cargo_update::update_lockfile;
 pub use self::cargo_update::upgrade_manifests;
 pub use self::cargo_update::write_manifest_upgrades;
 pub use self::cargo_update::UpdateOptions;
+pub use self::cargo_update::UpdateReportFormat;
 pub use self::common_for_install_and_uninstall::{resolve_root, InstallTracker};
 pub use self::fix::{fix, fix_exec_rustc, fix_get_proxy_lock_addr, FixOptions};
 pub use self::lockfile::{load_pkg_lockfile, resolve_to_string, write_pkg_lockfile};
@@ -37,8 +39,8 @@ pub use self::registry::PublishOpts;
 pub use self::registry::RegistryCredentialConfig;
 pub use self::registry::RegistryOrIndex;
 pub use self::resolve::{
-    add_overrides, get_resolved_packages, resolve_with_previous, resolve_ws, resolve_ws_with_opts,
-    WorkspaceResolve,
+    add_overrides, get_resolved_packages, member_rust_versions, resolve_with_previous, resolve_ws,
+    resolve_ws_with_opts, rust_version_exceeded, WorkspaceResolve,
 };
 pub use self::vendor::{vendor, VendorOptions};
 
@@ -49,6 +51,7 @@ pub mod cargo_config;
 mod cargo_doc;
 mod cargo_fetch;
 mod cargo_install;
+mod cargo_merge_lockfile;
 mod cargo_new;
 mod cargo_output_metadata;
 mod cargo_package;
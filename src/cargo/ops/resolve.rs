@@ -61,7 +61,8 @@ use crate::core::resolver::features::{
     CliFeatures, FeatureOpts, FeatureResolver, ForceAllTargets, RequestedFeatures, ResolvedFeatures,
 };
 use crate::core::resolver::{
-    self, HasDevUnits, Resolve, ResolveOpts, ResolveVersion, VersionOrdering, VersionPreferences,
+    self, HasDevUnits, Resolve, ResolveError, ResolveOpts, ResolveVersion, VersionOrdering,
+    VersionPreferences,
 };
 use crate::core::summary::Summary;
 use crate::core::Dependency;
@@ -76,10 +77,12 @@ use crate::ops;
 use crate::sources::RecursivePathSource;
 use crate::util::cache_lock::CacheLockMode;
 use crate::util::errors::CargoResult;
+use crate::util::machine_message::{self, Message};
 use crate::util::CanonicalUrl;
 use anyhow::Context as _;
 use cargo_util::paths;
 use cargo_util_schemas::core::PartialVersion;
+use cargo_util_schemas::manifest::RustVersion;
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, trace};
 
@@ -145,6 +148,7 @@ pub fn resolve_ws_with_opts<'gctx>(
     dry_run: bool,
 ) -> CargoResult<WorkspaceResolve<'gctx>> {
     let mut registry = ws.package_registry()?;
+    let feature_opts = FeatureOpts::new(ws, has_dev_units, force_all_targets)?;
     let (resolve, resolved_with_overrides) = if ws.ignore_lock() {
         let add_patches = true;
         let resolve = None;
@@ -224,8 +228,10 @@ pub fn resolve_ws_with_opts<'gctx>(
 
     let pkg_set = get_resolved_packages(&resolved_with_overrides, registry)?;
 
+    // When unifying features across the whole workspace, the dependencies of
+    // members that aren't selected contribute features as well.
     let member_ids = ws
-        .members_with_features(specs, cli_features)?
+        .members_with_unified_features(specs, cli_features, feature_opts.unification())?
         .into_iter()
         .map(|(p, _fts)| p.package_id())
         .collect::<Vec<_>>();
@@ -238,7 +244,6 @@ pub fn resolve_ws_with_opts<'gctx>(
         force_all_targets,
     )?;
 
-    let feature_opts = FeatureOpts::new(ws, has_dev_units, force_all_targets)?;
     let resolved_features = FeatureResolver::resolve(
         ws,
         target_data,
@@ -349,12 +354,13 @@ pub fn resolve_with_previous<'gctx>(
     }
 
     // Try to keep all from previous resolve if no instruction given.
+    let updating = keep_previous.is_some();
     let keep_previous = keep_previous.unwrap_or(&|_| true);
 
     // While registering patches, we will record preferences for particular versions
     // of various packages.
     let mut version_prefs = VersionPreferences::default();
-    if ws.gctx().cli_unstable().minimal_versions {
+    if ws.resolve_minimal_versions() {
         version_prefs.version_ordering(VersionOrdering::MinimumVersionsFirst)
     }
     if ws.resolve_honors_rust_version() {
@@ -369,6 +375,12 @@ pub fn resolve_with_previous<'gctx>(
         }
         version_prefs.rust_versions(rust_versions);
     }
+    if let Some(time) = ws.resolve_publish_time() {
+        version_prefs.max_publish_time(time);
+    }
+    for (name, constraint) in ws.constraints() {
+        version_prefs.constrain(*name, constraint.clone());
+    }
 
     let avoid_patch_ids = if register_patches {
         register_patch_entries(registry, ws, previous, &mut version_prefs, keep_previous)?
@@ -376,8 +388,16 @@ pub fn resolve_with_previous<'gctx>(
         HashSet::new()
     };
 
-    // Refine `keep` with patches that should avoid locking.
-    let keep = |p: &PackageId| keep_previous(p) && !avoid_patch_ids.contains(p);
+    // Refine `keep` with patches that should avoid locking, and with locked
+    // versions that `[workspace.constraints]` no longer allow.
+    let keep = |p: &PackageId| {
+        keep_previous(p)
+            && !avoid_patch_ids.contains(p)
+            && ws
+                .constraints()
+                .get(&p.name())
+                .map_or(true, |constraint| constraint.matches(p.version()))
+    };
 
     let dev_deps = ws.require_optional_deps() || has_dev_units == HasDevUnits::Yes;
 
@@ -387,7 +407,16 @@ pub fn resolve_with_previous<'gctx>(
         // In the case where a previous instance of resolve is available, we
         // want to lock as many packages as possible to the previous version
         // without disturbing the graph structure.
-        register_previous_locks(ws, registry, r, &keep, dev_deps);
+        //
+        // When updating with minimal versions, registry packages are only
+        // preferred below rather than locked. When an updated dependent
+        // raises its requirement on one of them, it then moves to the minimal
+        // version satisfying the new requirement instead of failing to
+        // resolve.
+        let soft_lock = updating && ws.resolve_minimal_versions();
+        let lock = |p: &PackageId| keep(p) && !(soft_lock && p.source_id().is_registry());
+        registry.add_to_yanked_whitelist(r.iter().filter(keep));
+        register_previous_locks(ws, registry, r, &lock, dev_deps);
 
         // Prefer to use anything in the previous lock file, aka we want to have conservative updates.
         let _span = tracing::span!(tracing::Level::TRACE, "prefer_package_id").entered();
@@ -403,7 +432,8 @@ pub fn resolve_with_previous<'gctx>(
 
     let summaries: Vec<(Summary, ResolveOpts)> = {
         let _span = tracing::span!(tracing::Level::TRACE, "registry.lock").entered();
-        ws.members_with_features(specs, cli_features)?
+        let unification = FeatureOpts::new(ws, has_dev_units, ForceAllTargets::No)?.unification();
+        ws.members_with_unified_features(specs, cli_features, unification)?
             .into_iter()
             .map(|(member, features)| {
                 let summary = registry.lock(member.summary().clone());
@@ -427,7 +457,39 @@ pub fn resolve_with_previous<'gctx>(
         &version_prefs,
         ResolveVersion::with_rust_version(ws.lowest_rust_version()),
         Some(ws.gctx()),
-    )?;
+    )
+    .or_else(|err| report_resolve_error(ws, registry, err))?;
+
+    if ws.gctx().cli_unstable().msrv_per_member
+        && ws.resolve_honors_rust_version()
+        && has_mixed_rust_versions(ws)
+    {
+        // Now that we know which members each package is reachable from, hold
+        // every package only to the MSRVs of those members and resolve again.
+        // A package shared by several members is held to all of their MSRVs,
+        // just like the unified resolve above.
+        let mut by_name: HashMap<_, Vec<PartialVersion>> = HashMap::new();
+        for (pkg_id, members) in member_rust_versions(ws, &resolved) {
+            let rust_versions = by_name.entry(pkg_id.name()).or_default();
+            for (_, rust_version) in members {
+                if !rust_versions.contains(&rust_version) {
+                    rust_versions.push(rust_version);
+                }
+            }
+        }
+        for (name, rust_versions) in by_name {
+            version_prefs.package_rust_versions(name, rust_versions);
+        }
+        resolved = resolver::resolve(
+            &summaries,
+            &replace,
+            registry,
+            &version_prefs,
+            ResolveVersion::with_rust_version(ws.lowest_rust_version()),
+            Some(ws.gctx()),
+        )
+        .or_else(|err| report_resolve_error(ws, registry, err))?;
+    }
 
     let patches = registry.patches().values().flat_map(|v| v.iter());
     resolved.register_used_patches(patches);
@@ -445,6 +507,84 @@ pub fn resolve_with_previous<'gctx>(
     Ok(resolved)
 }
 
+/// Passes on the error of a failed resolution, after reporting it as a
+/// `resolve-error` JSON message if [`Workspace::resolve_errors_as_json`].
+fn report_resolve_error(
+    ws: &Workspace<'_>,
+    registry: &mut PackageRegistry<'_>,
+    err: anyhow::Error,
+) -> CargoResult<Resolve> {
+    if ws.resolve_errors_as_json() {
+        if let Some(resolve_err) = err.chain().find_map(|e| e.downcast_ref()) {
+            emit_resolve_error(ws, registry, resolve_err)?;
+        }
+    }
+    Err(err)
+}
+
+/// Reports a failed dependency resolution as a `resolve-error` JSON message.
+fn emit_resolve_error(
+    ws: &Workspace<'_>,
+    registry: &mut PackageRegistry<'_>,
+    err: &ResolveError,
+) -> CargoResult<()> {
+    let mut msg = machine_message::ResolveError {
+        message: err.to_string(),
+        package_path: err.package_path().iter().map(|id| id.to_spec()).collect(),
+        dependency: None,
+        candidates: Vec::new(),
+        conflicts: Vec::new(),
+        filtered: Default::default(),
+    };
+    if let Some(details) = err.details() {
+        let dep = &details.dependency;
+        msg.dependency = Some(machine_message::ResolveErrorDependency {
+            name: dep.package_name().to_string(),
+            requirement: dep.version_req().to_string(),
+            source: dep.source_id().as_url().to_string(),
+            required_by: details.parent.to_spec(),
+        });
+        msg.candidates = details
+            .candidates
+            .iter()
+            .map(|s| machine_message::ResolveErrorCandidate {
+                version: s.version().to_string(),
+                rust_version: s.rust_version().map(|v| v.to_string()),
+            })
+            .collect();
+        msg.conflicts = details
+            .conflicts
+            .iter()
+            .map(|c| machine_message::ResolveErrorConflict {
+                package_id: c.package_id.to_spec(),
+                reason: c.reason,
+                detail: c.detail.map(|d| d.to_string()),
+                required_by: c.required_by.as_ref().map(|(pkg, dep)| {
+                    machine_message::ResolveErrorRequirement {
+                        package_id: pkg.to_spec(),
+                        requirement: dep.version_req().to_string(),
+                    }
+                }),
+            })
+            .collect();
+        msg.filtered = machine_message::ResolveErrorFiltered {
+            yanked: details
+                .yanked(registry)
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+            incompatible_rust_version: details
+                .incompatible_rust_versions
+                .iter()
+                .map(|v| v.to_string())
+                .collect(),
+            offline: details.offline,
+        };
+    }
+    writeln!(ws.gctx().shell().out(), "{}", msg.to_json_string())?;
+    Ok(())
+}
+
 /// Read the `paths` configuration variable to discover all path overrides that
 /// have been configured.
 #[tracing::instrument(skip_all)]
@@ -488,6 +628,53 @@ pub fn get_resolved_packages<'gctx>(
     registry.get(&ids)
 }
 
+/// Whether the workspace members disagree on their `rust-version`.
+fn has_mixed_rust_versions(ws: &Workspace<'_>) -> bool {
+    let rust_versions: HashSet<_> = ws.members().map(|p| p.rust_version()).collect();
+    rust_versions.len() > 1
+}
+
+/// Finds the workspace members with a `rust-version` that each package in
+/// `resolve` is reachable from, for `-Zmsrv-per-member`.
+///
+/// Packages only reachable from members without a `rust-version` map to an
+/// empty list.
+pub fn member_rust_versions(
+    ws: &Workspace<'_>,
+    resolve: &Resolve,
+) -> HashMap<PackageId, Vec<(PackageId, PartialVersion)>> {
+    let mut reachable_from: HashMap<PackageId, Vec<(PackageId, PartialVersion)>> = HashMap::new();
+    for member in ws.members() {
+        let member_id = member.package_id();
+        let rust_version = member.rust_version().map(|rv| rv.as_partial().clone());
+        let mut visited = HashSet::new();
+        let mut queue = vec![member_id];
+        while let Some(pkg_id) = queue.pop() {
+            if !visited.insert(pkg_id) {
+                continue;
+            }
+            let members = reachable_from.entry(pkg_id).or_default();
+            if let Some(rust_version) = &rust_version {
+                members.push((member_id, rust_version.clone()));
+            }
+            queue.extend(resolve.deps(pkg_id).map(|(dep_id, _)| dep_id));
+        }
+    }
+    reachable_from
+}
+
+/// The members from [`member_rust_versions`] whose `rust-version` is older
+/// than the one `rust_version` requires.
+pub fn rust_version_exceeded<'a>(
+    rust_version: Option<&'a RustVersion>,
+    members: &'a [(PackageId, PartialVersion)],
+) -> impl Iterator<Item = PackageId> + 'a {
+    members.iter().filter_map(move |(member_id, msrv)| {
+        let rust_version = rust_version?;
+        (!rust_version.is_compatible_with(msrv)).then_some(*member_id)
+    })
+}
+
 /// In this function we're responsible for informing the `registry` of all
 /// locked dependencies from the previous lock file we had, `resolve`.
 ///
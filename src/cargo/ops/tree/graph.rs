@@ -116,6 +116,15 @@ impl<'a> Graph<'a> {
         }
     }
 
+    /// Returns a list of nodes the given node index points to for any kind of
+    /// edge.
+    pub fn all_connected_nodes(&self, from: usize) -> Vec<usize> {
+        let mut indexes: Vec<usize> = self.edges[from].0.values().flatten().copied().collect();
+        indexes.sort_unstable_by(|a, b| self.nodes[*a].cmp(&self.nodes[*b]));
+        indexes.dedup();
+        indexes
+    }
+
     /// Returns `true` if the given node has any outgoing edges.
     pub fn has_outgoing_edges(&self, index: usize) -> bool {
         !self.edges[index].0.is_empty()
@@ -144,11 +153,34 @@ impl<'a> Graph<'a> {
         result.into_iter().map(|(_node, i)| i).collect()
     }
 
+    /// Returns the indexes of the nodes for the feature `name` of any of the
+    /// given packages.
+    pub fn feature_indexes(&self, package_ids: &[PackageId], name: &str) -> Vec<usize> {
+        let mut result: Vec<(&Node, usize)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_i, node)| match node {
+                Node::Feature {
+                    node_index,
+                    name: feature,
+                } => {
+                    feature.as_str() == name
+                        && package_ids.contains(&self.package_id_for_index(*node_index))
+                }
+                _ => false,
+            })
+            .map(|(i, node)| (node, i))
+            .collect();
+        result.sort_unstable();
+        result.into_iter().map(|(_node, i)| i).collect()
+    }
+
     pub fn package_for_id(&self, id: PackageId) -> &Package {
         self.package_map[&id]
     }
 
-    fn package_id_for_index(&self, index: usize) -> PackageId {
+    pub fn package_id_for_index(&self, index: usize) -> PackageId {
         match self.nodes[index] {
             Node::Package { package_id, .. } => package_id,
             Node::Feature { .. } => panic!("unexpected feature node"),
@@ -302,7 +334,10 @@ pub fn build<'a>(
         }
     }
     if opts.graph_features {
-        add_internal_features(&mut graph, resolve);
+        // `--why-feature` also follows `dep:` features to the dependencies
+        // they enable.
+        let link_deps = opts.why_feature.is_some();
+        add_internal_features(&mut graph, resolve, link_deps);
     }
     Ok(graph)
 }
@@ -580,7 +615,7 @@ fn add_cli_features(
 
 /// Recursively adds connections between features in the `[features]` table
 /// for every package.
-fn add_internal_features(graph: &mut Graph<'_>, resolve: &Resolve) {
+fn add_internal_features(graph: &mut Graph<'_>, resolve: &Resolve, link_deps: bool) {
     // Collect features already activated by dependencies or command-line.
     let feature_nodes: Vec<(PackageId, usize, usize, InternedString)> = graph
         .nodes
@@ -603,6 +638,7 @@ fn add_internal_features(graph: &mut Graph<'_>, resolve: &Resolve) {
             package_id,
             feature_index,
             package_index,
+            link_deps,
         );
     }
 }
@@ -611,6 +647,8 @@ fn add_internal_features(graph: &mut Graph<'_>, resolve: &Resolve) {
 ///
 /// `from` is the index of the node that enables this feature.
 /// `package_index` is the index of the package node for the feature.
+/// `link_deps` adds an edge from the feature to the dependencies enabled with
+/// `dep:` syntax.
 fn add_feature_rec(
     graph: &mut Graph<'_>,
     resolve: &Resolve,
@@ -618,6 +656,7 @@ fn add_feature_rec(
     package_id: PackageId,
     from: usize,
     package_index: usize,
+    link_deps: bool,
 ) {
     let feature_map = resolve.summary(package_id).features();
     let Some(fvs) = feature_map.get(&feature_name) else {
@@ -642,6 +681,7 @@ fn add_feature_rec(
                         package_id,
                         feat_index,
                         package_index,
+                        link_deps,
                     );
                 }
             }
@@ -649,7 +689,17 @@ fn add_feature_rec(
             // uncertain whether or not this might be confusing in some cases
             // (like feature `"somefeat" = ["dep:somedep"]`), so maybe in the
             // future consider explicitly showing this?
-            FeatureValue::Dep { .. } => {}
+            FeatureValue::Dep { dep_name } => {
+                if !link_deps {
+                    continue;
+                }
+                // Not present if the dependency is not activated.
+                if let Some(dep_indexes) = graph.dep_name_map[&package_index].get(dep_name) {
+                    for (dep_index, _is_optional) in dep_indexes.clone() {
+                        graph.edges[from].add_edge(EdgeKind::Feature, dep_index);
+                    }
+                }
+            }
             FeatureValue::DepFeature {
                 dep_name,
                 dep_feature,
@@ -685,6 +735,9 @@ fn add_feature_rec(
                             package_index,
                             EdgeKind::Feature,
                         );
+                        if link_deps {
+                            graph.edges[from].add_edge(EdgeKind::Feature, dep_index);
+                        }
                     }
                     let (missing, feat_index) = add_feature(
                         graph,
@@ -701,6 +754,7 @@ fn add_feature_rec(
                             dep_pkg_id,
                             feat_index,
                             dep_index,
+                            link_deps,
                         );
                     }
                 }
@@ -3,7 +3,7 @@
 use self::format::Pattern;
 use crate::core::compiler::{CompileKind, RustcTargetData};
 use crate::core::dependency::DepKind;
-use crate::core::resolver::{features::CliFeatures, ForceAllTargets, HasDevUnits};
+use crate::core::resolver::{features::CliFeatures, ForceAllTargets, HasDevUnits, Resolve};
 use crate::core::{Package, PackageId, PackageIdSpec, PackageIdSpecQuery, Workspace};
 use crate::ops::{self, Packages};
 use crate::util::{CargoResult, GlobalContext};
@@ -15,6 +15,7 @@ use std::str::FromStr;
 
 mod format;
 mod graph;
+mod why_feature;
 
 pub use {graph::EdgeKind, graph::Node};
 
@@ -47,6 +48,10 @@ pub struct TreeOptions {
     pub max_display_depth: u32,
     /// Excludes proc-macro dependencies.
     pub no_proc_macro: bool,
+    /// A `<SPEC>/<FEATURE>` to explain, instead of printing the tree.
+    pub why_feature: Option<String>,
+    /// Prints the `why_feature` explanation as JSON.
+    pub why_feature_json: bool,
 }
 
 #[derive(PartialEq)]
@@ -148,6 +153,13 @@ pub fn build_and_print(ws: &Workspace<'_>, opts: &TreeOptions) -> CargoResult<()
         .packages()
         .map(|pkg| (pkg.package_id(), pkg))
         .collect();
+    let mut notes = Notes::default();
+    if ws.gctx().cli_unstable().msrv_per_member {
+        notes.msrv = msrv_notes(ws, &ws_resolve.targeted_resolve, &package_map);
+    }
+    if !opts.invert.is_empty() || opts.duplicates {
+        notes.constraints = constraint_notes(ws, &package_map);
+    }
 
     let mut graph = graph::build(
         ws,
@@ -161,6 +173,11 @@ pub fn build_and_print(ws: &Workspace<'_>, opts: &TreeOptions) -> CargoResult<()
         opts,
     )?;
 
+    if let Some(why_feature) = &opts.why_feature {
+        graph.invert();
+        return why_feature::print(ws, opts, &ws_resolve.targeted_resolve, &graph, why_feature);
+    }
+
     let root_specs = if opts.invert.is_empty() {
         specs
     } else {
@@ -203,17 +220,81 @@ pub fn build_and_print(ws: &Workspace<'_>, opts: &TreeOptions) -> CargoResult<()
         try to use option `--target all` first, and then narrow your search scope accordingly.",
         )?;
     } else {
-        print(ws.gctx(), opts, root_indexes, &pkgs_to_prune, &graph)?;
+        print(
+            ws.gctx(),
+            opts,
+            root_indexes,
+            &pkgs_to_prune,
+            &notes,
+            &graph,
+        )?;
     }
     Ok(())
 }
 
+/// Extra information printed along with packages.
+#[derive(Default)]
+struct Notes {
+    /// Appended to packages whose `rust-version` is too new for a workspace
+    /// member, see [`msrv_notes`].
+    msrv: HashMap<PackageId, String>,
+    /// Printed as an extra dependent of constrained packages when the tree is
+    /// inverted, see [`constraint_notes`].
+    constraints: HashMap<PackageId, String>,
+}
+
+/// Describes the `[workspace.constraints]` that apply to each package, as
+/// they are a source of requirements just like the package's dependents.
+fn constraint_notes(
+    ws: &Workspace<'_>,
+    package_map: &HashMap<PackageId, &Package>,
+) -> HashMap<PackageId, String> {
+    package_map
+        .keys()
+        .filter_map(|pkg_id| {
+            let constraint = ws.constraints().get(&pkg_id.name())?;
+            let note = format!(
+                "[workspace.constraints] {} = \"{constraint}\"",
+                pkg_id.name()
+            );
+            Some((*pkg_id, note))
+        })
+        .collect()
+}
+
+/// Annotates packages whose `rust-version` is newer than the `rust-version`
+/// of a workspace member they are reachable from, for `-Zmsrv-per-member`.
+fn msrv_notes(
+    ws: &Workspace<'_>,
+    resolve: &Resolve,
+    package_map: &HashMap<PackageId, &Package>,
+) -> HashMap<PackageId, String> {
+    let mut notes = HashMap::new();
+    for (pkg_id, members) in ops::member_rust_versions(ws, resolve) {
+        let Some(rust_version) = package_map.get(&pkg_id).and_then(|p| p.rust_version()) else {
+            continue;
+        };
+        let exceeded: Vec<_> = ops::rust_version_exceeded(Some(rust_version), &members)
+            .map(|member_id| format!("`{}`", member_id.name()))
+            .collect();
+        if !exceeded.is_empty() {
+            let note = format!(
+                " (rust-version {rust_version} exceeds the MSRV of {})",
+                exceeded.join(", ")
+            );
+            notes.insert(pkg_id, note);
+        }
+    }
+    notes
+}
+
 /// Prints a tree for each given root.
 fn print(
     gctx: &GlobalContext,
     opts: &TreeOptions,
     roots: Vec<usize>,
     pkgs_to_prune: &[PackageIdSpec],
+    notes: &Notes,
     graph: &Graph<'_>,
 ) -> CargoResult<()> {
     let format = Pattern::new(&opts.format)
@@ -248,6 +329,7 @@ fn print(
             &format,
             symbols,
             pkgs_to_prune,
+            notes,
             opts.prefix,
             opts.no_dedupe,
             opts.max_display_depth,
@@ -268,6 +350,7 @@ fn print_node<'a>(
     format: &Pattern,
     symbols: &Symbols,
     pkgs_to_prune: &[PackageIdSpec],
+    notes: &Notes,
     prefix: Prefix,
     no_dedupe: bool,
     max_display_depth: u32,
@@ -277,25 +360,7 @@ fn print_node<'a>(
 ) {
     let new = no_dedupe || visited_deps.insert(node_index);
 
-    match prefix {
-        Prefix::Depth => drop_print!(gctx, "{}", levels_continue.len()),
-        Prefix::Indent => {
-            if let Some((last_continues, rest)) = levels_continue.split_last() {
-                for continues in rest {
-                    let c = if *continues { symbols.down } else { " " };
-                    drop_print!(gctx, "{}   ", c);
-                }
-
-                let c = if *last_continues {
-                    symbols.tee
-                } else {
-                    symbols.ell
-                };
-                drop_print!(gctx, "{0}{1}{1} ", c, symbols.right);
-            }
-        }
-        Prefix::None => {}
-    }
+    print_prefix(gctx, symbols, prefix, levels_continue);
 
     let in_cycle = print_stack.contains(&node_index);
     // If this node does not have any outgoing edges, don't include the (*)
@@ -307,7 +372,18 @@ fn print_node<'a>(
     } else {
         " (*)"
     };
-    drop_println!(gctx, "{}{}", format.display(graph, node_index), star);
+    let msrv_note = match graph.node(node_index) {
+        Node::Package { package_id, .. } => notes.msrv.get(package_id).map(String::as_str),
+        _ => None,
+    }
+    .unwrap_or("");
+    drop_println!(
+        gctx,
+        "{}{}{}",
+        format.display(graph, node_index),
+        msrv_note,
+        star
+    );
 
     if !new || in_cycle {
         return;
@@ -327,6 +403,7 @@ fn print_node<'a>(
             format,
             symbols,
             pkgs_to_prune,
+            notes,
             prefix,
             no_dedupe,
             max_display_depth,
@@ -339,6 +416,29 @@ fn print_node<'a>(
     print_stack.pop();
 }
 
+/// Prints the prefix of a line in the tree.
+fn print_prefix(gctx: &GlobalContext, symbols: &Symbols, prefix: Prefix, levels_continue: &[bool]) {
+    match prefix {
+        Prefix::Depth => drop_print!(gctx, "{}", levels_continue.len()),
+        Prefix::Indent => {
+            if let Some((last_continues, rest)) = levels_continue.split_last() {
+                for continues in rest {
+                    let c = if *continues { symbols.down } else { " " };
+                    drop_print!(gctx, "{}   ", c);
+                }
+
+                let c = if *last_continues {
+                    symbols.tee
+                } else {
+                    symbols.ell
+                };
+                drop_print!(gctx, "{0}{1}{1} ", c, symbols.right);
+            }
+        }
+        Prefix::None => {}
+    }
+}
+
 /// Prints all the dependencies of a package for the given dependency kind.
 fn print_dependencies<'a>(
     gctx: &GlobalContext,
@@ -347,6 +447,7 @@ fn print_dependencies<'a>(
     format: &Pattern,
     symbols: &Symbols,
     pkgs_to_prune: &[PackageIdSpec],
+    notes: &Notes,
     prefix: Prefix,
     no_dedupe: bool,
     max_display_depth: u32,
@@ -356,7 +457,15 @@ fn print_dependencies<'a>(
     kind: &EdgeKind,
 ) {
     let deps = graph.connected_nodes(node_index, kind);
-    if deps.is_empty() {
+    // With `--invert`, the dependents of a package are printed, and a
+    // `[workspace.constraints]` entry for it is just one more of those.
+    let constraint = match (kind, graph.node(node_index)) {
+        (EdgeKind::Dep(DepKind::Normal), Node::Package { package_id, .. }) => {
+            notes.constraints.get(package_id)
+        }
+        _ => None,
+    };
+    if deps.is_empty() && constraint.is_none() {
         return;
     }
 
@@ -396,6 +505,13 @@ fn print_dependencies<'a>(
         })
         .peekable();
 
+    if let Some(constraint) = constraint {
+        levels_continue.push(it.peek().is_some());
+        print_prefix(gctx, symbols, prefix, levels_continue);
+        drop_println!(gctx, "{}", constraint);
+        levels_continue.pop();
+    }
+
     while let Some(dependency) = it.next() {
         levels_continue.push(it.peek().is_some());
         print_node(
@@ -405,6 +521,7 @@ fn print_dependencies<'a>(
             format,
             symbols,
             pkgs_to_prune,
+            notes,
             prefix,
             no_dedupe,
             max_display_depth,
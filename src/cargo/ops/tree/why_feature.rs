@@ -0,0 +1,238 @@
+//! Implementation of `cargo tree --why-feature`.
+//!
+//! This walks the inverted feature graph from a feature node up to the
+//! workspace members. For everything directly enabling the feature, it lists
+//! the shortest chain of features and dependencies that activates it.
+
+use super::format::Pattern;
+use super::graph::{Graph, Node};
+use super::TreeOptions;
+use crate::core::resolver::Resolve;
+use crate::core::{PackageId, PackageIdSpec, Workspace};
+use crate::drop_println;
+use crate::util::CargoResult;
+use anyhow::Context as _;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A chain of graph node indexes, from a workspace member down to the
+/// feature being explained.
+type Chain = Vec<usize>;
+
+/// Prints the activation chains of the feature given to `--why-feature`.
+///
+/// `graph` must be built with features and inverted.
+pub fn print(
+    ws: &Workspace<'_>,
+    opts: &TreeOptions,
+    resolve: &Resolve,
+    graph: &Graph<'_>,
+    why_feature: &str,
+) -> CargoResult<()> {
+    let gctx = ws.gctx();
+    let Some((spec, feature)) = why_feature.rsplit_once('/') else {
+        anyhow::bail!(
+            "`--why-feature` expects a package and feature in the form `<SPEC>/<FEATURE>`, \
+             found `{why_feature}`"
+        );
+    };
+    let spec = PackageIdSpec::parse(spec)?;
+    let package_ids = resolve.specs_to_ids(&[spec])?;
+    let members: HashSet<PackageId> = ws.members().map(|pkg| pkg.package_id()).collect();
+    let explained: Vec<_> = graph
+        .feature_indexes(&package_ids, feature)
+        .into_iter()
+        .map(|target| (target, activation_chains(graph, &members, target)))
+        .filter(|(_, chains)| !chains.is_empty())
+        .collect();
+    if explained.is_empty() {
+        gctx.shell()
+            .warn(format!("feature `{why_feature}` is not enabled"))?;
+        return Ok(());
+    }
+
+    let format = Pattern::new(&opts.format)
+        .with_context(|| format!("tree format `{}` not valid", opts.format))?;
+    for (i, (target, chains)) in explained.into_iter().enumerate() {
+        if opts.why_feature_json {
+            let explanation = Explanation {
+                reason: "why-feature",
+                package: graph
+                    .package_id_for_index(feature_package(graph, target))
+                    .to_spec(),
+                feature,
+                chains: chains
+                    .iter()
+                    .map(|chain| chain.iter().map(|i| step(graph, *i)).collect())
+                    .collect(),
+            };
+            drop_println!(gctx, "{}", serde_json::to_string(&explanation)?);
+        } else {
+            if i != 0 {
+                drop_println!(gctx);
+            }
+            for chain in chains {
+                let chain: Vec<_> = chain
+                    .iter()
+                    .map(|i| format.display(graph, *i).to_string())
+                    .collect();
+                drop_println!(gctx, "{}", chain.join(" -> "));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The `--why-feature` JSON output for one feature node.
+#[derive(Serialize)]
+struct Explanation<'a> {
+    reason: &'static str,
+    package: PackageIdSpec,
+    feature: &'a str,
+    chains: Vec<Vec<Step>>,
+}
+
+/// A node of an activation chain in the JSON output.
+#[derive(Serialize)]
+struct Step {
+    package: PackageIdSpec,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feature: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    command_line: bool,
+}
+
+fn step(graph: &Graph<'_>, index: usize) -> Step {
+    match graph.node(index) {
+        Node::Package { package_id, .. } => Step {
+            package: package_id.to_spec(),
+            feature: None,
+            command_line: false,
+        },
+        Node::Feature { node_index, name } => Step {
+            package: graph.package_id_for_index(*node_index).to_spec(),
+            feature: Some(name.to_string()),
+            command_line: graph.is_cli_feature(index),
+        },
+    }
+}
+
+/// The index of the package node the given feature node is for.
+fn feature_package(graph: &Graph<'_>, index: usize) -> usize {
+    match graph.node(index) {
+        Node::Feature { node_index, .. } => *node_index,
+        Node::Package { .. } => panic!("expected a feature node"),
+    }
+}
+
+/// Collects, for each node directly enabling the feature node `target`, the
+/// shortest chain activating it from a workspace member or a feature enabled
+/// on the command-line.
+fn activation_chains(graph: &Graph<'_>, members: &HashSet<PackageId>, target: usize) -> Vec<Chain> {
+    if is_root(graph, members, target) {
+        return vec![vec![target]];
+    }
+    enablers(graph, target)
+        .into_iter()
+        .filter(|enabler| is_enabled_externally(graph, target, *enabler))
+        .filter_map(|enabler| shortest_chain(graph, members, target, enabler))
+        .collect()
+}
+
+/// Searches breadth-first from `enabler` up to the closest root, so each node
+/// is visited at most once. Returns `None` if no root is reachable without
+/// going through `target`.
+fn shortest_chain(
+    graph: &Graph<'_>,
+    members: &HashSet<PackageId>,
+    target: usize,
+    enabler: usize,
+) -> Option<Chain> {
+    // The node each visited node enables, towards `target`.
+    let mut enables = HashMap::from([(enabler, target)]);
+    let mut queue = VecDeque::from([enabler]);
+    while let Some(index) = queue.pop_front() {
+        let next = enablers(graph, index);
+        if is_root(graph, members, index) || next.is_empty() {
+            let mut chain = vec![index];
+            let mut current = index;
+            while let Some(&enabled) = enables.get(&current) {
+                chain.push(enabled);
+                current = enabled;
+            }
+            return Some(chain);
+        }
+        for n in next {
+            if n == target || enables.contains_key(&n) || !is_enabled_externally(graph, index, n) {
+                continue;
+            }
+            enables.insert(n, index);
+            queue.push_back(n);
+        }
+    }
+    None
+}
+
+/// Checks if a chain can start at the given node: a workspace member, or a
+/// feature enabled on the command-line.
+fn is_root(graph: &Graph<'_>, members: &HashSet<PackageId>, index: usize) -> bool {
+    match graph.node(index) {
+        Node::Package { package_id, .. } => members.contains(package_id),
+        Node::Feature { .. } => graph.is_cli_feature(index),
+    }
+}
+
+/// The nodes enabling the given node in the inverted graph.
+///
+/// An optional dependency is only in the graph because a feature of its
+/// dependent enables it with `dep:`, so those features are used instead of
+/// the dependent package.
+fn enablers(graph: &Graph<'_>, index: usize) -> Vec<usize> {
+    let package = match graph.node(index) {
+        Node::Package { .. } => index,
+        Node::Feature { node_index, .. } => *node_index,
+    };
+    let dep_links: Vec<(usize, usize)> = graph
+        .all_connected_nodes(package)
+        .into_iter()
+        .filter_map(|i| match graph.node(i) {
+            Node::Feature { node_index, .. } if *node_index != package => Some((*node_index, i)),
+            _ => None,
+        })
+        .collect();
+    let mut result = Vec::new();
+    for enabler in graph.all_connected_nodes(index) {
+        let links: Vec<usize> = dep_links
+            .iter()
+            .filter(|(dependent, _)| *dependent == enabler)
+            .map(|(_, feature)| *feature)
+            .collect();
+        if links.is_empty() {
+            result.push(enabler);
+        } else {
+            result.extend(links);
+        }
+    }
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+/// Every feature of a package points to the package itself. Only follow the
+/// ones enabled from outside of the package to explain why it is in the graph,
+/// as its other features are just a consequence of those.
+fn is_enabled_externally(graph: &Graph<'_>, index: usize, enabler: usize) -> bool {
+    match graph.node(enabler) {
+        Node::Feature { node_index, .. } if *node_index == index => {
+            graph.is_cli_feature(enabler)
+                || graph
+                    .all_connected_nodes(enabler)
+                    .into_iter()
+                    .any(|i| match graph.node(i) {
+                        Node::Feature { node_index, .. } => *node_index != index,
+                        Node::Package { .. } => i != index,
+                    })
+        }
+        _ => true,
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::core::{GitReference, PackageId, SourceId};
 use crate::sources::overlay::DependencyConfusionThreatOverlaySource;
+use crate::sources::registry::RegistrySource;
 use crate::sources::source::Source;
 use crate::sources::{ReplacedSource, CRATES_IO_REGISTRY};
 use crate::util::context::{self, ConfigRelativePath, OptValue};
@@ -50,6 +51,9 @@ struct SourceConfigDef {
     tag: OptValue<String>,
     /// The git revision.
     rev: OptValue<String>,
+    /// Names of remote registries to download `.crate` files from before
+    /// this registry, in order.
+    mirrors: OptValue<Vec<String>>,
 }
 
 /// Configuration for a particular source, found in TOML looking like:
@@ -71,6 +75,10 @@ struct SourceConfig {
     /// this configuration key was defined (such as the `.cargo/config` path
     /// or the environment variable name).
     replace_with: Option<(String, String)>,
+
+    /// Names of the mirrors of this source, with where they were defined
+    /// like `replace_with`.
+    mirrors: Option<(Vec<String>, String)>,
 }
 
 impl<'gctx> SourceConfigMap<'gctx> {
@@ -113,6 +121,7 @@ impl<'gctx> SourceConfigMap<'gctx> {
             SourceConfig {
                 id: SourceId::crates_io(gctx)?,
                 replace_with: None,
+                mirrors: None,
             },
         )?;
         if SourceId::crates_io_is_sparse(gctx)? {
@@ -121,6 +130,7 @@ impl<'gctx> SourceConfigMap<'gctx> {
                 SourceConfig {
                     id: SourceId::crates_io_maybe_sparse_http(gctx)?,
                     replace_with: None,
+                    mirrors: None,
                 },
             )?;
         }
@@ -130,6 +140,7 @@ impl<'gctx> SourceConfigMap<'gctx> {
                 SourceConfig {
                     id: SourceId::for_alt_registry(&url.parse()?, CRATES_IO_REGISTRY)?,
                     replace_with: None,
+                    mirrors: None,
                 },
             )?;
         }
@@ -237,7 +248,14 @@ restore the source replacement configuration to continue the build
         id: SourceId,
         yanked_whitelist: &HashSet<PackageId>,
     ) -> CargoResult<Box<dyn Source + 'gctx>> {
-        let src = id.load(self.gctx, yanked_whitelist)?;
+        let src = match self.mirrors(id)? {
+            Some(mirrors) => {
+                let mut src = RegistrySource::remote(id, yanked_whitelist, self.gctx)?;
+                src.set_mirrors(&mirrors)?;
+                Box::new(src)
+            }
+            None => id.load(self.gctx, yanked_whitelist)?,
+        };
         if let Some(overlay_id) = self.overlays.get(&id) {
             let overlay = overlay_id.load(self.gctx(), yanked_whitelist)?;
             Ok(Box::new(DependencyConfusionThreatOverlaySource::new(
@@ -248,6 +266,50 @@ restore the source replacement configuration to continue the build
         }
     }
 
+    /// Gets the [`SourceId`]s of the mirrors configured for the source `id`,
+    /// if any.
+    fn mirrors(&self, id: SourceId) -> CargoResult<Option<Vec<SourceId>>> {
+        if !self.gctx.cli_unstable().registry_mirrors {
+            return Ok(None);
+        }
+        let Some(name) = self.id2name.get(&id) else {
+            return Ok(None);
+        };
+        let Some((mirrors, cfg_loc)) = self.cfgs.get(name).and_then(|cfg| cfg.mirrors.as_ref())
+        else {
+            return Ok(None);
+        };
+        if !id.is_remote_registry() {
+            bail!(
+                "source `{name}` has mirrors, but only remote registries can have mirrors \
+                 (configuration in `{cfg_loc}`)"
+            );
+        }
+        let mirrors = mirrors
+            .iter()
+            .map(|mirror| {
+                let mirror_id = match self.cfgs.get(mirror) {
+                    Some(cfg) => cfg.id,
+                    None => SourceId::alt_registry(self.gctx, mirror).with_context(|| {
+                        format!(
+                            "could not find a configured source with the name `{mirror}` \
+                             when attempting to lookup the mirrors of `{name}` \
+                             (configuration in `{cfg_loc}`)"
+                        )
+                    })?,
+                };
+                if !mirror_id.is_remote_registry() {
+                    bail!(
+                        "mirror `{mirror}` of source `{name}` is not a remote registry \
+                         (configuration in `{cfg_loc}`)"
+                    );
+                }
+                Ok(mirror_id)
+            })
+            .collect::<CargoResult<_>>()?;
+        Ok(Some(mirrors))
+    }
+
     /// Adds a source config with an associated name.
     fn add(&mut self, name: &str, cfg: SourceConfig) -> CargoResult<()> {
         if let Some(old_name) = self.id2name.insert(cfg.id, name.to_string()) {
@@ -315,6 +377,13 @@ restore the source replacement configuration to continue the build
         if name == CRATES_IO_REGISTRY && srcs.is_empty() {
             srcs.push(SourceId::crates_io_maybe_sparse_http(self.gctx)?);
         }
+        if srcs.is_empty() && def.mirrors.is_some() && def.replace_with.is_none() {
+            // Only mirrors of a registry from the `[registries]` table.
+            if !self.gctx.cli_unstable().registry_mirrors {
+                return Ok(());
+            }
+            srcs.push(SourceId::alt_registry(self.gctx, &name)?);
+        }
 
         match srcs.len() {
             0 => bail!(
@@ -333,12 +402,14 @@ restore the source replacement configuration to continue the build
         let replace_with = def
             .replace_with
             .map(|val| (val.val, val.definition.to_string()));
+        let mirrors = def.mirrors.map(|val| (val.val, val.definition.to_string()));
 
         self.add(
             &name,
             SourceConfig {
                 id: src,
                 replace_with,
+                mirrors,
             },
         )?;
 
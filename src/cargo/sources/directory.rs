@@ -111,6 +111,7 @@ impl<'gctx> Source for DirectorySource<'gctx> {
             QueryKind::Exact => dep.matches(pkg.summary()),
             QueryKind::Alternatives => true,
             QueryKind::Normalized => dep.matches(pkg.summary()),
+            QueryKind::RejectedVersions => false,
         });
         for summary in matches.map(|pkg| pkg.summary().clone()) {
             f(IndexSummary::Candidate(summary));
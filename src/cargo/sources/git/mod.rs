@@ -3,7 +3,8 @@
 //! Apparently, the most important type in this module is [`GitSource`].
 //! [`utils`] provides libgit2 utilities like fetch and checkout, whereas
 //! [`oxide`] is the counterpart for gitoxide integration. [`known_hosts`]
-//! is the mitigation of [CVE-2022-46176].
+//! is the mitigation of [CVE-2022-46176]. [`sparse`] plans checkouts of only
+//! the packages in use for `-Zgit=sparse-checkout`.
 //!
 //! [CVE-2022-46176]: https://blog.rust-lang.org/2023/01/10/cve-2022-46176.html
 
@@ -12,6 +13,7 @@ pub use self::utils::{fetch, resolve_ref, GitCheckout, GitDatabase, GitRemote};
 mod known_hosts;
 mod oxide;
 mod source;
+mod sparse;
 mod utils;
 
 /// For `-Zgitoxide` integration.
@@ -1,11 +1,16 @@
 //! This module contains all code sporting `gitoxide` for operations on `git` repositories and it mirrors
 //! `utils` closely for now. One day it can be renamed into `utils` once `git2` isn't required anymore.
 
+use crate::sources::git::sparse::{
+    parent_references, write_file, FileMode, GitTree, SparseCheckout,
+};
 use crate::util::network::http::HttpTimeout;
 use crate::util::{human_readable_bytes, network, MetricsCounter, Progress};
 use crate::{CargoResult, GlobalContext};
 use cargo_util::paths;
 use gix::bstr::{BString, ByteSlice};
+use gix::objs::tree::EntryKind;
+use gix::traverse::tree::recorder::Entry;
 use std::cell::RefCell;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -384,3 +389,85 @@ pub fn reinitialize(git_dir: &Path) -> CargoResult<()> {
     paths::remove_dir_all(&tmp)?;
     Ok(())
 }
+
+/// Lists the files of the tree of `rev` in the repository at `repo_path`, along
+/// with the contents of the manifests and the paths the Rust sources refer
+/// to. This is the `gitoxide` counterpart of
+/// [`GitDatabase::tree`](super::GitDatabase::tree).
+pub fn read_tree(repo_path: &Path, rev: git2::Oid) -> CargoResult<GitTree> {
+    let repo = gix::open_opts(repo_path, gix::open::Options::isolated())?;
+    let mut files = Vec::new();
+    let mut manifests = Vec::new();
+    let mut references = Vec::new();
+    for entry in tree_entries(&repo, rev)? {
+        let Ok(path) = entry.filepath.to_str().map(str::to_owned) else {
+            continue;
+        };
+        if entry.mode.is_blob() {
+            let name = path.rsplit('/').next().unwrap_or_default();
+            if name == "Cargo.toml" {
+                let contents = repo.find_object(entry.oid)?.detach().data;
+                manifests.push((path.clone(), contents));
+            } else if name.ends_with(".rs") {
+                let paths = parent_references(&repo.find_object(entry.oid)?.data);
+                if !paths.is_empty() {
+                    references.push((path.clone(), paths));
+                }
+            }
+        }
+        files.push(path);
+    }
+    Ok(GitTree {
+        files,
+        manifests,
+        references,
+    })
+}
+
+/// Writes the files of the tree of `rev` in the repository at `repo_path`
+/// that are included in the `sparse` checkout to `workdir`. This is the
+/// `gitoxide` counterpart of the end of a sparse reset with `git2`.
+pub fn checkout_sparse(
+    repo_path: &Path,
+    rev: git2::Oid,
+    workdir: &Path,
+    sparse: &SparseCheckout,
+    pb: &mut Progress<'_>,
+) -> CargoResult<()> {
+    let repo = gix::open_opts(repo_path, gix::open::Options::isolated())?;
+    let entries: Vec<_> = tree_entries(&repo, rev)?
+        .into_iter()
+        .filter_map(|entry| {
+            let path = entry.filepath.to_str().ok()?.to_owned();
+            sparse.includes(&path).then_some((path, entry))
+        })
+        .collect();
+    for (i, (path, entry)) in entries.iter().enumerate() {
+        let mode = match entry.mode.kind() {
+            EntryKind::BlobExecutable => FileMode::Executable,
+            EntryKind::Link => FileMode::Symlink,
+            EntryKind::Commit => FileMode::Submodule,
+            EntryKind::Blob | EntryKind::Tree => FileMode::Regular,
+        };
+        if mode == FileMode::Submodule {
+            write_file(workdir, path, mode, &[])?;
+        } else {
+            write_file(workdir, path, mode, &repo.find_object(entry.oid)?.data)?;
+        }
+        pb.tick(i + 1, entries.len(), "")?;
+    }
+    Ok(())
+}
+
+/// Lists the entries of the tree of `rev`, except for trees themselves.
+fn tree_entries(repo: &gix::Repository, rev: git2::Oid) -> CargoResult<Vec<Entry>> {
+    let id = gix::ObjectId::try_from(rev.as_bytes())?;
+    let tree = repo.find_object(id)?.peel_to_tree()?;
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    tree.traverse().breadthfirst(&mut recorder)?;
+    Ok(recorder
+        .records
+        .into_iter()
+        .filter(|entry| !entry.mode.is_tree())
+        .collect())
+}
@@ -4,6 +4,7 @@ use crate::core::global_cache_tracker;
 use crate::core::GitReference;
 use crate::core::SourceId;
 use crate::core::{Dependency, Package, PackageId};
+use crate::sources::git::sparse::{SparseCheckout, TreePackages};
 use crate::sources::git::utils::rev_to_oid;
 use crate::sources::git::utils::GitRemote;
 use crate::sources::source::MaybePackage;
@@ -18,6 +19,7 @@ use crate::util::interning::InternedString;
 use crate::util::GlobalContext;
 use anyhow::Context as _;
 use cargo_util::paths::exclude_from_backups_and_indexing;
+use std::collections::BTreeSet;
 use std::fmt::{self, Debug, Formatter};
 use std::task::Poll;
 use tracing::trace;
@@ -43,7 +45,8 @@ use url::Url;
 /// │  ├── gimli-a0d193bd15a5ed96/
 /// │  │  ├── 8e73ef0/     # Git short ID for a certain revision
 /// │  │  ├── a2a4b78/
-/// │  │  └── e33d1ac/
+/// │  │  ├── e33d1ac/
+/// │  │  └── e33d1ac-sparse/  # only some packages, with `-Zgit=sparse-checkout`
 /// │  ├── log-c58e1db3de7c154d-shallow/
 /// │  │  └── 11eda98/
 /// └── db/
@@ -83,7 +86,8 @@ pub struct GitSource<'gctx> {
     /// A short string that uniquely identifies the version of the checkout.
     ///
     /// This is typically a 7-character string of the OID hash, automatically
-    /// increasing in size if it is ambiguous.
+    /// increasing in size if it is ambiguous, with a `-sparse` suffix for a
+    /// sparse checkout.
     ///
     /// This is set to `Some` after the git repo has been checked out
     /// (automatically handled via [`GitSource::block_until_ready`]).
@@ -94,6 +98,14 @@ pub struct GitSource<'gctx> {
     gctx: &'gctx GlobalContext,
     /// Disables status messages.
     quiet: bool,
+    /// Names of the packages queried so far, to only check out their
+    /// directories with `-Zgit=sparse-checkout`.
+    ///
+    /// This is `None` if sparse checkouts are disabled.
+    wanted: Option<BTreeSet<InternedString>>,
+    /// The packages in the tree of the locked revision, along with the
+    /// directories checked out, if the checkout is sparse.
+    sparse: Option<(TreePackages, SparseCheckout)>,
 }
 
 impl<'gctx> GitSource<'gctx> {
@@ -114,6 +126,11 @@ impl<'gctx> GitSource<'gctx> {
                 .git
                 .map_or(false, |features| features.shallow_deps),
         );
+        let wanted = gctx
+            .cli_unstable()
+            .git
+            .map_or(false, |features| features.sparse_checkout)
+            .then(BTreeSet::new);
 
         let source = GitSource {
             remote,
@@ -124,6 +141,8 @@ impl<'gctx> GitSource<'gctx> {
             ident: ident.into(),
             gctx,
             quiet: false,
+            wanted,
+            sparse: None,
         };
 
         Ok(source)
@@ -139,12 +158,43 @@ impl<'gctx> GitSource<'gctx> {
     /// haven't yet updated.
     pub fn read_packages(&mut self) -> CargoResult<Vec<Package>> {
         if self.path_source.is_none() {
+            // All packages are wanted, so a sparse checkout is of no use.
+            self.wanted = None;
             self.invalidate_cache();
             self.block_until_ready()?;
         }
         self.path_source.as_mut().unwrap().read_packages()
     }
 
+    /// Lists the commits reachable from the revision `to` but not from the
+    /// revision `from`, newest first, along with their summaries.
+    ///
+    /// Only the local database of this source is looked at, nothing is
+    /// fetched. Fails if the database doesn't have both revisions.
+    pub fn log(&self, from: &str, to: &str) -> CargoResult<Vec<(git2::Oid, String)>> {
+        let db_path = self.gctx.git_db_path().join(&self.ident);
+        let db = self.remote.db_at(db_path.as_path_unlocked())?;
+        db.log(git2::Oid::from_str(from)?, git2::Oid::from_str(to)?)
+    }
+
+    /// Records that the package called `name` is needed, for
+    /// `-Zgit=sparse-checkout`.
+    ///
+    /// If it's in the repository but outside the current sparse checkout, the
+    /// checkout is dropped so the next [`GitSource::block_until_ready`] widens it.
+    fn want(&mut self, name: InternedString) {
+        let Some(wanted) = &mut self.wanted else {
+            return;
+        };
+        wanted.insert(name);
+        if let Some((packages, sparse)) = &self.sparse {
+            if self.path_source.is_some() && packages.is_missing(&name, sparse) {
+                trace!("widening sparse checkout for `{name}`");
+                self.path_source = None;
+            }
+        }
+    }
+
     fn mark_used(&self) -> CargoResult<()> {
         self.gctx
             .deferred_global_last_use()?
@@ -243,6 +293,7 @@ impl<'gctx> Source for GitSource<'gctx> {
         kind: QueryKind,
         f: &mut dyn FnMut(IndexSummary),
     ) -> Poll<CargoResult<()>> {
+        self.want(dep.package_name());
         if let Some(src) = self.path_source.as_mut() {
             src.query(dep, kind, f)
         } else {
@@ -338,6 +389,27 @@ impl<'gctx> Source for GitSource<'gctx> {
         // <https://github.com/servo/servo/pull/14397>.
         let short_id = db.to_short_id(actual_rev)?;
 
+        // With `-Zgit=sparse-checkout`, find the directories of the packages
+        // queried so far by scanning the manifests of the revision. This
+        // falls back to a full checkout if any of them can't be found. If
+        // nothing has been queried yet, defer the checkout until something is.
+        let mut plan = None;
+        if let Some(wanted) = &self.wanted {
+            if wanted.is_empty() {
+                self.locked_rev = Revision::Locked(actual_rev);
+                return Ok(());
+            }
+            let tree = db.tree(actual_rev, self.gctx)?;
+            let packages = TreePackages::new(&tree);
+            if let Some(sparse) = packages.sparse_checkout(wanted.iter().map(|n| n.as_str())) {
+                plan = Some((packages, sparse));
+            }
+        }
+        let checkout_name = match plan {
+            Some(_) => format!("{}-sparse", short_id.as_str()),
+            None => short_id.as_str().to_owned(),
+        };
+
         // Check out `actual_rev` from the database to a scoped location on the
         // filesystem. This will use hard links and such to ideally make the
         // checkout operation here pretty fast.
@@ -345,9 +417,14 @@ impl<'gctx> Source for GitSource<'gctx> {
             .gctx
             .git_checkouts_path()
             .join(&self.ident)
-            .join(short_id.as_str());
+            .join(&checkout_name);
         let checkout_path = checkout_path.into_path_unlocked();
-        db.copy_to(actual_rev, &checkout_path, self.gctx)?;
+        let sparse = plan.as_ref().map(|(_, sparse)| sparse);
+        let checkout = db.copy_to(actual_rev, &checkout_path, sparse, self.gctx)?;
+        self.sparse = match (plan, checkout.sparse()) {
+            (Some((packages, _)), Some(sparse)) => Some((packages, sparse.clone())),
+            _ => None,
+        };
 
         let source_id = self
             .source_id
@@ -355,7 +432,7 @@ impl<'gctx> Source for GitSource<'gctx> {
         let path_source = RecursivePathSource::new(&checkout_path, source_id, self.gctx);
 
         self.path_source = Some(path_source);
-        self.short_id = Some(short_id.as_str().into());
+        self.short_id = Some(checkout_name.as_str().into());
         self.locked_rev = Revision::Locked(actual_rev);
         self.path_source.as_mut().unwrap().load()?;
 
@@ -369,6 +446,11 @@ impl<'gctx> Source for GitSource<'gctx> {
             id,
             self.remote
         );
+        self.want(id.name());
+        if self.path_source.is_none() {
+            // The checkout was deferred or is being widened.
+            self.block_until_ready()?;
+        }
         self.mark_used()?;
         self.path_source
             .as_mut()
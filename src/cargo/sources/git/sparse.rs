@@ -0,0 +1,403 @@
+//! Sparse checkouts of git dependencies for `-Zgit=sparse-checkout`.
+//!
+//! A git dependency often lives in a large repository hosting many packages,
+//! while only a few of them are actually needed for a build. Rather than
+//! materializing the entire tree of a revision, Cargo scans the manifests in
+//! the tree object of the [`GitDatabase`] with [`TreePackages`], picks the
+//! directories of the packages in use as a [`SparseCheckout`], and
+//! [`GitCheckout`] only writes the files within them.
+//!
+//! Similar to the cone mode of `git sparse-checkout`, a directory is checked
+//! out in full, along with the files directly inside each of its ancestors.
+//! That way a workspace root `Cargo.toml` is available for inheritance.
+//!
+//! Packages may also read files outside of their directory, for example with
+//! `include_str!("../../README.md")` or from a build script. The Rust sources
+//! in the tree are scanned for such paths with [`parent_references`], and
+//! their directories are checked out as well. A package referring to the
+//! repository root gets a full checkout instead.
+//!
+//! The checkout itself resets `HEAD` and the index to the revision, then
+//! writes the files of the directories with [`write_file`], as libgit2 would
+//! match pathspecs as globs rather than literal paths.
+//!
+//! [`GitDatabase`]: super::GitDatabase
+//! [`GitCheckout`]: super::GitCheckout
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Context as _;
+use cargo_util::paths;
+use tracing::debug;
+
+use crate::util::errors::CargoResult;
+
+/// Name of the file recording the directories of a sparse checkout, placed
+/// at the root of the checkout next to `.cargo-ok`.
+const SPARSE_CHECKOUT_FILE: &str = ".cargo-sparse";
+
+/// The files of the tree of a revision in a [`GitDatabase`](super::GitDatabase).
+///
+/// All paths are relative to the repository root, separated by `/`.
+pub struct GitTree {
+    /// Paths of all files, including symlinks and submodules.
+    pub files: Vec<String>,
+    /// Paths and contents of all `Cargo.toml` files.
+    pub manifests: Vec<(String, Vec<u8>)>,
+    /// Paths of the Rust sources referring to paths going up a directory,
+    /// along with those paths, as found by [`parent_references`].
+    pub references: Vec<(String, Vec<String>)>,
+}
+
+/// How a file of a tree is written to the working directory by [`write_file`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileMode {
+    Regular,
+    Executable,
+    Symlink,
+    /// A submodule, checked out later on.
+    Submodule,
+}
+
+/// The directories a sparse [`GitCheckout`](super::GitCheckout) materializes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SparseCheckout {
+    /// Directories checked out in full, relative to the repository root.
+    /// Never contains the root itself, which is a full checkout.
+    dirs: BTreeSet<String>,
+}
+
+impl SparseCheckout {
+    /// Checks if the file at `path` is part of this checkout.
+    pub fn includes(&self, path: &str) -> bool {
+        let parent = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+        self.contains_dir(path)
+            || parent.is_empty()
+            || self.dirs.iter().any(|dir| is_within(dir, parent))
+    }
+
+    /// Checks if the directory at `dir` is checked out in full.
+    pub fn contains_dir(&self, dir: &str) -> bool {
+        self.dirs.iter().any(|d| is_within(dir, d))
+    }
+
+    /// Checks if every directory of `other` is checked out in full.
+    pub fn covers(&self, other: &SparseCheckout) -> bool {
+        other.dirs.iter().all(|dir| self.contains_dir(dir))
+    }
+
+    /// Adds the directories of `other` to this checkout.
+    pub fn extend(&mut self, other: &SparseCheckout) {
+        self.dirs.extend(other.dirs.iter().cloned());
+    }
+
+    /// Reads the directories recorded in the checkout at `checkout_path`.
+    ///
+    /// Returns `None` if the checkout isn't sparse.
+    pub fn load(checkout_path: &Path) -> CargoResult<Option<SparseCheckout>> {
+        let path = checkout_path.join(SPARSE_CHECKOUT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let dirs = paths::read(&path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_owned)
+            .collect();
+        Ok(Some(SparseCheckout { dirs }))
+    }
+
+    /// Records the directories of this checkout in the checkout at `checkout_path`.
+    pub fn store(&self, checkout_path: &Path) -> CargoResult<()> {
+        let mut contents = String::new();
+        for dir in &self.dirs {
+            contents.push_str(dir);
+            contents.push('\n');
+        }
+        paths::write(checkout_path.join(SPARSE_CHECKOUT_FILE), contents)
+    }
+}
+
+/// The packages found in the manifests of a [`GitTree`].
+#[derive(Debug, Default)]
+pub struct TreePackages {
+    /// Directory of each package, by package name.
+    dirs: HashMap<String, String>,
+    /// Other directories a package directory refers to, such as the ones of
+    /// its path dependencies.
+    needs: HashMap<String, Vec<String>>,
+}
+
+impl TreePackages {
+    /// Scans the manifests of `tree` for packages.
+    ///
+    /// Manifests that fail to parse are skipped, so any package depending on
+    /// them is looked up in a full checkout instead.
+    pub fn new(tree: &GitTree) -> TreePackages {
+        let mut manifests = HashMap::new();
+        for (path, contents) in &tree.manifests {
+            let dir = path.strip_suffix("Cargo.toml").unwrap_or(path);
+            let dir = dir.strip_suffix('/').unwrap_or(dir);
+            let Some(table) = std::str::from_utf8(contents)
+                .ok()
+                .and_then(|s| s.parse::<toml::Table>().ok())
+            else {
+                debug!("skipping unparsable manifest `{path}` for sparse checkout");
+                continue;
+            };
+            manifests.insert(dir.to_owned(), table);
+        }
+
+        let files: HashSet<&str> = tree.files.iter().map(String::as_str).collect();
+        let mut dirs = HashSet::new();
+        for file in &files {
+            let mut file = *file;
+            while let Some((parent, _)) = file.rsplit_once('/') {
+                if !dirs.insert(parent) {
+                    break;
+                }
+                file = parent;
+            }
+        }
+
+        let mut packages = TreePackages::default();
+        for (dir, table) in &manifests {
+            let Some(package) = table.get("package").and_then(|p| p.as_table()) else {
+                continue;
+            };
+            if let Some(name) = package.get("name").and_then(|n| n.as_str()) {
+                packages.dirs.insert(name.to_owned(), dir.clone());
+            }
+
+            let mut needs = Vec::new();
+            for key in ["build", "readme", "license-file"] {
+                if let Some(path) = package.get(key).and_then(|f| join(dir, f.as_str()?)) {
+                    needs.extend(file_dir(dir, &path));
+                }
+            }
+            let targets = table.get("lib").into_iter().chain(
+                table
+                    .get("bin")
+                    .and_then(|b| b.as_array())
+                    .into_iter()
+                    .flatten(),
+            );
+            for target in targets {
+                if let Some(path) = target.get("path").and_then(|f| join(dir, f.as_str()?)) {
+                    needs.extend(file_dir(dir, &path));
+                }
+            }
+
+            // Paths in `include_str!` and the like are relative to the source
+            // file, while build scripts run in the package directory.
+            let references = tree
+                .references
+                .iter()
+                .filter(|(source, _)| is_within(source, dir));
+            for (source, paths) in references {
+                let source_dir = source.rsplit_once('/').map_or("", |(parent, _)| parent);
+                for path in paths {
+                    let candidates = match path.strip_prefix('/') {
+                        // Likely `concat!(env!("CARGO_MANIFEST_DIR"), "/../..")`.
+                        Some(path) => [join(dir, path), None],
+                        None => [join(source_dir, path), join(dir, path)],
+                    };
+                    for path in candidates.into_iter().flatten() {
+                        if files.contains(path.as_str()) {
+                            needs.extend(file_dir(dir, &path));
+                        } else if path.is_empty() || dirs.contains(path.as_str()) {
+                            needs.push(path);
+                        }
+                    }
+                }
+            }
+
+            for deps in dependency_tables(table) {
+                for (name, dep) in deps {
+                    if let Some(path) = dep.get("path").and_then(|p| p.as_str()) {
+                        needs.extend(join(dir, path));
+                    } else if dep.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+                        let path = workspace_root(&manifests, dir).and_then(|root| {
+                            let dep = manifests[root]
+                                .get("workspace")?
+                                .get("dependencies")?
+                                .get(name)?;
+                            join(root, dep.get("path")?.as_str()?)
+                        });
+                        needs.extend(path);
+                    }
+                }
+            }
+            packages.needs.insert(dir.clone(), needs);
+        }
+        packages
+    }
+
+    /// Checks if the package called `name` is in the tree but not in the
+    /// checkout `sparse`.
+    pub fn is_missing(&self, name: &str, sparse: &SparseCheckout) -> bool {
+        self.dirs
+            .get(name)
+            .map_or(false, |dir| !sparse.contains_dir(dir))
+    }
+
+    /// Plans a sparse checkout of the packages called `names`, along with
+    /// the directories they refer to.
+    ///
+    /// Returns `None` if a full checkout is needed instead, either because a
+    /// package can't be found or because it refers to the repository root.
+    pub fn sparse_checkout<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Option<SparseCheckout> {
+        let mut pending = names
+            .into_iter()
+            .map(|name| self.dirs.get(name).cloned())
+            .collect::<Option<Vec<_>>>()?;
+        let mut dirs = BTreeSet::new();
+        while let Some(dir) = pending.pop() {
+            if dir.is_empty() {
+                return None;
+            }
+            if !dirs.insert(dir.clone()) {
+                continue;
+            }
+            pending.extend(self.needs.get(&dir).into_iter().flatten().cloned());
+        }
+        Some(SparseCheckout { dirs })
+    }
+}
+
+/// Finds the paths going up a directory in the Rust source `contents`, such
+/// as the one of `include_str!("../README.md")` or a file a build script
+/// reads.
+///
+/// This is a heuristic, as anything looking like such a path counts, even in
+/// comments. At worst more directories are checked out than needed.
+pub fn parent_references(contents: &[u8]) -> Vec<String> {
+    let contents = String::from_utf8_lossy(contents);
+    let is_delimiter = |c: char| {
+        c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '(' | ')' | '{' | '}' | ',')
+    };
+    let mut references = Vec::new();
+    for (i, _) in contents.match_indices("..") {
+        let start = contents[..i].rfind(is_delimiter).map_or(0, |p| p + 1);
+        let end = contents[i..]
+            .find(is_delimiter)
+            .map_or(contents.len(), |p| i + p);
+        let path = &contents[start..end];
+        if path.split('/').any(|c| c == "..") {
+            references.push(path.to_owned());
+        }
+    }
+    references.sort_unstable();
+    references.dedup();
+    references
+}
+
+/// Writes the file at `path` of a tree, relative to the repository root, to
+/// the working directory `workdir`.
+///
+/// The `data` is the contents of a file, or the target of a symlink.
+pub fn write_file(workdir: &Path, path: &str, mode: FileMode, data: &[u8]) -> CargoResult<()> {
+    let dst = workdir.join(path);
+    if let Some(parent) = dst.parent() {
+        paths::create_dir_all(parent)?;
+    }
+    // Widening a checkout writes the files already there again.
+    if dst.symlink_metadata().map_or(false, |meta| !meta.is_dir()) {
+        paths::remove_file(&dst)?;
+    }
+    match mode {
+        FileMode::Regular => paths::write(&dst, data)?,
+        FileMode::Executable => {
+            paths::write(&dst, data)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&dst, std::fs::Permissions::from_mode(0o755))
+                    .with_context(|| format!("failed to set permissions of `{}`", dst.display()))?;
+            }
+        }
+        FileMode::Symlink => {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(paths::bytes2path(data)?, &dst)
+                .with_context(|| format!("failed to create symlink `{}`", dst.display()))?;
+            // Like git with `core.symlinks` disabled.
+            #[cfg(not(unix))]
+            paths::write(&dst, data)?;
+        }
+        FileMode::Submodule => paths::create_dir_all(&dst)?,
+    }
+    Ok(())
+}
+
+/// Checks if `path` is `dir` itself or inside of it.
+fn is_within(path: &str, dir: &str) -> bool {
+    dir.is_empty()
+        || path
+            .strip_prefix(dir)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Returns the directory of the file at `path` for the package in `dir` to
+/// check out, unless the file is directly inside an ancestor of `dir`, as
+/// those are always checked out.
+fn file_dir(dir: &str, path: &str) -> Option<String> {
+    let parent = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+    (!is_within(dir, parent)).then(|| parent.to_owned())
+}
+
+/// Joins the relative `path` onto the directory `base`, both relative to the
+/// repository root. Returns `None` if the result is outside the repository.
+fn join(base: &str, path: &str) -> Option<String> {
+    if path.starts_with('/') || path.contains('\\') {
+        return None;
+    }
+    let mut components = base
+        .split('/')
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            c => components.push(c),
+        }
+    }
+    Some(components.join("/"))
+}
+
+/// Finds the directory of the closest manifest with a `[workspace]` table,
+/// starting from `dir` itself.
+fn workspace_root<'a>(manifests: &'a HashMap<String, toml::Table>, dir: &str) -> Option<&'a str> {
+    let mut dir = dir;
+    loop {
+        if let Some((root, table)) = manifests.get_key_value(dir) {
+            if table.contains_key("workspace") {
+                return Some(root);
+            }
+        }
+        if dir.is_empty() {
+            return None;
+        }
+        dir = dir.rsplit_once('/').map_or("", |(parent, _)| parent);
+    }
+}
+
+/// Iterates the dependency tables of a manifest that matter for building it
+/// as a dependency, including platform-specific ones.
+fn dependency_tables(table: &toml::Table) -> impl Iterator<Item = &toml::Table> {
+    const KEYS: [&str; 3] = ["dependencies", "build-dependencies", "build_dependencies"];
+    let targets = table
+        .get("target")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    std::iter::once(table)
+        .chain(targets.filter_map(|t| t.as_table()))
+        .flat_map(|t| KEYS.iter().filter_map(|key| t.get(*key)?.as_table()))
+}
@@ -5,6 +5,9 @@ use crate::core::{GitReference, Verbosity};
 use crate::sources::git::fetch::RemoteKind;
 use crate::sources::git::oxide;
 use crate::sources::git::oxide::cargo_config_to_gitoxide_overrides;
+use crate::sources::git::sparse::{
+    parent_references, write_file, FileMode, GitTree, SparseCheckout,
+};
 use crate::util::errors::CargoResult;
 use crate::util::{
     human_readable_bytes, network, GlobalContext, IntoUrl, MetricsCounter, Progress,
@@ -78,6 +81,8 @@ pub struct GitCheckout<'a> {
     revision: git2::Oid,
     /// Underlying Git repository instance for this checkout.
     repo: git2::Repository,
+    /// The directories materialized if this is a sparse checkout.
+    sparse: Option<SparseCheckout>,
 }
 
 impl GitRemote {
@@ -164,11 +169,16 @@ impl GitRemote {
 
 impl GitDatabase {
     /// Checkouts to a revision at `dest`ination from this database.
-    #[tracing::instrument(skip(self, gctx))]
+    ///
+    /// With `sparse`, only the files of the given directories are checked
+    /// out. An existing sparse checkout is widened to include them as well,
+    /// if it doesn't already.
+    #[tracing::instrument(skip(self, sparse, gctx))]
     pub fn copy_to(
         &self,
         rev: git2::Oid,
         dest: &Path,
+        sparse: Option<&SparseCheckout>,
         gctx: &GlobalContext,
     ) -> CargoResult<GitCheckout<'_>> {
         // If the existing checkout exists, and it is fresh, use it.
@@ -180,13 +190,75 @@ impl GitDatabase {
             .map(|repo| GitCheckout::new(self, rev, repo))
             .filter(|co| co.is_fresh())
         {
-            Some(co) => co,
-            None => GitCheckout::clone_into(dest, self, rev, gctx)?,
+            Some(mut co) => {
+                co.sparse = SparseCheckout::load(&co.path)?;
+                if let (Some(recorded), Some(wanted)) = (&mut co.sparse, sparse) {
+                    if !recorded.covers(wanted) {
+                        recorded.extend(wanted);
+                        co.reset(gctx)?;
+                    }
+                }
+                co
+            }
+            None => GitCheckout::clone_into(dest, self, rev, sparse, gctx)?,
         };
         checkout.update_submodules(gctx)?;
         Ok(checkout)
     }
 
+    /// Lists the files of the tree of `rev`, along with the contents of the
+    /// manifests and the paths the Rust sources refer to, to plan a sparse
+    /// checkout.
+    pub fn tree(&self, rev: git2::Oid, gctx: &GlobalContext) -> CargoResult<GitTree> {
+        if gctx
+            .cli_unstable()
+            .gitoxide
+            .map_or(false, |git| git.checkout)
+        {
+            return oxide::read_tree(&self.path, rev);
+        }
+
+        let tree = self.repo.find_object(rev, None)?.peel_to_tree()?;
+        let mut files = Vec::new();
+        let mut manifests = Vec::new();
+        let mut references = Vec::new();
+        let mut error = None;
+        tree.walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+            let (Some(name), Some(kind)) = (entry.name(), entry.kind()) else {
+                return git2::TreeWalkResult::Ok;
+            };
+            if kind == ObjectType::Tree {
+                return git2::TreeWalkResult::Ok;
+            }
+            let path = format!("{root}{name}");
+            if kind == ObjectType::Blob && (name == "Cargo.toml" || name.ends_with(".rs")) {
+                let blob = match self.repo.find_blob(entry.id()) {
+                    Ok(blob) => blob,
+                    Err(e) => {
+                        error = Some(e);
+                        return git2::TreeWalkResult::Abort;
+                    }
+                };
+                if name == "Cargo.toml" {
+                    manifests.push((path.clone(), blob.content().to_vec()));
+                } else {
+                    let paths = parent_references(blob.content());
+                    if !paths.is_empty() {
+                        references.push((path.clone(), paths));
+                    }
+                }
+            }
+            files.push(path);
+            git2::TreeWalkResult::Ok
+        })
+        .or_else(|e| error.take().map_or(Err(e), Err))?;
+        Ok(GitTree {
+            files,
+            manifests,
+            references,
+        })
+    }
+
     /// Get a short OID for a `revision`, usually 7 chars or more if ambiguous.
     pub fn to_short_id(&self, revision: git2::Oid) -> CargoResult<GitShortID> {
         let obj = self.repo.find_object(revision, None)?;
@@ -202,6 +274,22 @@ impl GitDatabase {
     pub fn resolve(&self, r: &GitReference) -> CargoResult<git2::Oid> {
         resolve_ref(r, &self.repo)
     }
+
+    /// Lists the commits reachable from `to` but not from `from`, newest
+    /// first, along with their summaries.
+    pub fn log(&self, from: git2::Oid, to: git2::Oid) -> CargoResult<Vec<(git2::Oid, String)>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        revwalk.push(to)?;
+        revwalk.hide(from)?;
+        revwalk
+            .map(|oid| {
+                let oid = oid?;
+                let commit = self.repo.find_commit(oid)?;
+                Ok((oid, commit.summary().unwrap_or_default().to_string()))
+            })
+            .collect()
+    }
 }
 
 /// Resolves [`GitReference`] to an object ID with objects the `repo` currently has.
@@ -265,6 +353,7 @@ impl<'a> GitCheckout<'a> {
             database,
             revision,
             repo,
+            sparse: None,
         }
     }
 
@@ -275,10 +364,13 @@ impl<'a> GitCheckout<'a> {
 
     /// Clone a repo for a `revision` into a local path from a `datatabase`.
     /// This is a filesystem-to-filesystem clone.
+    ///
+    /// With `sparse`, only the files of the given directories are checked out.
     fn clone_into(
         into: &Path,
         database: &'a GitDatabase,
         revision: git2::Oid,
+        sparse: Option<&SparseCheckout>,
         gctx: &GlobalContext,
     ) -> CargoResult<GitCheckout<'a>> {
         let dirname = into.parent().unwrap();
@@ -328,11 +420,17 @@ impl<'a> GitCheckout<'a> {
         })?;
         let repo = repo.unwrap();
 
-        let checkout = GitCheckout::new(database, revision, repo);
+        let mut checkout = GitCheckout::new(database, revision, repo);
+        checkout.sparse = sparse.cloned();
         checkout.reset(gctx)?;
         Ok(checkout)
     }
 
+    /// The directories checked out, if this is a sparse checkout.
+    pub fn sparse(&self) -> Option<&SparseCheckout> {
+        self.sparse.as_ref()
+    }
+
     /// Checks if the `HEAD` of this checkout points to the expected revision.
     fn is_fresh(&self) -> bool {
         match self.repo.revparse_single("HEAD") {
@@ -357,6 +455,9 @@ impl<'a> GitCheckout<'a> {
     /// ready to go. Hence if we start to do a reset, we make sure this file
     /// *doesn't* exist, and then once we're done we create the file.
     ///
+    /// For a sparse checkout, only the files of its directories are written,
+    /// and the directories are recorded before marking it ready.
+    ///
     /// [`.cargo-ok`]: CHECKOUT_READY_LOCK
     fn reset(&self, gctx: &GlobalContext) -> CargoResult<()> {
         let ok_file = self.path.join(CHECKOUT_READY_LOCK);
@@ -369,7 +470,13 @@ impl<'a> GitCheckout<'a> {
         }
 
         let object = self.repo.find_object(self.revision, None)?;
-        reset(&self.repo, &object, gctx)?;
+        match &self.sparse {
+            Some(sparse) => {
+                reset_sparse(&self.repo, &object, sparse, gctx)?;
+                sparse.store(&self.path)?;
+            }
+            None => reset(&self.repo, &object, gctx)?,
+        }
         paths::create(ok_file)?;
         Ok(())
     }
@@ -380,8 +487,30 @@ impl<'a> GitCheckout<'a> {
     /// Submodules set to `none` won't be fetched.
     ///
     /// [^1]: <https://git-scm.com/docs/git-submodule#Documentation/git-submodule.txt-none>
+    ///
+    /// Submodules outside of a sparse checkout are left alone.
     fn update_submodules(&self, gctx: &GlobalContext) -> CargoResult<()> {
-        return update_submodules(&self.repo, gctx, self.remote_url().as_str());
+        debug!("update submodules for: {:?}", self.repo.workdir().unwrap());
+
+        for mut child in self.repo.submodules()? {
+            if let Some(sparse) = &self.sparse {
+                if !child
+                    .path()
+                    .to_str()
+                    .map_or(false, |p| sparse.contains_dir(p))
+                {
+                    continue;
+                }
+            }
+            update_submodule(&self.repo, &mut child, gctx, self.remote_url().as_str())
+                .with_context(|| {
+                    format!(
+                        "failed to update submodule `{}`",
+                        child.name().unwrap_or("")
+                    )
+                })?;
+        }
+        return Ok(());
 
         /// Recursive helper for [`GitCheckout::update_submodules`].
         fn update_submodules(
@@ -813,6 +942,63 @@ fn reset(repo: &git2::Repository, obj: &git2::Object<'_>, gctx: &GlobalContext)
     Ok(())
 }
 
+/// Like [`reset()`], but only writes the files included in the `sparse`
+/// checkout to the working directory.
+///
+/// This does a mixed reset of `HEAD` and the index, then writes the files
+/// from the tree of `obj` itself. The pathspecs of a libgit2 checkout are
+/// globs, so they can't select directories with special characters.
+fn reset_sparse(
+    repo: &git2::Repository,
+    obj: &git2::Object<'_>,
+    sparse: &SparseCheckout,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    debug!("doing sparse reset of {sparse:?}");
+    repo.reset(obj, git2::ResetType::Mixed, None)?;
+    let workdir = repo.workdir().unwrap();
+    let mut pb = Progress::new("Checkout", gctx);
+    if gctx
+        .cli_unstable()
+        .gitoxide
+        .map_or(false, |git| git.checkout)
+    {
+        oxide::checkout_sparse(repo.path(), obj.id(), workdir, sparse, &mut pb)?;
+        debug!("sparse reset done");
+        return Ok(());
+    }
+
+    let tree = obj.peel_to_tree()?;
+    let mut entries = Vec::new();
+    tree.walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+        let Some(name) = entry.name() else {
+            return git2::TreeWalkResult::Ok;
+        };
+        let path = format!("{root}{name}");
+        let mode = match entry.filemode() {
+            0o100755 => FileMode::Executable,
+            0o120000 => FileMode::Symlink,
+            0o160000 => FileMode::Submodule,
+            0o040000 => return git2::TreeWalkResult::Ok,
+            _ => FileMode::Regular,
+        };
+        if sparse.includes(&path) {
+            entries.push((path, mode, entry.id()));
+        }
+        git2::TreeWalkResult::Ok
+    })?;
+    for (i, (path, mode, id)) in entries.iter().enumerate() {
+        let data = match mode {
+            FileMode::Submodule => Vec::new(),
+            _ => repo.find_blob(*id)?.content().to_vec(),
+        };
+        write_file(workdir, path, *mode, &data)?;
+        pb.tick(i + 1, entries.len(), "")?;
+    }
+    debug!("sparse reset done");
+    Ok(())
+}
+
 /// Prepares the callbacks for fetching a git repository.
 ///
 /// The main purpose of this function is to construct everything before a fetch.
@@ -7,6 +7,7 @@ use std::task::Poll;
 
 use crate::core::{Dependency, EitherManifest, Manifest, Package, PackageId, SourceId};
 use crate::ops;
+use crate::ops::lockfile::is_lockfile_name;
 use crate::sources::source::MaybePackage;
 use crate::sources::source::QueryKind;
 use crate::sources::source::Source;
@@ -19,7 +20,7 @@ use crate::util::GlobalContext;
 use anyhow::Context as _;
 use cargo_util::paths;
 use filetime::FileTime;
-use gix::bstr::{BString, ByteVec};
+use gix::bstr::{BString, ByteSlice, ByteVec};
 use gix::dir::entry::Status;
 use gix::index::entry::Stage;
 use ignore::gitignore::GitignoreBuilder;
@@ -148,6 +149,7 @@ impl<'gctx> Source for PathSource<'gctx> {
                 QueryKind::Exact => dep.matches(s),
                 QueryKind::Alternatives => true,
                 QueryKind::Normalized => dep.matches(s),
+                QueryKind::RejectedVersions => false,
             };
             if matched {
                 f(IndexSummary::Candidate(s.clone()))
@@ -335,6 +337,7 @@ impl<'gctx> Source for RecursivePathSource<'gctx> {
                 QueryKind::Exact => dep.matches(s),
                 QueryKind::Alternatives => true,
                 QueryKind::Normalized => dep.matches(s),
+                QueryKind::RejectedVersions => false,
             };
             if matched {
                 f(IndexSummary::Candidate(s.clone()))
@@ -505,7 +508,7 @@ fn _list_files(pkg: &Package, gctx: &GlobalContext) -> CargoResult<Vec<PathBuf>>
         };
 
         let rel = relative_path.as_os_str();
-        if rel == "Cargo.lock" {
+        if rel.to_str().is_some_and(is_lockfile_name) {
             return pkg.include_lockfile();
         } else if rel == "Cargo.toml" {
             return true;
@@ -625,10 +628,11 @@ fn list_files_gix(
     for item in repo
         .dirwalk_iter(index.clone(), pathspec, Default::default(), options)?
         .filter(|res| {
-            // Don't include Cargo.lock if it is untracked. Packaging will
-            // generate a new one as needed.
+            // Don't include Cargo.lock or named lock files if they are
+            // untracked. Packaging will generate a new one as needed.
             res.as_ref().map_or(true, |item| {
-                !(item.entry.status == Status::Untracked && item.entry.rela_path == "Cargo.lock")
+                !(item.entry.status == Status::Untracked
+                    && item.entry.rela_path.to_str().is_ok_and(is_lockfile_name))
             })
         })
         .map(|res| res.map(|item| (item.entry.rela_path, item.entry.disk_kind)))
@@ -15,10 +15,12 @@ use crate::util::{auth, Filesystem, GlobalContext, IntoUrl, Progress, ProgressSt
 use anyhow::Context as _;
 use cargo_credential::Operation;
 use cargo_util::paths;
+use cargo_util::registry::make_dep_path;
 use curl::easy::{Easy, List};
 use curl::multi::{EasyHandle, Multi};
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
@@ -34,9 +36,19 @@ const LAST_MODIFIED: &'static str = "last-modified";
 const WWW_AUTHENTICATE: &'static str = "www-authenticate";
 const IF_NONE_MATCH: &'static str = "if-none-match";
 const IF_MODIFIED_SINCE: &'static str = "if-modified-since";
+const INDEX_SIGNATURE: &'static str = "cargo-index-signature";
 
 const UNKNOWN: &'static str = "Unknown";
 
+/// Name of the file storing the [`ChangesState`], next to the cached
+/// `config.json`.
+const CHANGES_STATE: &'static str = "changes.json";
+
+/// Path the request for the index change log is tracked as in [`Downloads`].
+///
+/// Index files are always in a subdirectory, so this can't clash with one.
+const CHANGES_LOG: &'static str = "changes";
+
 /// A registry served by the HTTP-based registry API.
 ///
 /// This type is primarily accessed through the [`RegistryData`] trait.
@@ -52,6 +64,11 @@ const UNKNOWN: &'static str = "Unknown";
 /// cost is somewhat mitigated by the use of HTTP conditional fetches (`If-Modified-Since` and
 /// `If-None-Match` for `ETag`s) which can be efficiently handled by HTTP/2.
 ///
+/// With `-Zsparse-index-changes`, a registry listing an index change log in
+/// its `config.json` can also tell which index files changed since the last
+/// sync in a single request, so that only those are revalidated. See
+/// [`HttpRegistry::sync_changes`].
+///
 /// [RFC 2789]: https://github.com/rust-lang/rfcs/pull/2789
 pub struct HttpRegistry<'gctx> {
     /// The name of this source, a unique string (across all sources) used as
@@ -108,6 +125,43 @@ pub struct HttpRegistry<'gctx> {
 
     /// Disables status messages.
     quiet: bool,
+
+    /// Has the index change log been requested since the last index update?
+    changes_synced: bool,
+
+    /// Index files known to be up-to-date from the index change log, if the
+    /// registry has one.
+    changes: Option<ChangesState>,
+}
+
+/// The local state of the index change log of a registry, see
+/// [`RegistryConfig::changes`].
+#[derive(Default, Serialize, Deserialize)]
+struct ChangesState {
+    /// The token returned by the change log on the last sync.
+    token: Option<String>,
+    /// Index files validated since the last sync, with the version that was
+    /// validated.
+    ///
+    /// Those that the change log does not list as changed since then are
+    /// still up-to-date, as long as the cached version is the same.
+    unchanged: BTreeMap<PathBuf, String>,
+    /// Whether there is anything new to save.
+    #[serde(skip)]
+    dirty: bool,
+}
+
+/// A response from the index change log.
+#[derive(Deserialize)]
+struct ChangesResponse {
+    /// The token to pass as `since` on the next sync.
+    token: String,
+    /// The crates whose index files changed since the given token.
+    ///
+    /// `None` if the registry can't tell, for example if no token was given
+    /// or if it is too old, in which case every index file has to be
+    /// revalidated.
+    crates: Option<Vec<String>>,
 }
 
 /// State for currently pending index file downloads.
@@ -143,6 +197,12 @@ struct Download<'gctx> {
     /// The path of the package that we're downloading.
     path: PathBuf,
 
+    /// The URL being downloaded.
+    url: String,
+
+    /// The version of the cached index file, if the request is conditional.
+    index_version: Option<String>,
+
     /// Actual downloaded data, updated throughout the lifetime of this download.
     data: RefCell<Vec<u8>>,
 
@@ -159,10 +219,25 @@ struct Headers {
     last_modified: Option<String>,
     etag: Option<String>,
     www_authenticate: Vec<String>,
+    index_signature: Option<String>,
     /// All headers, including explicit headers above.
     all: Vec<String>,
 }
 
+impl Headers {
+    /// The version of the index file in the response, from its `ETag` or
+    /// `Last-Modified` header.
+    fn index_version(&self) -> Option<String> {
+        if let Some(etag) = &self.etag {
+            Some(format!("{}: {}", ETAG, etag))
+        } else if let Some(lm) = &self.last_modified {
+            Some(format!("{}: {}", LAST_MODIFIED, lm))
+        } else {
+            None
+        }
+    }
+}
+
 /// HTTP status code [`HttpRegistry`] cares about.
 enum StatusCode {
     Success,
@@ -234,6 +309,8 @@ impl<'gctx> HttpRegistry<'gctx> {
             login_url: None,
             auth_error_headers: vec![],
             quiet: false,
+            changes_synced: false,
+            changes: None,
         })
     }
 
@@ -313,7 +390,7 @@ impl<'gctx> HttpRegistry<'gctx> {
             );
             let mut handle = self.multi.remove(handle)?;
             let data = download.data.take();
-            let url = self.full_url(&download.path);
+            let url = download.url.clone();
             let result = match download.retry.r#try(|| {
                 result.with_context(|| format!("failed to download from `{}`", url))?;
                 let code = handle.response_code()?;
@@ -335,11 +412,27 @@ impl<'gctx> HttpRegistry<'gctx> {
                 };
                 Ok((data, code))
             }) {
-                RetryResult::Success((data, code)) => Ok(CompletedDownload {
-                    response_code: code,
-                    data,
-                    header_map: download.header_map.take(),
-                }),
+                RetryResult::Success((data, code)) => {
+                    let header_map = download.header_map.take();
+                    match code {
+                        StatusCode::Success => {
+                            if let Some(index_version) = header_map.index_version() {
+                                self.record_unchanged(&download.path, &index_version);
+                            }
+                        }
+                        StatusCode::NotModified => {
+                            if let Some(index_version) = &download.index_version {
+                                self.record_unchanged(&download.path, index_version);
+                            }
+                        }
+                        StatusCode::NotFound | StatusCode::Unauthorized => {}
+                    }
+                    Ok(CompletedDownload {
+                        response_code: code,
+                        data,
+                        header_map,
+                    })
+                }
                 RetryResult::Err(e) => Err(e),
                 RetryResult::Retry(sleep) => {
                     debug!(target: "network", "download retry {:?} for {sleep}ms", download.path);
@@ -425,6 +518,7 @@ impl<'gctx> HttpRegistry<'gctx> {
             LoadResponse::Data {
                 raw_data,
                 index_version: _,
+                signature: _,
             } => {
                 trace!("config loaded");
                 self.registry_config = Some(serde_json::from_slice(&raw_data)?);
@@ -444,6 +538,271 @@ impl<'gctx> HttpRegistry<'gctx> {
         }
     }
 
+    /// Check if the index change log says that the cached `index_version` of
+    /// the index file of `path` is up-to-date.
+    fn is_unchanged(&self, path: &Path, index_version: &str) -> bool {
+        let unchanged = self
+            .changes
+            .as_ref()
+            .and_then(|changes| changes.unchanged.get(path))
+            .is_some_and(|version| version == index_version);
+        if unchanged {
+            trace!(
+                "using local {} as the index change log has no update",
+                path.display()
+            );
+        }
+        unchanged
+    }
+
+    /// Fetches the index change log, if the registry has one, to learn which
+    /// of the index files validated since the last sync are still
+    /// up-to-date.
+    ///
+    /// This only happens once per index update, before any other index file
+    /// is requested, so that the files validated afterwards are up-to-date
+    /// as of the token returned by the change log. The request is queued
+    /// with the index file downloads, and is pending until
+    /// [`RegistryData::block_until_ready`] completes it.
+    fn sync_changes(&mut self) -> Poll<CargoResult<()>> {
+        if self.changes_synced
+            || !self.requested_update
+            || !self.gctx.cli_unstable().sparse_index_changes
+            || self.gctx.offline()
+            || self.gctx.cli_unstable().no_index_update
+        {
+            return Poll::Ready(Ok(()));
+        }
+        let changes_path = Path::new(CHANGES_LOG);
+        if self.downloads.pending_paths.contains(changes_path) {
+            return Poll::Pending;
+        }
+        if let Some(result) = self.downloads.results.remove(changes_path) {
+            self.changes_synced = true;
+            self.finish_changes(result)?;
+            return Poll::Ready(Ok(()));
+        }
+
+        let config = ready!(self.config()?);
+        let (config_auth_required, changes_url) = (config.auth_required, config.changes.clone());
+        let auth_required = self.auth_required || config_auth_required;
+        let Some(changes_url) = changes_url else {
+            self.changes_synced = true;
+            return Poll::Ready(Ok(()));
+        };
+        let mut url = match self.url.join(&changes_url) {
+            Ok(url) => url,
+            Err(e) => {
+                self.changes_synced = true;
+                self.warn_changes_failed(
+                    anyhow::Error::from(e)
+                        .context(format!("invalid index change log url `{changes_url}`")),
+                )?;
+                return Poll::Ready(Ok(()));
+            }
+        };
+
+        let state_path = self
+            .assert_index_locked(&self.index_path)
+            .join(CHANGES_STATE);
+        let state: ChangesState = match fs::read(&state_path) {
+            Ok(raw_data) => serde_json::from_slice(&raw_data).unwrap_or_else(|e| {
+                debug!("failed to decode {CHANGES_STATE}: {e}");
+                ChangesState::default()
+            }),
+            Err(_) => ChangesState::default(),
+        };
+        if let Some(since) = &state.token {
+            url.query_pairs_mut().append_pair("since", since);
+        }
+        self.changes = Some(state);
+
+        self.start_fetch()?;
+        let mut headers = List::new();
+        headers.append("cargo-protocol: version=1")?;
+        headers.append("accept: application/json")?;
+        if auth_required {
+            let authorization = auth::auth_token(
+                self.gctx,
+                &self.source_id,
+                self.login_url.as_ref(),
+                Operation::Read,
+                self.auth_error_headers.clone(),
+                true,
+            )?;
+            headers.append(&format!("Authorization: {}", authorization))?;
+        }
+        self.start_download(changes_path, url.as_str(), headers, None)?;
+        Poll::Pending
+    }
+
+    /// Updates the state of the index change log with the response of the
+    /// request started by [`HttpRegistry::sync_changes`].
+    fn finish_changes(&mut self, result: CargoResult<CompletedDownload>) -> CargoResult<()> {
+        let response = result.and_then(|result| match result.response_code {
+            StatusCode::Success => serde_json::from_slice::<ChangesResponse>(&result.data)
+                .context("failed to parse the index change log"),
+            StatusCode::NotModified => {
+                anyhow::bail!("the index change log said not modified (HTTP 304)")
+            }
+            StatusCode::NotFound => anyhow::bail!("the index change log was not found"),
+            StatusCode::Unauthorized => {
+                anyhow::bail!("the index change log requires authorization (HTTP 401)")
+            }
+        });
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                // Without the change log, every index file is revalidated.
+                self.changes = None;
+                return self.warn_changes_failed(e);
+            }
+        };
+        let state = self.changes.get_or_insert_with(ChangesState::default);
+        match response.crates {
+            Some(crates) => {
+                debug!("{} crates changed since the last sync", crates.len());
+                for name in crates {
+                    let path = make_dep_path(&name.to_lowercase(), false);
+                    state.unchanged.remove(Path::new(&path));
+                }
+            }
+            None => {
+                debug!("the index change log can't tell what changed since the last sync");
+                state.unchanged.clear();
+            }
+        }
+        state.token = Some(response.token);
+        state.dirty = true;
+        self.save_changes();
+        Ok(())
+    }
+
+    /// Warns that the index change log couldn't be used.
+    fn warn_changes_failed(&self, e: anyhow::Error) -> CargoResult<()> {
+        self.gctx.shell().warn(format!(
+            "failed to fetch the index change log of {}, \
+             checking every index file for updates\n\
+             {e:?}",
+            self.source_id.display_index(),
+        ))
+    }
+
+    /// Records that `index_version` of the index file of `path` is
+    /// up-to-date as of the last sync of the index change log.
+    fn record_unchanged(&mut self, path: &Path, index_version: &str) {
+        if let Some(changes) = self.changes.as_mut() {
+            if path != Path::new(RegistryConfig::NAME) && path != Path::new(CHANGES_LOG) {
+                changes
+                    .unchanged
+                    .insert(path.to_path_buf(), index_version.to_string());
+                changes.dirty = true;
+            }
+        }
+    }
+
+    /// Writes the state of the index change log, if it changed.
+    fn save_changes(&mut self) {
+        let Some(changes) = self.changes.as_mut().filter(|changes| changes.dirty) else {
+            return;
+        };
+        changes.dirty = false;
+        let state_path = self.index_path.as_path_unlocked().join(CHANGES_STATE);
+        let result = serde_json::to_vec(changes)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| paths::write(&state_path, data));
+        if let Err(e) = result {
+            debug!("failed to write {CHANGES_STATE}: {e}");
+        }
+    }
+
+    /// Queues the download of `url` to the pool of requests that cURL
+    /// manages, tracked as `path` until [`RegistryData::load`] takes its
+    /// result.
+    fn start_download(
+        &mut self,
+        path: &Path,
+        url: &str,
+        headers: List,
+        index_version: Option<&str>,
+    ) -> CargoResult<()> {
+        let mut handle = http_handle(self.gctx)?;
+        debug!(target: "network", "fetch {}", url);
+        handle.get(true)?;
+        handle.url(url)?;
+        handle.follow_location(true)?;
+
+        // Enable HTTP/2 if possible.
+        crate::try_old_curl_http2_pipewait!(self.multiplexing, handle);
+
+        handle.http_headers(headers)?;
+
+        // We're going to have a bunch of downloads all happening "at the same time".
+        // So, we need some way to track what headers/data/responses are for which request.
+        // We do that through this token. Each request (and associated response) gets one.
+        let token = self.downloads.next;
+        self.downloads.next += 1;
+        debug!(target: "network", "downloading {} as {}", path.display(), token);
+        let is_new = self.downloads.pending_paths.insert(path.to_path_buf());
+        assert!(is_new, "path queued for download more than once");
+
+        // Each write should go to self.downloads.pending[&token].data.
+        // Since the write function must be 'static, we access downloads through a thread-local.
+        // That thread-local is set up in `block_until_ready` when it calls self.multi.perform,
+        // which is what ultimately calls this method.
+        handle.write_function(move |buf| {
+            trace!(target: "network", "{} - {} bytes of data", token, buf.len());
+            tls::with(|downloads| {
+                if let Some(downloads) = downloads {
+                    downloads.pending[&token]
+                        .0
+                        .data
+                        .borrow_mut()
+                        .extend_from_slice(buf);
+                }
+            });
+            Ok(buf.len())
+        })?;
+
+        // And ditto for the header function.
+        handle.header_function(move |buf| {
+            if let Some((tag, value)) = Self::handle_http_header(buf) {
+                tls::with(|downloads| {
+                    if let Some(downloads) = downloads {
+                        let mut header_map = downloads.pending[&token].0.header_map.borrow_mut();
+                        header_map.all.push(format!("{tag}: {value}"));
+                        match tag.to_ascii_lowercase().as_str() {
+                            LAST_MODIFIED => header_map.last_modified = Some(value.to_string()),
+                            ETAG => header_map.etag = Some(value.to_string()),
+                            WWW_AUTHENTICATE => header_map.www_authenticate.push(value.to_string()),
+                            INDEX_SIGNATURE => header_map.index_signature = Some(value.to_string()),
+                            _ => {}
+                        }
+                    }
+                });
+            }
+
+            true
+        })?;
+
+        let dl = Download {
+            token,
+            path: path.to_path_buf(),
+            url: url.to_string(),
+            index_version: index_version.map(str::to_string),
+            data: RefCell::new(Vec::new()),
+            header_map: Default::default(),
+            retry: Retry::new(self.gctx)?,
+        };
+
+        // Finally add the request we've lined up to the pool of requests that cURL manages.
+        let mut handle = self.multi.add(handle)?;
+        handle.set_token(token)?;
+        self.downloads.pending.insert(dl.token, (dl, handle));
+
+        Ok(())
+    }
+
     /// Moves failed [`Download`]s that are ready to retry to the pending queue.
     fn add_sleepers(&mut self) -> CargoResult<()> {
         for (dl, handle) in self.downloads.sleeping.to_retry() {
@@ -493,13 +852,17 @@ impl<'gctx> RegistryData for HttpRegistry<'gctx> {
             return Poll::Pending;
         }
 
+        if path != Path::new(RegistryConfig::NAME) {
+            ready!(self.sync_changes()?);
+        }
+
         if let Some(index_version) = index_version {
             trace!(
                 "local cache of {} is available at version `{}`",
                 path.display(),
                 index_version
             );
-            if self.is_fresh(path) {
+            if self.is_fresh(path) || self.is_unchanged(path, index_version) {
                 return Poll::Ready(Ok(LoadResponse::CacheValid));
             }
         } else if self.fresh.contains(path) {
@@ -533,17 +896,15 @@ impl<'gctx> RegistryData for HttpRegistry<'gctx> {
             // in `handle_completed_downloads`
             match result.response_code {
                 StatusCode::Success => {
-                    let response_index_version = if let Some(etag) = result.header_map.etag {
-                        format!("{}: {}", ETAG, etag)
-                    } else if let Some(lm) = result.header_map.last_modified {
-                        format!("{}: {}", LAST_MODIFIED, lm)
-                    } else {
-                        UNKNOWN.to_string()
-                    };
+                    let response_index_version = result
+                        .header_map
+                        .index_version()
+                        .unwrap_or_else(|| UNKNOWN.to_string());
                     trace!("index file version: {}", response_index_version);
                     return Poll::Ready(Ok(LoadResponse::Data {
                         raw_data: result.data,
                         index_version: Some(response_index_version),
+                        signature: result.header_map.index_signature,
                     }));
                 }
                 StatusCode::NotModified => {
@@ -626,16 +987,7 @@ impl<'gctx> RegistryData for HttpRegistry<'gctx> {
         // Looks like we're going to have to do a network request.
         self.start_fetch()?;
 
-        let mut handle = http_handle(self.gctx)?;
         let full_url = self.full_url(path);
-        debug!(target: "network", "fetch {}", full_url);
-        handle.get(true)?;
-        handle.url(&full_url)?;
-        handle.follow_location(true)?;
-
-        // Enable HTTP/2 if possible.
-        crate::try_old_curl_http2_pipewait!(self.multiplexing, handle);
-
         let mut headers = List::new();
         // Include a header to identify the protocol. This allows the server to
         // know that Cargo is attempting to use the sparse protocol.
@@ -666,68 +1018,7 @@ impl<'gctx> RegistryData for HttpRegistry<'gctx> {
             headers.append(&format!("Authorization: {}", authorization))?;
             trace!(target: "network", "including authorization for {}", full_url);
         }
-        handle.http_headers(headers)?;
-
-        // We're going to have a bunch of downloads all happening "at the same time".
-        // So, we need some way to track what headers/data/responses are for which request.
-        // We do that through this token. Each request (and associated response) gets one.
-        let token = self.downloads.next;
-        self.downloads.next += 1;
-        debug!(target: "network", "downloading {} as {}", path.display(), token);
-        let is_new = self.downloads.pending_paths.insert(path.to_path_buf());
-        assert!(is_new, "path queued for download more than once");
-
-        // Each write should go to self.downloads.pending[&token].data.
-        // Since the write function must be 'static, we access downloads through a thread-local.
-        // That thread-local is set up in `block_until_ready` when it calls self.multi.perform,
-        // which is what ultimately calls this method.
-        handle.write_function(move |buf| {
-            trace!(target: "network", "{} - {} bytes of data", token, buf.len());
-            tls::with(|downloads| {
-                if let Some(downloads) = downloads {
-                    downloads.pending[&token]
-                        .0
-                        .data
-                        .borrow_mut()
-                        .extend_from_slice(buf);
-                }
-            });
-            Ok(buf.len())
-        })?;
-
-        // And ditto for the header function.
-        handle.header_function(move |buf| {
-            if let Some((tag, value)) = Self::handle_http_header(buf) {
-                tls::with(|downloads| {
-                    if let Some(downloads) = downloads {
-                        let mut header_map = downloads.pending[&token].0.header_map.borrow_mut();
-                        header_map.all.push(format!("{tag}: {value}"));
-                        match tag.to_ascii_lowercase().as_str() {
-                            LAST_MODIFIED => header_map.last_modified = Some(value.to_string()),
-                            ETAG => header_map.etag = Some(value.to_string()),
-                            WWW_AUTHENTICATE => header_map.www_authenticate.push(value.to_string()),
-                            _ => {}
-                        }
-                    }
-                });
-            }
-
-            true
-        })?;
-
-        let dl = Download {
-            token,
-            path: path.to_path_buf(),
-            data: RefCell::new(Vec::new()),
-            header_map: Default::default(),
-            retry: Retry::new(self.gctx)?,
-        };
-
-        // Finally add the request we've lined up to the pool of requests that cURL manages.
-        let mut handle = self.multi.add(handle)?;
-        handle.set_token(token)?;
-        self.downloads.pending.insert(dl.token, (dl, handle));
-
+        self.start_download(path, &full_url, headers, index_version)?;
         Poll::Pending
     }
 
@@ -743,6 +1034,12 @@ impl<'gctx> RegistryData for HttpRegistry<'gctx> {
         debug!("invalidated index cache");
         self.fresh.clear();
         self.requested_update = true;
+        // The index files known to be up-to-date are only so as of the last
+        // sync, so sync the index change log again before trusting them. The
+        // state is saved first, and read back on the next sync.
+        self.save_changes();
+        self.changes = None;
+        self.changes_synced = false;
     }
 
     fn set_quiet(&mut self, quiet: bool) {
@@ -807,6 +1104,8 @@ impl<'gctx> RegistryData for HttpRegistry<'gctx> {
             // `self.downloads.sleeping` for retry.
             self.handle_completed_downloads()?;
             if remaining_in_multi + self.downloads.sleeping.len() as u32 == 0 {
+                // Keep what the downloads told about the index change log.
+                self.save_changes();
                 return Ok(());
             }
             // Handles failed transfers in `self.downloads.sleeping` and
@@ -197,6 +197,17 @@ impl<'a> SummariesCache<'a> {
         Ok(ret)
     }
 
+    /// Gets the contents of the index file the cache was made from, as long
+    /// as every line of it was understood and cached.
+    pub fn index_file(&self) -> Vec<u8> {
+        let mut contents = Vec::new();
+        for (_version, data) in self.versions.iter() {
+            contents.extend_from_slice(data);
+            contents.push(b'\n');
+        }
+        contents
+    }
+
     /// Serializes itself with a given `index_version`.
     pub fn serialize(&self, index_version: &str) -> Vec<u8> {
         // NB: keep this method in sync with `parse` above
@@ -261,12 +272,37 @@ impl<'gctx> CacheManager<'gctx> {
         }
     }
 
+    /// Gets the signature of the index file that the cache associated with
+    /// the key was made from, see `-Zindex-signing`.
+    pub fn get_signature(&self, key: &str) -> Option<String> {
+        fs::read_to_string(self.signature_path(key)).ok()
+    }
+
+    /// Associates the signature of the index file the cache was made from
+    /// with the key, or removes it if there is none.
+    ///
+    /// Signatures are kept next to the caches, so that the format of
+    /// [`SummariesCache`] stays the same for unsigned registries.
+    pub fn put_signature(&self, key: &str, signature: Option<&str>) {
+        let signature_path = &self.signature_path(key);
+        let result = match signature {
+            Some(signature) => fs::write(signature_path, signature),
+            None => fs::remove_file(signature_path),
+        };
+        if let Err(e) = result {
+            if e.kind() != io::ErrorKind::NotFound {
+                tracing::info!(?signature_path, "failed to write signature: {e}");
+            }
+        }
+    }
+
     /// Invalidates the cache associated with the key.
     pub fn invalidate(&self, key: &str) {
-        let cache_path = &self.cache_path(key);
-        if let Err(e) = fs::remove_file(cache_path) {
-            if e.kind() != io::ErrorKind::NotFound {
-                tracing::debug!(?cache_path, "failed to remove from cache: {e}");
+        for cache_path in [&self.cache_path(key), &self.signature_path(key)] {
+            if let Err(e) = fs::remove_file(cache_path) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    tracing::debug!(?cache_path, "failed to remove from cache: {e}");
+                }
             }
         }
     }
@@ -278,4 +314,9 @@ impl<'gctx> CacheManager<'gctx> {
         // the way it is.
         self.cache_root.join(relative).into_path_unlocked()
     }
+
+    fn signature_path(&self, key: &str) -> PathBuf {
+        // Package names can't contain a `.`, so this can't clash with a cache.
+        self.cache_path(key).with_extension("sig")
+    }
 }
@@ -24,6 +24,8 @@ use crate::core::dependency::{Artifact, DepKind};
 use crate::core::Dependency;
 use crate::core::{PackageId, SourceId, Summary};
 use crate::sources::registry::{LoadResponse, RegistryData};
+use crate::util::auth;
+use crate::util::credential::paseto::verify_index_signature;
 use crate::util::interning::InternedString;
 use crate::util::IntoUrl;
 use crate::util::{internal, CargoResult, Filesystem, GlobalContext, OptVersionReq};
@@ -37,9 +39,11 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::str;
 use std::task::{ready, Poll};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 use tracing::{debug, info};
 
-mod cache;
+pub(super) mod cache;
 use self::cache::CacheManager;
 use self::cache::SummariesCache;
 
@@ -233,6 +237,11 @@ pub struct IndexPackage<'a> {
     /// Added in 2023 (see <https://github.com/rust-lang/crates.io/pull/6267>),
     /// can be `None` if published before then or if not set in the manifest.
     pub rust_version: Option<RustVersion>,
+    /// The time this version was published, in RFC 3339 format.
+    ///
+    /// Registries aren't required to provide it, and it is `None` for
+    /// registries that don't.
+    pub pubtime: Option<String>,
     /// The schema version for this entry.
     ///
     /// If this is None, it defaults to version `1`. Entries with unknown
@@ -416,12 +425,20 @@ impl<'gctx> RegistryIndex<'gctx> {
         load.prepare()?;
 
         let root = load.assert_index_locked(&self.path);
+        let index_signing = self.gctx.cli_unstable().index_signing && self.source_id.is_sparse();
+        let pinned_signing_key = if index_signing {
+            pinned_signing_key(self.gctx, self.source_id)?
+        } else {
+            None
+        };
         let summaries = ready!(Summaries::parse(
             root,
             &name,
             self.source_id,
             load,
             self.gctx.cli_unstable().bindeps,
+            index_signing,
+            pinned_signing_key.as_deref(),
             &self.cache_manager,
         ))?
         .unwrap_or_default();
@@ -536,12 +553,18 @@ impl Summaries {
     /// * `load` --- the actual index implementation which may be very slow to
     ///   call. We avoid this if we can.
     /// * `bindeps` --- whether the `-Zbindeps` unstable flag is enabled
+    /// * `index_signing` --- whether to verify the signatures of index files,
+    ///   if the registry has a signing key (`-Zindex-signing`)
+    /// * `pinned_signing_key` --- the signing key pinned in the configuration
+    ///   of the registry, see [`signing_key`]
     pub fn parse(
         root: &Path,
         name: &str,
         source_id: SourceId,
         load: &mut dyn RegistryData,
         bindeps: bool,
+        index_signing: bool,
+        pinned_signing_key: Option<&str>,
         cache_manager: &CacheManager<'_>,
     ) -> Poll<CargoResult<Option<Summaries>>> {
         // This is the file we're loading from cache or the index data.
@@ -549,10 +572,47 @@ impl Summaries {
         let name = &name.to_lowercase();
         let relative = make_dep_path(&name, false);
 
+        // The signing key is in `config.json`. Get it before loading the index
+        // file, so that its data isn't lost while waiting for the config.
+        let signing_key = if index_signing {
+            let advertised = ready!(load.config()?).and_then(|config| config.signing_key);
+            signing_key(pinned_signing_key, advertised, source_id)?
+        } else {
+            None
+        };
+
         let mut cached_summaries = None;
         let mut index_version = None;
+        // Why the cache couldn't be used, if it had to be verified and
+        // failed. Reported if the index file can't be loaded otherwise.
+        let mut cache_error = None;
         if let Some(contents) = cache_manager.get(name) {
-            match Summaries::parse_cache(contents) {
+            let verified = match &signing_key {
+                Some(signing_key) => SummariesCache::parse(&contents).and_then(|cache| {
+                    let signature = cache_manager
+                        .get_signature(name)
+                        .ok_or_else(|| anyhow::format_err!("no signature was cached"))?;
+                    verify_index_signature(
+                        signing_key,
+                        &signature,
+                        &relative,
+                        &cache.index_file(),
+                        false,
+                    )
+                }),
+                None => Ok(()),
+            };
+            let parsed = match verified {
+                Ok(()) => Summaries::parse_cache(contents),
+                Err(e) => {
+                    tracing::debug!("failed to verify {name:?} cache: {e}");
+                    cache_error = Some(e.context(format!(
+                        "failed to verify the signature of the cached index file `{relative}` of {source_id}"
+                    )));
+                    Err(anyhow::format_err!("unverified cache"))
+                }
+            };
+            match parsed {
                 Ok((s, v)) => {
                     cached_summaries = Some(s);
                     index_version = Some(v);
@@ -572,16 +632,38 @@ impl Summaries {
             }
             LoadResponse::NotFound => {
                 cache_manager.invalidate(name);
+                if let Some(e) = cache_error {
+                    return Poll::Ready(Err(e));
+                }
                 return Poll::Ready(Ok(None));
             }
             LoadResponse::Data {
                 raw_data,
                 index_version,
+                signature,
             } => {
                 // This is the fallback path where we actually talk to the registry backend to load
                 // information. Here we parse every single line in the index (as we need
                 // to find the versions)
                 tracing::debug!("slow path for {:?}", relative);
+                if let Some(signing_key) = &signing_key {
+                    // Nothing unverified may end up in the index cache.
+                    let verified = match &signature {
+                        Some(signature) => verify_index_signature(
+                            signing_key,
+                            signature,
+                            &relative,
+                            &raw_data,
+                            true,
+                        ),
+                        None => Err(anyhow::format_err!("the registry sent no signature")),
+                    };
+                    if let Err(e) = verified {
+                        return Poll::Ready(Err(e.context(format!(
+                            "failed to verify the signature of the index file `{relative}` of {source_id}"
+                        ))));
+                    }
+                }
                 let mut cache = SummariesCache::default();
                 let mut ret = Summaries::default();
                 ret.raw_data = raw_data;
@@ -621,6 +703,9 @@ impl Summaries {
                     // about to return, write that back out to disk so future Cargo
                     // invocations can use it.
                     cache_manager.put(name, &cache_bytes);
+                    if signing_key.is_some() {
+                        cache_manager.put_signature(name, signature.as_deref());
+                    }
 
                     // If we've got debug assertions enabled read back in the cached values
                     // and assert they match the expected result.
@@ -722,6 +807,7 @@ impl IndexSummary {
             yanked,
             links,
             rust_version,
+            pubtime,
             v,
         } = serde_json::from_slice(line)?;
         let v = v.unwrap_or(1);
@@ -738,6 +824,11 @@ impl IndexSummary {
         }
         let mut summary = Summary::new(pkgid, deps, &features, links, rust_version)?;
         summary.set_checksum(cksum);
+        // An unreadable time is treated like a missing one rather than an
+        // error, see the caution above.
+        if let Some(pubtime) = pubtime.and_then(|t| OffsetDateTime::parse(&t, &Rfc3339).ok()) {
+            summary.set_pubtime(pubtime);
+        }
 
         let v_max = if bindeps {
             INDEX_V_MAX + 1
@@ -827,6 +918,37 @@ impl<'a> RegistryDependency<'a> {
     }
 }
 
+/// Gets the `signing-key` pinned in the configuration of the registry
+/// `source_id`, like in its `[registries.<name>]` table.
+pub(super) fn pinned_signing_key(
+    gctx: &GlobalContext,
+    source_id: SourceId,
+) -> CargoResult<Option<String>> {
+    Ok(auth::registry_credential_config_raw(gctx, &source_id)?
+        .and_then(|config| config.signing_key))
+}
+
+/// Gets the key the index files of the registry `source_id` must be signed
+/// with, for `-Zindex-signing`.
+///
+/// A `pinned` key is always required, even if the registry stops
+/// advertising one in its `config.json`. Otherwise the `advertised` key is
+/// used, if any.
+pub(super) fn signing_key(
+    pinned: Option<&str>,
+    advertised: Option<String>,
+    source_id: SourceId,
+) -> CargoResult<Option<String>> {
+    match (pinned, advertised) {
+        (Some(pinned), Some(advertised)) if pinned != advertised => anyhow::bail!(
+            "{source_id} advertises the signing key `{advertised}`, \
+             but the signing key `{pinned}` is pinned in the configuration"
+        ),
+        (Some(pinned), _) => Ok(Some(pinned.to_string())),
+        (None, advertised) => Ok(advertised),
+    }
+}
+
 /// Like [`slice::split`] but is optimized by [`memchr`].
 fn split(haystack: &[u8], needle: u8) -> impl Iterator<Item = &[u8]> {
     struct Split<'a> {
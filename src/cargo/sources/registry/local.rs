@@ -120,6 +120,7 @@ impl<'gctx> RegistryData for LocalRegistry<'gctx> {
             Poll::Ready(Ok(LoadResponse::Data {
                 raw_data,
                 index_version: None,
+                signature: None,
             }))
         } else {
             Poll::Pending
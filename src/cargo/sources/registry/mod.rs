@@ -203,9 +203,9 @@ use tracing::debug;
 use crate::core::dependency::Dependency;
 use crate::core::global_cache_tracker;
 use crate::core::{Package, PackageId, SourceId};
-use crate::sources::source::MaybePackage;
 use crate::sources::source::QueryKind;
 use crate::sources::source::Source;
+use crate::sources::source::{DownloadFallback, MaybePackage};
 use crate::sources::PathSource;
 use crate::util::cache_lock::CacheLockMode;
 use crate::util::interning::InternedString;
@@ -268,6 +268,12 @@ pub struct RegistrySource<'gctx> {
     /// warning twice, with the assumption of (`dep.package_name()` + `--precise`
     /// version) being sufficient to uniquely identify the same query result.
     selected_precise_yanked: HashSet<(InternedString, semver::Version)>,
+    /// Remote registries to try downloading `.crate` files from, in order,
+    /// before this registry.
+    ///
+    /// Only their download locations are used. Summaries and checksums always
+    /// come from the index of this registry.
+    mirrors: Vec<(SourceId, Box<dyn RegistryData + 'gctx>)>,
 }
 
 /// The [`config.json`] file stored in the index.
@@ -278,7 +284,9 @@ pub struct RegistrySource<'gctx> {
 /// {
 ///     "dl": "https://example.com/api/{crate}/{version}/download",
 ///     "api": "https://example.com/api",
-///     "auth-required": false             # unstable feature (RFC 3139)
+///     "auth-required": false,            # unstable feature (RFC 3139)
+///     "changes": "https://example.com/changes", # unstable feature
+///     "signing-key": "k3.public.AmDwjlyf8jAV3gm5Z7Kz9xAOcsKslt_Vwp5v-emjFzBHLCtcANzTaVEghTNEMj9PkQ" # unstable feature
 /// }
 /// ```
 ///
@@ -317,6 +325,22 @@ pub struct RegistryConfig {
     /// [RFC 3139]: https://rust-lang.github.io/rfcs/3139-cargo-alternative-registry-auth.html
     #[serde(default)]
     pub auth_required: bool,
+
+    /// URL of the index change log of a sparse registry, relative to the
+    /// index URL. It tells which index files changed since a previous sync,
+    /// so only those need to be revalidated.
+    ///
+    /// Only used with `-Zsparse-index-changes`.
+    pub changes: Option<String>,
+
+    /// PASERK public key (`k3.public.…`) that the index files of a sparse
+    /// registry are signed with. See [`verify_index_signature`] for the
+    /// format of the signatures.
+    ///
+    /// [`verify_index_signature`]: crate::util::credential::paseto::verify_index_signature
+    ///
+    /// Only used with `-Zindex-signing`.
+    pub signing_key: Option<String>,
 }
 
 /// Result from loading data from a registry.
@@ -329,6 +353,8 @@ pub enum LoadResponse {
         raw_data: Vec<u8>,
         /// Version of this data to determine whether it is out of date.
         index_version: Option<String>,
+        /// Signature of this data, if the registry sent one.
+        signature: Option<String>,
     },
 
     /// The requested crate was found.
@@ -449,6 +475,7 @@ pub(crate) mod index;
 pub use index::IndexSummary;
 mod local;
 mod remote;
+pub mod snapshot;
 
 /// Generates a unique name for [`SourceId`] to have a unique path to put their
 /// index files.
@@ -467,6 +494,32 @@ fn short_name(id: SourceId, is_shallow: bool) -> String {
     name
 }
 
+/// Generates the name of the directories where a remote registry puts its
+/// index files and `.crate` files.
+fn remote_name(id: SourceId, gctx: &GlobalContext) -> String {
+    short_name(
+        id,
+        gctx.cli_unstable()
+            .git
+            .map_or(false, |features| features.shallow_index)
+            && !id.is_sparse(),
+    )
+}
+
+/// Creates the [`RegistryData`] of a remote registry, either an HTTP-based
+/// [`http_remote::HttpRegistry`] or a Git-based [`remote::RemoteRegistry`].
+fn remote_ops<'gctx>(
+    source_id: SourceId,
+    gctx: &'gctx GlobalContext,
+    name: &str,
+) -> CargoResult<Box<dyn RegistryData + 'gctx>> {
+    Ok(if source_id.is_sparse() {
+        Box::new(http_remote::HttpRegistry::new(source_id, gctx, name)?)
+    } else {
+        Box::new(remote::RemoteRegistry::new(source_id, gctx, name))
+    })
+}
+
 impl<'gctx> RegistrySource<'gctx> {
     /// Creates a [`Source`] of a "remote" registry.
     /// It could be either an HTTP-based [`http_remote::HttpRegistry`] or
@@ -479,18 +532,8 @@ impl<'gctx> RegistrySource<'gctx> {
         gctx: &'gctx GlobalContext,
     ) -> CargoResult<RegistrySource<'gctx>> {
         assert!(source_id.is_remote_registry());
-        let name = short_name(
-            source_id,
-            gctx.cli_unstable()
-                .git
-                .map_or(false, |features| features.shallow_index)
-                && !source_id.is_sparse(),
-        );
-        let ops = if source_id.is_sparse() {
-            Box::new(http_remote::HttpRegistry::new(source_id, gctx, &name)?) as Box<_>
-        } else {
-            Box::new(remote::RemoteRegistry::new(source_id, gctx, &name)) as Box<_>
-        };
+        let name = remote_name(source_id, gctx);
+        let ops = remote_ops(source_id, gctx, &name)?;
 
         Ok(RegistrySource::new(
             source_id,
@@ -501,6 +544,23 @@ impl<'gctx> RegistrySource<'gctx> {
         ))
     }
 
+    /// Downloads `.crate` files from the remote registries of `mirrors`,
+    /// trying them in order before falling back to this registry.
+    ///
+    /// The downloaded files are still verified against the checksums in the
+    /// index of this registry.
+    pub fn set_mirrors(&mut self, mirrors: &[SourceId]) -> CargoResult<()> {
+        self.mirrors = mirrors
+            .iter()
+            .map(|&mirror| {
+                assert!(mirror.is_remote_registry());
+                let name = remote_name(mirror, self.gctx);
+                Ok((mirror, remote_ops(mirror, self.gctx, &name)?))
+            })
+            .collect::<CargoResult<_>>()?;
+        Ok(())
+    }
+
     /// Creates a [`Source`] of a local registry, with [`local::LocalRegistry`] under the hood.
     ///
     /// * `path` --- The root path of a local registry on the file system.
@@ -538,6 +598,7 @@ impl<'gctx> RegistrySource<'gctx> {
             yanked_whitelist: yanked_whitelist.clone(),
             ops,
             selected_precise_yanked: HashSet::new(),
+            mirrors: Vec::new(),
         }
     }
 
@@ -797,7 +858,7 @@ impl<'gctx> Source for RegistrySource<'gctx> {
                 .index
                 .query_inner(dep.package_name(), &req, &mut *self.ops, &mut |s| {
                     let matched = match kind {
-                        QueryKind::Exact => {
+                        QueryKind::Exact | QueryKind::RejectedVersions => {
                             if req.is_precise() && self.gctx.cli_unstable().unstable_options {
                                 dep.matches_prerelease(s.as_summary())
                             } else {
@@ -810,6 +871,13 @@ impl<'gctx> Source for RegistrySource<'gctx> {
                     if !matched {
                         return;
                     }
+                    if kind == QueryKind::RejectedVersions {
+                        // Only report what the branch below would filter out.
+                        if s.is_yanked() && !self.yanked_whitelist.contains(&s.package_id()) {
+                            callback(s);
+                        }
+                        return;
+                    }
                     // Next filter out all yanked packages. Some yanked packages may
                     // leak through if they're in a whitelist (aka if they were
                     // previously in `Cargo.lock`
@@ -896,18 +964,61 @@ impl<'gctx> Source for RegistrySource<'gctx> {
                 Poll::Ready(hash) => break hash,
             }
         };
-        match self.ops.download(package, hash)? {
-            MaybeLock::Ready(file) => self.get_pkg(package, &file).map(MaybePackage::Ready),
+        let (url, descriptor, authorization) = match self.ops.download(package, hash)? {
+            MaybeLock::Ready(file) => {
+                return self.get_pkg(package, &file).map(MaybePackage::Ready);
+            }
             MaybeLock::Download {
                 url,
                 descriptor,
                 authorization,
-            } => Ok(MaybePackage::Download {
-                url,
-                descriptor,
-                authorization,
-            }),
+            } => (url, descriptor, authorization),
+        };
+        let mut locations = Vec::new();
+        if !self.gctx.offline() {
+            for (mirror, ops) in &mut self.mirrors {
+                match ops.download(package.with_source_id(*mirror), hash) {
+                    Ok(MaybeLock::Ready(mut file)) => {
+                        // Previously downloaded from the mirror used as a
+                        // registry of its own, so still needs verification.
+                        let mut data = Vec::new();
+                        file.read_to_end(&mut data)?;
+                        let file = self.ops.finish_download(package, hash, &data)?;
+                        return self.get_pkg(package, &file).map(MaybePackage::Ready);
+                    }
+                    Ok(MaybeLock::Download {
+                        url,
+                        descriptor,
+                        authorization,
+                    }) => locations.push(DownloadFallback {
+                        url,
+                        descriptor,
+                        authorization,
+                    }),
+                    Err(e) => {
+                        let msg = format!(
+                            "skipping mirror `{}` of `{}`: {e:#}",
+                            mirror.display_registry_name(),
+                            self.source_id.display_registry_name()
+                        );
+                        self.gctx.shell().warn(msg)?;
+                    }
+                }
+            }
         }
+        locations.push(DownloadFallback {
+            url,
+            descriptor,
+            authorization,
+        });
+        let mut locations = locations.into_iter();
+        let first = locations.next().unwrap();
+        Ok(MaybePackage::Download {
+            url: first.url,
+            descriptor: first.descriptor,
+            authorization: first.authorization,
+            fallbacks: locations.collect(),
+        })
     }
 
     fn finish_download(&mut self, package: PackageId, data: Vec<u8>) -> CargoResult<Package> {
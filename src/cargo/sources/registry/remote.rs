@@ -295,6 +295,7 @@ impl<'gctx> RegistryData for RemoteRegistry<'gctx> {
             Ok(LoadResponse::Data {
                 raw_data: blob.content().to_vec(),
                 index_version: git_file_hash,
+                signature: None,
             })
         }
 
@@ -0,0 +1,251 @@
+//! Snapshots of the cached data of remote registries, for moving what a
+//! lockfile needs to a machine without network access.
+//!
+//! A snapshot has a directory for each exported registry, named like the
+//! directories the registry caches its data in. Each of them is laid out as a
+//! [local registry](super::local::LocalRegistry):
+//!
+//! ```text
+//! <snapshot>/index.crates.io-6f17d22bba15001f/
+//! ├── index/
+//! │   ├── config.json
+//! │   ├── se/rd/serde
+//! │   └── se/rd/serde.sig
+//! └── serde-1.0.210.crate
+//! ```
+//!
+//! Index files are written whole, along with their signature if the registry
+//! signs its index (see `-Zindex-signing`), so that they can be verified
+//! like when they are downloaded. Importing a snapshot verifies the index
+//! files and the `.crate` files against the lockfile, then adds them to the
+//! [index cache](super::index::cache) and to the crate cache of
+//! `CARGO_HOME`, so the registry can be used as usual while offline.
+//!
+//! Only sparse registries are supported, as the index cache of git
+//! registries is tied to the commit of their index.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::Context as _;
+use cargo_util::paths;
+use cargo_util::registry::make_dep_path;
+use cargo_util::Sha256;
+
+use crate::core::{PackageId, SourceId};
+use crate::sources::registry::index::cache::{CacheManager, SummariesCache};
+use crate::sources::registry::index::{pinned_signing_key, signing_key, IndexPackage};
+use crate::sources::registry::{remote_name, RegistryConfig};
+use crate::util::cache_lock::CacheLockMode;
+use crate::util::credential::paseto::verify_index_signature;
+use crate::util::{CargoResult, GlobalContext};
+
+/// The index version of index cache entries coming from a snapshot.
+///
+/// It doesn't look like an `ETag` or `Last-Modified` header, so the entries
+/// are downloaded again the next time the index is updated.
+const IMPORTED_INDEX_VERSION: &str = "Imported";
+
+/// Writes the index entries and `.crate` files of `pkgs` from the caches of
+/// the remote registry `source_id` into the snapshot at `dst`.
+///
+/// All of `pkgs` must have been downloaded already.
+pub fn export(
+    gctx: &GlobalContext,
+    source_id: SourceId,
+    pkgs: &[PackageId],
+    dst: &Path,
+) -> CargoResult<()> {
+    assert!(source_id.is_remote_registry());
+    if !source_id.is_sparse() {
+        anyhow::bail!(
+            "cannot export the index of {source_id}, \
+             only sparse registries can be exported"
+        );
+    }
+    let name = remote_name(source_id, gctx);
+    let index_path = gctx.registry_index_path().join(&name);
+    let cache_path = gctx.registry_cache_path().join(&name);
+    let cache_manager = CacheManager::new(index_path.join(".cache"), gctx);
+    let dst = dst.join(&name);
+    let index_dst = dst.join("index");
+    paths::create_dir_all(&dst)?;
+
+    let mut exported = BTreeMap::new();
+    for pkg in pkgs {
+        let key = pkg.name().to_lowercase();
+        if !exported.contains_key(&key) {
+            let contents = cache_manager
+                .get(&key)
+                .ok_or_else(|| anyhow::format_err!("no cached index entry found for `{pkg}`"))?;
+            let cache = SummariesCache::parse(&contents)
+                .with_context(|| format!("failed to read the cached index entry of `{pkg}`"))?;
+            let path = index_dst.join(make_dep_path(&key, false));
+            paths::create_dir_all(path.parent().unwrap())?;
+            paths::write(&path, cache.index_file())?;
+            if let Some(signature) = cache_manager.get_signature(&key) {
+                paths::write(path.with_extension("sig"), signature)?;
+            }
+            let versions: Vec<_> = cache.versions.into_iter().map(|(v, _)| v).collect();
+            exported.insert(key.clone(), versions);
+        }
+        if !exported[&key].contains(pkg.version()) {
+            anyhow::bail!("no cached index entry found for `{pkg}`");
+        }
+
+        let crate_file = cache_path.join(pkg.tarball_name()).into_path_unlocked();
+        paths::copy(&crate_file, dst.join(pkg.tarball_name()))
+            .with_context(|| format!("failed to export the `.crate` file of `{pkg}`"))?;
+    }
+
+    let config = index_path.join(RegistryConfig::NAME).into_path_unlocked();
+    if config.exists() {
+        paths::copy(&config, index_dst.join(RegistryConfig::NAME))?;
+    }
+    Ok(())
+}
+
+/// Seeds the caches of the remote registry `source_id` with the index files
+/// and `.crate` files of `pkgs` found in the snapshot at `src`.
+///
+/// The index entries of `pkgs` must match their `checksums` in the lockfile,
+/// and with `-Zindex-signing`, the index files must be signed by the
+/// registry. Packages missing from the snapshot are skipped. Returns how many
+/// packages were found.
+///
+/// The caller must hold the package cache lock in
+/// [`CacheLockMode::DownloadExclusive`] mode.
+pub fn import(
+    gctx: &GlobalContext,
+    source_id: SourceId,
+    pkgs: &[PackageId],
+    checksums: &HashMap<PackageId, Option<String>>,
+    src: &Path,
+) -> CargoResult<usize> {
+    assert!(source_id.is_remote_registry());
+    let name = remote_name(source_id, gctx);
+    let src = src.join(&name);
+    if !src.exists() {
+        return Ok(0);
+    }
+    if !source_id.is_sparse() {
+        anyhow::bail!(
+            "cannot import the index of {source_id}, \
+             only sparse registries can be imported"
+        );
+    }
+    let index_path = gctx.registry_index_path().join(&name);
+    let cache_path = gctx.registry_cache_path().join(&name);
+    let cache_manager = CacheManager::new(index_path.join(".cache"), gctx);
+
+    let config = src.join("index").join(RegistryConfig::NAME);
+    let signing_key = if gctx.cli_unstable().index_signing {
+        let advertised = if config.exists() {
+            let config: RegistryConfig = serde_json::from_slice(&paths::read_bytes(&config)?)
+                .with_context(|| format!("failed to parse `{}`", config.display()))?;
+            config.signing_key
+        } else {
+            None
+        };
+        let pinned = pinned_signing_key(gctx, source_id)?;
+        signing_key(pinned.as_deref(), advertised, source_id)?
+    } else {
+        None
+    };
+
+    let mut by_name: BTreeMap<String, Vec<PackageId>> = BTreeMap::new();
+    for pkg in pkgs {
+        by_name
+            .entry(pkg.name().to_lowercase())
+            .or_default()
+            .push(*pkg);
+    }
+
+    let cache_path =
+        gctx.assert_package_cache_locked(CacheLockMode::DownloadExclusive, &cache_path);
+    let mut found = 0;
+    for (key, pkgs) in by_name {
+        let relative = make_dep_path(&key, false);
+        let index_file = src.join("index").join(&relative);
+        if !index_file.exists() {
+            continue;
+        }
+        let data = paths::read_bytes(&index_file)?;
+        let signature_file = index_file.with_extension("sig");
+        let signature = if signature_file.exists() {
+            Some(paths::read(&signature_file)?)
+        } else {
+            None
+        };
+        if let Some(signing_key) = &signing_key {
+            let verified = signature
+                .as_deref()
+                .ok_or_else(|| anyhow::format_err!("the snapshot has no signature"))
+                .and_then(|signature| {
+                    verify_index_signature(signing_key, signature, &relative, &data, false)
+                });
+            if let Err(e) = verified {
+                return Err(e.context(format!(
+                    "failed to verify the signature of `{}`",
+                    index_file.display()
+                )));
+            }
+        }
+
+        let existing = cache_manager.get(&key);
+        let existing = existing
+            .as_deref()
+            .and_then(|contents| SummariesCache::parse(contents).ok());
+        let mut cache = SummariesCache::default();
+        let mut missing = false;
+        for line in data.split(|b| *b == b'\n').filter(|line| !line.is_empty()) {
+            let entry: IndexPackage<'_> = serde_json::from_slice(line)
+                .with_context(|| format!("failed to parse `{}`", index_file.display()))?;
+            cache.versions.push((entry.vers.clone(), line));
+            let Some(pkg) = pkgs.iter().find(|pkg| *pkg.version() == entry.vers) else {
+                continue;
+            };
+            if let Some(Some(locked)) = checksums.get(pkg) {
+                if *locked != entry.cksum {
+                    anyhow::bail!(
+                        "checksum for `{pkg}` in `{}` does not match the lockfile",
+                        index_file.display()
+                    );
+                }
+            }
+            let crate_file = src.join(pkg.tarball_name());
+            if !crate_file.exists() {
+                continue;
+            }
+            let actual = Sha256::new().update_path(&crate_file)?.finish_hex();
+            if actual != entry.cksum {
+                anyhow::bail!(
+                    "failed to verify the checksum of `{pkg}` in `{}`",
+                    src.display()
+                );
+            }
+            let dst = cache_path.join(pkg.tarball_name());
+            if !dst.exists() {
+                paths::create_dir_all(cache_path)?;
+                paths::copy(&crate_file, &dst)?;
+            }
+            missing |= !existing
+                .as_ref()
+                .is_some_and(|existing| existing.versions.iter().any(|(v, _)| *v == entry.vers));
+            found += 1;
+        }
+        // A cache that already has every locked version may be more recent
+        // than the snapshot, so it is kept.
+        if missing {
+            cache_manager.put(&key, &cache.serialize(IMPORTED_INDEX_VERSION));
+            cache_manager.put_signature(&key, signature.as_deref());
+        }
+    }
+
+    let config_dst = index_path.join(RegistryConfig::NAME).into_path_unlocked();
+    if config.exists() && !config_dst.exists() {
+        paths::create_dir_all(config_dst.parent().unwrap())?;
+        paths::copy(&config, &config_dst)?;
+    }
+    Ok(found)
+}
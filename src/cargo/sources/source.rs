@@ -189,6 +189,11 @@ pub enum QueryKind {
     /// Match a dependency in all ways and will normalize the package name.
     /// Each source defines what normalizing means.
     Normalized,
+    /// A query for packages matching the given dependency requirement that
+    /// the source refuses to offer as candidates, like yanked versions.
+    ///
+    /// This is only used to explain resolution failures.
+    RejectedVersions,
 }
 
 /// A download status that represents if a [`Package`] has already been
@@ -204,9 +209,23 @@ pub enum MaybePackage {
         descriptor: String,
         /// Authorization data that may be required to attach when downloading.
         authorization: Option<String>,
+        /// Other locations to download the same content from, tried in order
+        /// if downloading from `url` fails.
+        fallbacks: Vec<DownloadFallback>,
     },
 }
 
+/// A location to download a [`Package`] from if downloading it from the
+/// locations before it in [`MaybePackage::Download`] failed.
+pub struct DownloadFallback {
+    /// URL to download the content.
+    pub url: String,
+    /// Text to display to the user of what is being downloaded.
+    pub descriptor: String,
+    /// Authorization data that may be required to attach when downloading.
+    pub authorization: Option<String>,
+}
+
 /// A blanket implementation forwards all methods to [`Source`].
 impl<'a, T: Source + ?Sized + 'a> Source for Box<T> {
     fn source_id(&self) -> SourceId {
@@ -38,6 +38,8 @@ pub struct RegistryConfig {
     pub credential_provider: Option<PathAndArgs>,
     pub secret_key: OptValue<Secret<String>>,
     pub secret_key_subject: Option<String>,
+    /// Public key the index files must be signed with, see `-Zindex-signing`.
+    pub signing_key: Option<String>,
     #[serde(rename = "protocol")]
     _protocol: Option<String>,
 }
@@ -54,6 +56,7 @@ pub struct RegistryConfigExtended {
     pub credential_provider: Option<PathAndArgs>,
     pub secret_key: OptValue<Secret<String>>,
     pub secret_key_subject: Option<String>,
+    pub signing_key: Option<String>,
     #[serde(rename = "default")]
     _default: Option<String>,
     #[serde(rename = "global-credential-providers")]
@@ -68,6 +71,7 @@ impl RegistryConfigExtended {
             credential_provider: self.credential_provider,
             secret_key: self.secret_key,
             secret_key_subject: self.secret_key_subject,
+            signing_key: self.signing_key,
             _protocol: None,
         }
     }
@@ -3,7 +3,7 @@ use crate::core::compiler::{
 };
 use crate::core::resolver::{CliFeatures, ForceAllTargets, HasDevUnits};
 use crate::core::{shell, Edition, Package, Target, TargetKind, Workspace};
-use crate::ops::lockfile::LOCKFILE_NAME;
+use crate::ops::lockfile::{is_lockfile_name, LOCKFILE_NAME};
 use crate::ops::registry::RegistryOrIndex;
 use crate::ops::{self, CompileFilter, CompileOptions, NewOptions, Packages, VersionControl};
 use crate::util::important_paths::find_root_manifest_for_wd;
@@ -16,6 +16,7 @@ use crate::util::{
 };
 use crate::CargoResult;
 use anyhow::bail;
+use anyhow::Context as _;
 use cargo_util::paths;
 use cargo_util_schemas::manifest::ProfileName;
 use cargo_util_schemas::manifest::RegistryName;
@@ -34,7 +35,7 @@ pub use clap::{value_parser, Arg, ArgAction, ArgMatches};
 
 pub use clap::Command;
 
-use super::context::JobsConfig;
+use super::context::{CargoResolverConfig, JobsConfig};
 use super::IntoUrl;
 
 pub mod heading {
@@ -331,7 +332,7 @@ pub trait CommandExt: Sized {
 
     fn arg_lockfile_path(self) -> Self {
         self._arg(
-            opt("lockfile-path", "Path to Cargo.lock (unstable)")
+            opt("lockfile-path", "Path to Cargo.lock")
                 .value_name("PATH")
                 .help_heading(heading::MANIFEST_OPTIONS),
         )
@@ -571,16 +572,31 @@ pub trait ArgMatchesExt {
         lockfile_path(self._value_of("lockfile-path").map(Path::new), gctx)
     }
 
+    /// The lockfile path for the workspace, from `--lockfile-path` or else
+    /// the `resolver.lockfile-path` config.
+    fn workspace_lockfile_path(&self, gctx: &GlobalContext) -> CargoResult<Option<PathBuf>> {
+        match self.lockfile_path(gctx)? {
+            Some(path) => Ok(Some(path)),
+            None => config_lockfile_path(gctx),
+        }
+    }
+
     #[tracing::instrument(skip_all)]
     fn workspace<'a>(&self, gctx: &'a GlobalContext) -> CargoResult<Workspace<'a>> {
         let root = self.root_manifest(gctx)?;
-        let lockfile_path = self.lockfile_path(gctx)?;
+        let lockfile_path = self.workspace_lockfile_path(gctx)?;
         let mut ws = Workspace::new(&root, gctx)?;
         ws.set_resolve_honors_rust_version(self.honor_rust_version());
         if gctx.cli_unstable().avoid_dev_deps {
             ws.set_require_optional_deps(false);
         }
         ws.set_requested_lockfile_path(lockfile_path);
+        ws.set_resolve_errors_as_json(
+            self._values_of("message-format")
+                .iter()
+                .flat_map(|fmt| fmt.split(','))
+                .any(|fmt| fmt.to_ascii_lowercase().starts_with("json")),
+        );
         Ok(ws)
     }
 
@@ -759,6 +775,10 @@ Run `{cmd}` to see possible targets."
             &self.targets()?,
             mode,
         )?;
+        if let Some(ws) = workspace {
+            build_config.requested_kinds =
+                ws.resolve_target_specs(&build_config.requested_kinds)?;
+        }
         build_config.message_format = message_format.unwrap_or(MessageFormat::Human);
         build_config.requested_profile = self.get_profile_name("dev", profile_checking)?;
         build_config.build_plan = self.flag("build-plan");
@@ -1050,22 +1070,42 @@ pub fn lockfile_path(
         return Ok(None);
     };
 
-    gctx.cli_unstable()
-        .fail_if_stable_opt("--lockfile-path", 14421)?;
-
     let path = gctx.cwd().join(lockfile_path);
+    validate_lockfile_path(&path, lockfile_path)?;
+
+    return Ok(Some(path));
+}
 
-    if !path.ends_with(LOCKFILE_NAME) {
-        bail!("the lockfile-path must be a path to a {LOCKFILE_NAME} file (please rename your lock file to {LOCKFILE_NAME})")
+/// Reads the `resolver.lockfile-path` config, resolved relative to where it
+/// was defined.
+pub fn config_lockfile_path(gctx: &GlobalContext) -> CargoResult<Option<PathBuf>> {
+    let config = gctx.get::<CargoResolverConfig>("resolver")?;
+    let Some(lockfile_path) = config.lockfile_path else {
+        return Ok(None);
+    };
+
+    let path = lockfile_path.resolve_path(gctx);
+    validate_lockfile_path(&path, &path)
+        .context("failed to load config value `resolver.lockfile-path`")?;
+
+    Ok(Some(path))
+}
+
+fn validate_lockfile_path(path: &Path, display: &Path) -> CargoResult<()> {
+    let is_lockfile = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(is_lockfile_name);
+    if !is_lockfile {
+        bail!("the lockfile-path must be a path to a {LOCKFILE_NAME} or Cargo.<name>.lock file")
     }
     if path.is_dir() {
         bail!(
             "lockfile path `{}` is a directory but expected a file",
-            lockfile_path.display()
+            display.display()
         )
     }
-
-    return Ok(Some(path));
+    Ok(())
 }
 
 pub fn get_registry_candidates() -> CargoResult<Vec<clap_complete::CompletionCandidate>> {
@@ -106,7 +106,7 @@ mod path;
 pub use path::{ConfigRelativePath, PathAndArgs};
 
 mod target;
-pub use target::{TargetCfgConfig, TargetConfig};
+pub use target::{DiscoveredToolchain, TargetCfgConfig, TargetConfig};
 
 mod environment;
 use environment::Env;
@@ -225,6 +225,8 @@ pub struct GlobalContext {
     net_config: LazyCell<CargoNetConfig>,
     build_config: LazyCell<CargoBuildConfig>,
     target_cfgs: LazyCell<Vec<(String, TargetCfgConfig)>>,
+    /// Cache of toolchains found on `PATH`, by target triple.
+    discovered_toolchains: LazyCell<RefCell<HashMap<String, DiscoveredToolchain>>>,
     doc_extern_map: LazyCell<RustdocExternMap>,
     progress_config: ProgressConfig,
     env_config: LazyCell<Arc<HashMap<String, OsString>>>,
@@ -319,6 +321,7 @@ impl GlobalContext {
             net_config: LazyCell::new(),
             build_config: LazyCell::new(),
             target_cfgs: LazyCell::new(),
+            discovered_toolchains: LazyCell::new(),
             doc_extern_map: LazyCell::new(),
             progress_config: ProgressConfig::default(),
             env_config: LazyCell::new(),
@@ -1919,6 +1922,17 @@ impl GlobalContext {
         target::load_target_triple(self, target)
     }
 
+    /// Returns the linker and runner found on `PATH` for the given target
+    /// triple, see [`DiscoveredToolchain`].
+    pub fn discovered_toolchain(&self, target: &str) -> DiscoveredToolchain {
+        self.discovered_toolchains
+            .borrow_with(|| RefCell::new(HashMap::new()))
+            .borrow_mut()
+            .entry(target.to_string())
+            .or_insert_with(|| target::discover_toolchain(self, target))
+            .clone()
+    }
+
     /// Returns the cached [`SourceId`] corresponding to the main repository.
     ///
     /// This is the main cargo registry by default, but it can be overridden in
@@ -2699,6 +2713,10 @@ impl BuildTargetConfig {
 #[serde(rename_all = "kebab-case")]
 pub struct CargoResolverConfig {
     pub incompatible_rust_versions: Option<IncompatibleRustVersions>,
+    pub min_release_age: Option<String>,
+    pub lockfile_path: Option<ConfigRelativePath>,
+    pub feature_unification: Option<FeatureUnification>,
+    pub minimal_versions: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -2708,6 +2726,20 @@ pub enum IncompatibleRustVersions {
     Fallback,
 }
 
+/// Which packages the features of dependencies are unified across, see
+/// `resolver.feature-unification`.
+#[derive(Copy, Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeatureUnification {
+    /// Unify features across all packages selected for the build.
+    #[default]
+    Selected,
+    /// Unify features across all workspace members, whether selected or not.
+    Workspace,
+    /// Resolve features separately for each package selected for the build.
+    Package,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct TermConfig {
@@ -1,9 +1,11 @@
 use super::{ConfigKey, ConfigRelativePath, GlobalContext, OptValue, PathAndArgs, StringList, CV};
 use crate::core::compiler::{BuildOutput, LinkArgTarget};
 use crate::util::CargoResult;
+use cargo_platform::toolchain;
 use serde::Deserialize;
 use std::collections::{BTreeMap, HashMap};
-use std::path::PathBuf;
+use std::env;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 /// Config definition of a `[target.'cfg(…)']` table.
@@ -40,6 +42,19 @@ pub struct TargetConfig {
     pub links_overrides: Rc<BTreeMap<String, BuildOutput>>,
 }
 
+/// A linker and runner for a target that were found on `PATH` instead of being
+/// configured, see `-Zcross-toolchains`.
+///
+/// These are only used when neither `[target.<triple>]` nor a matching
+/// `[target.'cfg(..)']` table sets the corresponding value.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveredToolchain {
+    /// The linker, such as `/usr/bin/aarch64-linux-gnu-gcc`.
+    pub linker: Option<PathBuf>,
+    /// The runner and its arguments, such as `/usr/bin/qemu-aarch64`.
+    pub runner: Option<(PathBuf, Vec<String>)>,
+}
+
 /// Loads all of the `target.'cfg()'` tables.
 pub(super) fn load_target_cfgs(
     gctx: &GlobalContext,
@@ -107,6 +122,68 @@ pub(super) fn load_target_triple(gctx: &GlobalContext, triple: &str) -> CargoRes
     load_config_table(gctx, &format!("target.{}", triple))
 }
 
+/// Looks on `PATH` for the conventionally named cross toolchain of `triple`.
+///
+/// Returns nothing unless `-Zcross-toolchains` is enabled.
+pub(super) fn discover_toolchain(gctx: &GlobalContext, triple: &str) -> DiscoveredToolchain {
+    if !gctx.cli_unstable().cross_toolchains {
+        return DiscoveredToolchain::default();
+    }
+    let find = |names: Vec<String>| {
+        names
+            .iter()
+            .find_map(|name| find_on_path(gctx, Path::new(name)))
+    };
+    let linker = find(toolchain::linkers(triple));
+    let runner = find(toolchain::runners(triple)).map(|runner| {
+        let mut args = Vec::new();
+        // Dynamically linked binaries need qemu to be pointed at the target's
+        // loader and libraries, which distributions install alongside the
+        // cross toolchain.
+        let is_qemu = runner
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| name.starts_with("qemu-"));
+        if let (true, Some(prefix)) = (is_qemu, toolchain::gnu_prefix(triple)) {
+            let sysroot = Path::new("/usr").join(prefix);
+            if sysroot.is_dir() {
+                args.push("-L".to_string());
+                args.push(sysroot.display().to_string());
+            }
+        }
+        if toolchain::is_wasi(triple) {
+            args.push("run".to_string());
+        }
+        (runner, args)
+    });
+    DiscoveredToolchain { linker, runner }
+}
+
+fn find_on_path(gctx: &GlobalContext, program: &Path) -> Option<PathBuf> {
+    let paths = gctx.get_env_os("PATH")?;
+    env::split_paths(paths)
+        .map(|dir| dir.join(program))
+        .flat_map(|candidate| {
+            let with_exe = (!env::consts::EXE_EXTENSION.is_empty())
+                .then(|| candidate.with_extension(env::consts::EXE_EXTENSION));
+            std::iter::once(candidate).chain(with_exe)
+        })
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::prelude::*;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 /// Loads a single table for the given prefix.
 fn load_config_table(gctx: &GlobalContext, prefix: &str) -> CargoResult<TargetConfig> {
     // This needs to get each field individually because it cannot fetch the
@@ -4,10 +4,12 @@ use anyhow::Context as _;
 use cargo_credential::{
     Action, CacheControl, Credential, CredentialResponse, Error, Operation, RegistryInfo, Secret,
 };
+use cargo_util::Sha256;
 use clap::Command;
 use pasetors::{
     keys::{AsymmetricKeyPair, AsymmetricPublicKey, AsymmetricSecretKey, Generate},
     paserk::FormatAsPaserk,
+    token::UntrustedToken,
 };
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 use url::Url;
@@ -231,3 +233,57 @@ pub(crate) fn paserk_public_from_paserk_secret(secret_key: Secret<&str>) -> Opti
     FormatAsPaserk::fmt(&public, &mut paserk_pub_key).unwrap();
     Some(paserk_pub_key)
 }
+
+/// The message of the signature of an index file.
+#[derive(serde::Deserialize)]
+struct IndexSignature<'a> {
+    /// Path of the index file relative to the index root, like `3/b/bar`.
+    path: &'a str,
+    /// SHA256 checksum of the contents of the index file.
+    cksum: &'a str,
+    /// RFC 3339 time after which the signature must not be accepted from the
+    /// registry anymore, so that old index files can't be replayed.
+    expires: &'a str,
+}
+
+/// Verifies the signature of an index file of a registry that signs its index.
+///
+/// The signature is a PASETO in `v3.public` format, signed with the secret key
+/// of `public_key`, whose message is a JSON object with the `path` of the
+/// index file relative to the index root, the SHA256 `cksum` of `data`, and
+/// the RFC 3339 time the signature `expires` at.
+///
+/// The expiration is only checked if `check_expiry` is set, for index files
+/// just received from the registry. Index files from the index cache or from
+/// a snapshot were checked when they were received.
+pub(crate) fn verify_index_signature(
+    public_key: &str,
+    signature: &str,
+    path: &str,
+    data: &[u8],
+    check_expiry: bool,
+) -> anyhow::Result<()> {
+    let public: AsymmetricPublicKey<pasetors::version3::V3> = public_key
+        .try_into()
+        .context("failed to load the signing key of the registry")?;
+    let untrusted_token =
+        UntrustedToken::<pasetors::Public, pasetors::version3::V3>::try_from(signature)
+            .context("the signature is not a PASETO in `v3.public` format")?;
+    let trusted_token =
+        pasetors::version3::PublicToken::verify(&public, &untrusted_token, None, None)
+            .context("the signature was not made with the signing key of the registry")?;
+    let message: IndexSignature<'_> =
+        serde_json::from_str(trusted_token.payload()).context("invalid signature message")?;
+    if message.path != path {
+        anyhow::bail!("the signature is for `{}`", message.path);
+    }
+    if message.cksum != Sha256::new().update(data).finish_hex() {
+        anyhow::bail!("the signature is for different contents");
+    }
+    let expires = OffsetDateTime::parse(message.expires, &Rfc3339)
+        .with_context(|| format!("invalid signature expiration `{}`", message.expires))?;
+    if check_expiry && expires < OffsetDateTime::now_utc() {
+        anyhow::bail!("the signature expired at {}", message.expires);
+    }
+    Ok(())
+}
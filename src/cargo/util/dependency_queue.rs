@@ -170,6 +170,11 @@ impl<N: Hash + Eq + Clone, E: Eq + Hash + Clone, V> DependencyQueue<N, E, V> {
         self.dep_map.len()
     }
 
+    /// Returns the packages that have not been dequeued yet.
+    pub fn pending(&self) -> impl Iterator<Item = &N> {
+        self.dep_map.keys()
+    }
+
     /// Indicate that something has finished.
     ///
     /// Calling this function indicates that the `node` has produced `edge`. All
@@ -1,15 +1,38 @@
-use crate::core::{Edition, Feature, Features, Manifest, Package};
+use crate::core::compiler::unused_features::ReadFeatures;
+use crate::core::{
+    Dependency, Edition, Feature, FeatureValue, Features, Manifest, Package, Workspace,
+};
+use crate::util::interning::InternedString;
 use crate::{CargoResult, GlobalContext};
-use annotate_snippets::{Level, Snippet};
-use cargo_util_schemas::manifest::{TomlLintLevel, TomlToolLints};
+use annotate_snippets::{Level, Message, Snippet};
+use cargo_util_schemas::manifest::{TomlLintLevel, TomlLints, TomlToolLints};
 use pathdiff::diff_paths;
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::ops::Range;
 use std::path::Path;
 use toml_edit::ImDocument;
 
 const LINT_GROUPS: &[LintGroup] = &[TEST_DUMMY_UNSTABLE];
-pub const LINTS: &[Lint] = &[IM_A_TEAPOT, UNKNOWN_LINTS];
+pub const LINTS: &[Lint] = &[
+    IM_A_TEAPOT,
+    UNKNOWN_LINTS,
+    UNUSED_DEPENDENCIES,
+    UNUSED_FEATURES,
+];
+
+/// Returns the `[lints.cargo]` table of a package.
+pub fn cargo_lints_table(pkg: &Package) -> TomlToolLints {
+    pkg.manifest()
+        .normalized_toml()
+        .lints
+        .clone()
+        .map(|lints| lints.lints)
+        .unwrap_or(TomlLints::default())
+        .get("cargo")
+        .cloned()
+        .unwrap_or(TomlToolLints::default())
+}
 
 pub fn analyze_cargo_lints_table(
     pkg: &Package,
@@ -605,6 +628,360 @@ fn output_unknown_lints(
     Ok(())
 }
 
+const UNUSED_DEPENDENCIES: Lint = Lint {
+    name: "unused_dependencies",
+    desc: "unused dependency",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies that are not used by any of the targets they apply to.
+
+Normal dependencies apply to every target except the build script, dev
+dependencies to tests, benchmarks, examples and targets built in test mode,
+and build dependencies to the build script. A dependency is only reported once
+all of those targets were built by the command, like with
+`cargo check --all-targets`, and none of them used it.
+
+`cargo fix` removes the reported dependencies from `Cargo.toml`.
+
+### Why it is bad
+Unused dependencies slow down builds and pull in code, and possibly
+vulnerabilities, for no benefit.
+
+### Drawbacks
+- Doctests are not checked. Dev dependencies of a library with doctests are
+  not reported, and normal dependencies only used by doctests are reported
+  even though they belong in `[dev-dependencies]`.
+
+### Example
+```toml
+[dependencies]
+regex = "1.10" # not used by any target
+```
+"#,
+    ),
+};
+
+/// Returns `true` if the `unused_dependencies` lint runs for the package.
+pub fn unused_dependencies_enabled(pkg: &Package) -> bool {
+    let manifest = pkg.manifest();
+    let (lint_level, _) = UNUSED_DEPENDENCIES.level(
+        &cargo_lints_table(pkg),
+        manifest.edition(),
+        manifest.unstable_features(),
+    );
+    lint_level != LintLevel::Allow
+}
+
+pub fn check_unused_dependencies(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    unused: &[Dependency],
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason) =
+        UNUSED_DEPENDENCIES.level(pkg_lints, manifest.edition(), manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(());
+    }
+
+    let level = lint_level.to_diagnostic_level();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let emitted_reason = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        UNUSED_DEPENDENCIES.name
+    );
+    for (i, dep) in unused.iter().enumerate() {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("{}: `{}`", UNUSED_DEPENDENCIES.desc, dep.name_in_toml());
+        let platform = dep.platform().map(|p| p.to_string());
+        let mut key_path = Vec::new();
+        if let Some(platform) = &platform {
+            key_path.extend(["target", platform.as_str()]);
+        }
+        key_path.extend([dep.kind().kind_table(), dep.name_in_toml().as_str()]);
+
+        let mut message = if let (Some(key_span), Some(value_span)) = (
+            get_span(manifest.document(), &key_path, false),
+            get_span(manifest.document(), &key_path, true),
+        ) {
+            level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(key_span.start..value_span.end))
+                    .fold(true),
+            )
+        } else {
+            level.title(&title)
+        };
+        if i == 0 {
+            message = message.footer(Level::Note.title(&emitted_reason));
+        }
+        message = message
+            .footer(Level::Help.title("remove the dependency, or run `cargo fix` to remove it"));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const UNUSED_FEATURES: Lint = Lint {
+    name: "unused_features",
+    desc: "unused feature",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+Checks for features of a workspace member that are never enabled, and for
+features that are enabled but never read.
+
+A feature is enabled if it is a default feature, or if a workspace member
+enables it through a dependency on the package, the `required-features` of a
+target, or `package.metadata.docs.rs.features`, directly or through other
+features.
+
+Features enabled on the command-line or by any package in the build count
+as enabled too.
+
+A feature is read if it enables a dependency or another feature, if it is
+in the `required-features` of a target, or if a source file compiled for the
+package checks it with `cfg(feature = "...")` or names its
+`CARGO_FEATURE_*` environment variable in a build script. A feature is only
+reported as not read once all the targets of the package were built, like
+with `cargo check --all-targets`.
+
+This also reports the `cfg(feature = "...")` checks of features that the
+package does not declare.
+
+### Why it is bad
+Features that nothing enables are dead code paths that are never built or
+tested, and features that nothing reads only mislead users into enabling them.
+
+### Drawbacks
+- Features enabled by packages outside of the workspace are reported, so this
+  is mostly useful for packages that are not published.
+- The `cfg` predicates are found in the source files, so uses generated by
+  macros or other code generation are not found.
+
+### Example
+```toml
+[features]
+legacy = [] # no member enables it, and no `cfg` reads it
+```
+"#,
+    ),
+};
+
+/// Returns `true` if the `unused_features` lint runs for the package.
+pub fn unused_features_enabled(pkg: &Package) -> bool {
+    let manifest = pkg.manifest();
+    let (lint_level, _) = UNUSED_FEATURES.level(
+        &cargo_lints_table(pkg),
+        manifest.edition(),
+        manifest.unstable_features(),
+    );
+    lint_level != LintLevel::Allow
+}
+
+/// Emits the lint for the features of `pkg`.
+///
+/// `built` has the features enabled for the units of `pkg` in the current
+/// build, including the ones from the command-line. `read` has the features
+/// read by its sources, if all of its targets were checked.
+pub fn check_unused_features(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    ws: &Workspace<'_>,
+    built: &HashSet<InternedString>,
+    read: Option<&ReadFeatures>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason) =
+        UNUSED_FEATURES.level(pkg_lints, manifest.edition(), manifest.unstable_features());
+    if lint_level == LintLevel::Allow || !ws.is_member(pkg) {
+        return Ok(());
+    }
+    let declared = manifest.normalized_toml().features.as_ref();
+
+    let mut enabled = enabled_features(pkg, ws);
+    enabled.extend(built.iter().copied());
+    // Features gating a target are read by cargo itself.
+    let required: HashSet<&str> = manifest
+        .targets()
+        .iter()
+        .filter_map(|t| t.required_features())
+        .flatten()
+        .map(|f| f.as_str())
+        .collect();
+    let mut unused = Vec::new();
+    for (name, values) in declared.into_iter().flatten() {
+        if name.as_str() == "default" {
+            continue;
+        }
+        if !enabled.contains(name.as_str()) {
+            unused.push((
+                name.as_str(),
+                "it is not enabled by any workspace member or target",
+            ));
+        } else if values.is_empty()
+            && !required.contains(name.as_str())
+            && read.is_some_and(|read| !read.contains(name))
+        {
+            unused.push((
+                name.as_str(),
+                "it is not read by any `cfg(feature = \"...\")`",
+            ));
+        }
+    }
+    unused.sort_unstable();
+    // Checks of features that don't exist, including the implicit features
+    // of optional dependencies.
+    let mut unknown: Vec<&str> = read
+        .into_iter()
+        .flat_map(|read| read.cfgs.iter())
+        .map(String::as_str)
+        .filter(|name| !pkg.summary().features().contains_key(*name))
+        .collect();
+    unknown.sort_unstable();
+
+    let level = lint_level.to_diagnostic_level();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let emitted_reason = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        UNUSED_FEATURES.name
+    );
+    let mut emitted_note = false;
+    // Only the first message explains the lint level.
+    fn note<'a>(message: Message<'a>, reason: &'a str, emitted_note: &mut bool) -> Message<'a> {
+        if std::mem::replace(emitted_note, true) {
+            message
+        } else {
+            message.footer(Level::Note.title(reason))
+        }
+    }
+    for (name, why) in unused {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("{}: `{name}`", UNUSED_FEATURES.desc);
+        let message = if let (Some(key_span), Some(value_span)) = (
+            get_span(manifest.document(), &["features", name], false),
+            get_span(manifest.document(), &["features", name], true),
+        ) {
+            level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(key_span.start..value_span.end).label(why))
+                    .fold(true),
+            )
+        } else {
+            level.title(&title)
+        };
+        let message = note(message, &emitted_reason, &mut emitted_note)
+            .footer(Level::Help.title("remove the feature from `[features]`"));
+
+        gctx.shell().print_message(message)?;
+    }
+    for name in unknown {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!(
+            "`cfg(feature = \"{name}\")` checks a feature that `{}` does not declare",
+            pkg.name()
+        );
+        let message = note(level.title(&title), &emitted_reason, &mut emitted_note)
+            .footer(Level::Help.title("add the feature to `[features]`, or remove the check"));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Returns the features of `pkg` that are enabled by the workspace, directly
+/// or through other features.
+fn enabled_features(pkg: &Package, ws: &Workspace<'_>) -> HashSet<InternedString> {
+    let manifest = pkg.manifest();
+    let mut pending: Vec<InternedString> = vec![InternedString::new("default")];
+
+    for target in manifest.targets() {
+        if let Some(required) = target.required_features() {
+            pending.extend(
+                required
+                    .iter()
+                    .filter(|f| !f.contains('/'))
+                    .map(|f| InternedString::new(f)),
+            );
+        }
+    }
+    if let Some(docs_rs) = manifest
+        .custom_metadata()
+        .and_then(|m| m.get("docs"))
+        .and_then(|m| m.get("rs"))
+        .and_then(|m| m.get("features"))
+        .and_then(|f| f.as_array())
+    {
+        pending.extend(
+            docs_rs
+                .iter()
+                .filter_map(|f| f.as_str())
+                .map(InternedString::new),
+        );
+    }
+    for member in ws.members() {
+        for dep in member.dependencies() {
+            if !dep.matches_id(pkg.package_id()) {
+                continue;
+            }
+            if dep.uses_default_features() {
+                pending.push(InternedString::new("default"));
+            }
+            pending.extend(dep.features().iter().copied());
+            for value in member.summary().features().values().flatten() {
+                if let FeatureValue::DepFeature {
+                    dep_name,
+                    dep_feature,
+                    ..
+                } = value
+                {
+                    if *dep_name == dep.name_in_toml() {
+                        pending.push(*dep_feature);
+                    }
+                }
+            }
+        }
+    }
+
+    let features = pkg.summary().features();
+    let mut enabled = HashSet::new();
+    while let Some(feature) = pending.pop() {
+        if !enabled.insert(feature) {
+            continue;
+        }
+        for value in features.get(&feature).into_iter().flatten() {
+            if let FeatureValue::Feature(name) = value {
+                pending.push(*name);
+            }
+        }
+    }
+    enabled
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
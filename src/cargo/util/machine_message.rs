@@ -43,6 +43,10 @@ pub struct Artifact<'a> {
     pub filenames: Vec<PathBuf>,
     pub executable: Option<PathBuf>,
     pub fresh: bool,
+    /// The `--target` this artifact was built for, only reported with
+    /// `-Zmultitarget-summary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_triple: Option<&'a str>,
 }
 
 impl<'a> Message for Artifact<'a> {
@@ -95,6 +99,10 @@ pub struct TimingInfo<'a> {
     pub duration: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rmeta_time: Option<f64>,
+    /// The `--target` this unit was built for, only reported with
+    /// `-Zmultitarget-summary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_triple: Option<&'a str>,
 }
 
 impl<'a> Message for TimingInfo<'a> {
@@ -106,6 +114,16 @@ impl<'a> Message for TimingInfo<'a> {
 #[derive(Serialize)]
 pub struct BuildFinished {
     pub success: bool,
+    /// Whether each requested `--target` built successfully, only reported
+    /// with `-Zmultitarget-summary`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targets: Option<Vec<BuildFinishedTarget>>,
+}
+
+#[derive(Serialize)]
+pub struct BuildFinishedTarget {
+    pub target: String,
+    pub success: bool,
 }
 
 impl Message for BuildFinished {
@@ -113,3 +131,70 @@ impl Message for BuildFinished {
         "build-finished"
     }
 }
+
+/// Dependency resolution failed, emitted before the error is reported.
+#[derive(Serialize)]
+pub struct ResolveError {
+    /// The human-readable error.
+    pub message: String,
+    /// The package whose dependency couldn't be resolved, followed by the
+    /// packages depending on it up to a workspace member.
+    pub package_path: Vec<PackageIdSpec>,
+    /// The dependency that couldn't be resolved, if the failure was about a
+    /// single dependency. The fields below are only filled in along with it.
+    pub dependency: Option<ResolveErrorDependency>,
+    /// Versions matching the requirement that were considered.
+    pub candidates: Vec<ResolveErrorCandidate>,
+    /// Previously selected packages that ruled out the candidates.
+    pub conflicts: Vec<ResolveErrorConflict>,
+    /// Versions that were left out before resolution.
+    pub filtered: ResolveErrorFiltered,
+}
+
+#[derive(Serialize)]
+pub struct ResolveErrorDependency {
+    pub name: String,
+    pub requirement: String,
+    pub source: String,
+    pub required_by: PackageIdSpec,
+}
+
+#[derive(Serialize)]
+pub struct ResolveErrorCandidate {
+    pub version: String,
+    pub rust_version: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ResolveErrorConflict {
+    pub package_id: PackageIdSpec,
+    /// The kind of conflict, like `semver` or `links`.
+    pub reason: &'static str,
+    /// The `links` value or the features involved.
+    pub detail: Option<String>,
+    /// What caused `package_id` to be selected, absent for workspace roots.
+    pub required_by: Option<ResolveErrorRequirement>,
+}
+
+#[derive(Serialize)]
+pub struct ResolveErrorRequirement {
+    pub package_id: PackageIdSpec,
+    pub requirement: String,
+}
+
+#[derive(Serialize, Default)]
+pub struct ResolveErrorFiltered {
+    /// Yanked versions that would otherwise match the requirement.
+    pub yanked: Vec<String>,
+    /// Candidates needing a newer Rust than the `rust-version` resolved for.
+    /// These are only deprioritized, never left out.
+    pub incompatible_rust_version: Vec<String>,
+    /// Whether `--offline` limited the candidates to downloaded versions.
+    pub offline: bool,
+}
+
+impl Message for ResolveError {
+    fn reason(&self) -> &str {
+        "resolve-error"
+    }
+}
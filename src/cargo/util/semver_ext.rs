@@ -191,6 +191,58 @@ impl From<VersionReq> for OptVersionReq {
     }
 }
 
+/// A requirement from `[workspace.constraints]`.
+///
+/// On top of the comparators of a [`VersionReq`], this accepts `!=VERSION` to
+/// exclude a single version, as in `">=0.3, !=0.3.35"`.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct VersionConstraint {
+    req: VersionReq,
+    excluded: Vec<Version>,
+    /// The constraint as written in the manifest.
+    original: String,
+}
+
+impl VersionConstraint {
+    pub fn matches(&self, version: &Version) -> bool {
+        // Constraints only narrow the requirements of dependencies, so they
+        // don't get to exclude pre-releases on their own.
+        self.req.matches_prerelease(version) && !self.excluded.contains(version)
+    }
+}
+
+impl std::str::FromStr for VersionConstraint {
+    type Err = semver::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut comparators = Vec::new();
+        let mut excluded = Vec::new();
+        for part in s.split(',').map(str::trim) {
+            if let Some(version) = part.strip_prefix("!=") {
+                excluded.push(version.trim().parse()?);
+            } else {
+                comparators.push(part);
+            }
+        }
+        let req = if comparators.is_empty() {
+            VersionReq::STAR
+        } else {
+            comparators.join(", ").parse()?
+        };
+        Ok(VersionConstraint {
+            req,
+            excluded,
+            original: s.to_owned(),
+        })
+    }
+}
+
+impl Display for VersionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
 #[cfg(test)]
 mod matches_prerelease {
     use semver::VersionReq;
@@ -280,3 +332,38 @@ mod matches_prerelease {
         assert!(req.matches_prerelease(&to_ver));
     }
 }
+
+#[cfg(test)]
+mod version_constraint {
+    use super::Version;
+    use super::VersionConstraint;
+
+    #[test]
+    fn matches() {
+        let cases = [
+            ("<0.9.100", "0.9.99", true),
+            ("<0.9.100", "0.9.100", false),
+            ("!=0.3.35", "0.3.35", false),
+            ("!=0.3.35", "0.3.36", true),
+            ("!=0.3.35", "0.4.0-alpha.1", true),
+            (">=0.3, !=0.3.35", "0.3.34", true),
+            (">=0.3, !=0.3.35", "0.3.35", false),
+            (">=0.3, !=0.3.35", "0.2.9", false),
+        ];
+        for (constraint, ver, expected) in cases {
+            let constraint: VersionConstraint = constraint.parse().unwrap();
+            let ver = Version::parse(ver).unwrap();
+            assert_eq!(
+                constraint.matches(&ver),
+                expected,
+                "constraint: {constraint}, ver: {ver}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert!("!=0.3".parse::<VersionConstraint>().is_err());
+        assert!("<=>1".parse::<VersionConstraint>().is_err());
+    }
+}
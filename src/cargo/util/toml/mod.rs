@@ -75,7 +75,8 @@ pub fn read_manifest(
         let empty = Vec::new();
         let cargo_features = original_toml.cargo_features.as_ref().unwrap_or(&empty);
         let features = Features::new(cargo_features, gctx, &mut warnings, source_id.is_path())?;
-        let workspace_config = to_workspace_config(&original_toml, path, gctx, &mut warnings)?;
+        let workspace_config =
+            to_workspace_config(&original_toml, path, &features, gctx, &mut warnings)?;
         if let WorkspaceConfig::Root(ws_root_config) = &workspace_config {
             let package_root = path.parent().unwrap();
             gctx.ws_roots
@@ -203,6 +204,7 @@ fn stringify(dst: &mut String, path: &serde_ignored::Path<'_>) {
 fn to_workspace_config(
     original_toml: &manifest::TomlManifest,
     manifest_file: &Path,
+    features: &Features,
     gctx: &GlobalContext,
     warnings: &mut Vec<String>,
 ) -> CargoResult<WorkspaceConfig> {
@@ -226,7 +228,10 @@ fn to_workspace_config(
                     unused_dep_keys(name, "workspace.dependencies", dep.unused_keys(), warnings);
                 }
             }
-            let ws_root_config = to_workspace_root_config(toml_config, manifest_file);
+            if toml_config.constraints.is_some() {
+                features.require(Feature::workspace_constraints())?;
+            }
+            let ws_root_config = to_workspace_root_config(toml_config, manifest_file)?;
             WorkspaceConfig::Root(ws_root_config)
         }
         (None, root) => WorkspaceConfig::Member {
@@ -243,7 +248,7 @@ fn to_workspace_config(
 fn to_workspace_root_config(
     normalized_toml: &manifest::TomlWorkspace,
     manifest_file: &Path,
-) -> WorkspaceRootConfig {
+) -> CargoResult<WorkspaceRootConfig> {
     let package_root = manifest_file.parent().unwrap();
     let inheritable = InheritableFields {
         package: normalized_toml.package.clone(),
@@ -251,6 +256,13 @@ fn to_workspace_root_config(
         lints: normalized_toml.lints.clone(),
         _ws_root: package_root.to_owned(),
     };
+    let mut constraints = BTreeMap::new();
+    for (name, constraint) in normalized_toml.constraints.iter().flatten() {
+        let constraint = constraint.parse().with_context(|| {
+            format!("failed to parse the version constraint `{constraint}` for `workspace.constraints.{name}`")
+        })?;
+        constraints.insert(InternedString::new(name), constraint);
+    }
     let ws_root_config = WorkspaceRootConfig::new(
         package_root,
         &normalized_toml.members,
@@ -258,8 +270,9 @@ fn to_workspace_root_config(
         &normalized_toml.exclude,
         &Some(inheritable),
         &normalized_toml.metadata,
+        constraints,
     );
-    ws_root_config
+    Ok(ws_root_config)
 }
 
 /// See [`Manifest::normalized_toml`] for more details
@@ -583,6 +596,16 @@ fn normalize_package_toml<'a>(
         metabuild: original_package.metabuild.clone(),
         default_target: original_package.default_target.clone(),
         forced_target: original_package.forced_target.clone(),
+        target_specs: original_package
+            .target_specs
+            .clone()
+            .map(|value| {
+                field_inherit_with(value, "target-specs", || {
+                    inherit()?.target_specs(package_root)
+                })
+            })
+            .transpose()?
+            .map(manifest::InheritableField::Value),
         links: original_package.links.clone(),
         exclude: original_package
             .exclude
@@ -968,6 +991,17 @@ impl InheritableFields {
         resolve_relative_path("license-file", &self._ws_root, package_root, license_file)
     }
 
+    /// Gets the field `workspace.package.target-specs`.
+    fn target_specs(&self, package_root: &Path) -> CargoResult<Vec<String>> {
+        let Some(target_specs) = self.package.as_ref().and_then(|p| p.target_specs.as_ref()) else {
+            bail!("`workspace.package.target-specs` was not defined");
+        };
+        target_specs
+            .iter()
+            .map(|spec| resolve_relative_path("target-specs", &self._ws_root, package_root, spec))
+            .collect()
+    }
+
     /// Gets the field `workspace.package.readme`.
     fn readme(&self, package_root: &Path) -> CargoResult<manifest::StringOrBool> {
         let Some(readme) = normalize_package_readme(
@@ -1554,16 +1588,28 @@ pub fn to_real_manifest(
         }
     }
 
+    let target_specs = to_target_specs(
+        normalized_package
+            .normalized_target_specs()
+            .expect("previously normalized"),
+        package_root,
+        &features,
+    )?;
+    let compile_target =
+        |name: &str| match target_specs.iter().find(|spec| spec.short_name() == name) {
+            Some(spec) => Ok(*spec),
+            None => CompileTarget::new(name),
+        };
     let default_kind = normalized_package
         .default_target
-        .as_ref()
-        .map(|t| CompileTarget::new(&*t))
+        .as_deref()
+        .map(compile_target)
         .transpose()?
         .map(CompileKind::Target);
     let forced_kind = normalized_package
         .forced_target
-        .as_ref()
-        .map(|t| CompileTarget::new(&*t))
+        .as_deref()
+        .map(compile_target)
         .transpose()?
         .map(CompileKind::Target);
     let include = normalized_package
@@ -1589,6 +1635,7 @@ pub fn to_real_manifest(
         summary,
         default_kind,
         forced_kind,
+        target_specs,
         targets,
         exclude,
         include,
@@ -1718,6 +1765,46 @@ fn missing_dep_diagnostic(
     Err(AlreadyPrintedError::new(anyhow!("").into()).into())
 }
 
+/// Loads the custom target specifications declared in `package.target-specs`.
+///
+/// Their file stems can then be used as target names by `--target`,
+/// `package.forced-target` and `package.default-target`.
+fn to_target_specs(
+    target_specs: Option<&Vec<String>>,
+    package_root: &Path,
+    features: &Features,
+) -> CargoResult<Vec<CompileTarget>> {
+    let Some(target_specs) = target_specs else {
+        return Ok(Vec::new());
+    };
+    features.require(Feature::target_specs())?;
+
+    let mut loaded: Vec<(&str, CompileTarget)> = Vec::new();
+    for spec in target_specs {
+        if !spec.ends_with(".json") {
+            bail!("target spec `{spec}` in `package.target-specs` must be a `.json` file");
+        }
+        let path = package_root.join(spec);
+        let path = path
+            .to_str()
+            .ok_or_else(|| anyhow::format_err!("non-UTF8 path in `package.target-specs`"))?;
+        let target = CompileTarget::new(path)
+            .with_context(|| format!("failed to load target spec `{spec}`"))?;
+        if let Some((other, _)) = loaded
+            .iter()
+            .find(|(_, t)| t.short_name() == target.short_name())
+        {
+            bail!(
+                "target specs `{other}` and `{spec}` in `package.target-specs` \
+                 both declare the target `{}`",
+                target.short_name()
+            );
+        }
+        loaded.push((spec, target));
+    }
+    Ok(loaded.into_iter().map(|(_, target)| target).collect())
+}
+
 fn to_virtual_manifest(
     contents: String,
     document: toml_edit::ImDocument<String>,
@@ -2779,6 +2866,28 @@ fn prepare_toml_for_publish(
         }
     }
 
+    if let Some(target_specs) = &package.target_specs {
+        let target_specs = target_specs
+            .as_value()
+            .context("target specs should have been resolved before `prepare_for_publish()`")?;
+        let target_specs = target_specs
+            .iter()
+            .map(|spec| {
+                let abs_spec_path = paths::normalize_path(&package_root.join(spec));
+                let Ok(spec_path) = abs_spec_path.strip_prefix(package_root) else {
+                    bail!("target spec `{spec}` is outside of the package and cannot be published");
+                };
+                Ok(normalize_path_string_sep(
+                    spec_path
+                        .to_str()
+                        .ok_or_else(|| anyhow::format_err!("non-UTF8 `package.target-specs`"))?
+                        .to_owned(),
+                ))
+            })
+            .collect::<CargoResult<_>>()?;
+        package.target_specs = Some(manifest::InheritableField::Value(target_specs));
+    }
+
     if let Some(readme) = &package.readme {
         let readme = readme
             .as_value()
@@ -1482,6 +1482,65 @@ failed to select a version for `bad` which could resolve this conflict
         .run();
 }
 
+#[cargo_test]
+fn incompatible_dependencies_explained() {
+    Package::new("bad", "1.0.0").publish();
+    Package::new("bad", "1.0.1").publish();
+    Package::new("bad", "2.0.0").publish();
+    Package::new("bad", "2.0.1").publish();
+    Package::new("bar", "0.1.0").dep("bad", "=1.0.0").publish();
+    Package::new("baz", "0.1.0").dep("bad", ">=2.0.1").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.1.0"
+                baz = "0.1.0"
+                bad = ">=1.0.1, <=2.0.0"
+            "#,
+        )
+        .file("src/main.rs", "fn main(){}")
+        .build();
+
+    p.cargo("build -Zexplain-conflicts")
+        .masquerade_as_nightly_cargo(&["explain-conflicts"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for `bad`.
+    ... required by package `foo v0.0.1 ([ROOT]/foo)`
+versions that meet the requirements `>=1.0.1, <=2.0.0` are: 2.0.0, 1.0.1
+
+all possible versions conflict with previously selected packages.
+
+because `foo v0.0.1 ([ROOT]/foo)` requires `bad = ">=1.0.1, <=2.0.0"` (satisfied by bad v2.0.0)
+and `baz v0.1.0` requires `bad = ">=2.0.1"`, which selected `bad v2.0.1`,
+and only one version of `bad` compatible with 2.x may be selected,
+`foo v0.0.1 ([ROOT]/foo)` and `baz v0.1.0` are incompatible.
+(`baz v0.1.0` is required through `foo v0.0.1 ([ROOT]/foo)` -> `baz v0.1.0`)
+
+because `foo v0.0.1 ([ROOT]/foo)` requires `bad = ">=1.0.1, <=2.0.0"` (satisfied by bad v1.0.1)
+and `bar v0.1.0` requires `bad = "=1.0.0"`, which selected `bad v1.0.0`,
+and only one version of `bad` compatible with 1.x may be selected,
+`foo v0.0.1 ([ROOT]/foo)` and `bar v0.1.0` are incompatible.
+(`bar v0.1.0` is required through `foo v0.0.1 ([ROOT]/foo)` -> `bar v0.1.0`)
+
+to resolve this conflict, consider:
+  - relax the requirement `bad = ">=1.0.1, <=2.0.0"` in `foo v0.0.1 ([ROOT]/foo)`
+
+failed to select a version for `bad` which could resolve this conflict
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn compile_path_dep_then_change_version() {
     let p = project()
@@ -3,6 +3,7 @@
 use super::config::write_config_at;
 use cargo_test_support::paths;
 use cargo_test_support::prelude::*;
+use cargo_test_support::project;
 use cargo_test_support::str;
 use std::fs;
 use std::path::PathBuf;
@@ -574,6 +575,85 @@ profile.dev.package.foo.opt-level = 1
 target.'cfg(target_os = "linux")'.runner = "runme"
 
 
+"#]])
+        .with_stderr_data(str![[r#""#]])
+        .run();
+}
+
+#[cargo_test]
+fn get_discovered_toolchains() {
+    let tools = project()
+        .at("tools")
+        .no_manifest()
+        .executable("riscv64-linux-gnu-gcc", "")
+        .executable("qemu-riscv64", "")
+        .executable("wasmtime", "")
+        .build();
+    // Files that aren't executable are skipped.
+    let shadowed = paths::root().join("shadowed");
+    fs::create_dir_all(&shadowed).unwrap();
+    fs::write(shadowed.join("qemu-riscv64"), "").unwrap();
+    let path = std::env::join_paths([shadowed, tools.root()].into_iter().chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))
+    .unwrap();
+
+    cargo_process(
+        "config get target.riscv64gc-unknown-linux-gnu -Zunstable-options -Zcross-toolchains",
+    )
+    .masquerade_as_nightly_cargo(&["cargo-config", "cross-toolchains"])
+    .env("PATH", &path)
+    .with_stdout_data(str![[r#"
+# The following values were discovered on PATH and are used when not configured.
+# target.riscv64gc-unknown-linux-gnu.linker = "[ROOT]/tools/riscv64-linux-gnu-gcc"
+# target.riscv64gc-unknown-linux-gnu.runner = ["[ROOT]/tools/qemu-riscv64"[..]]
+
+"#]])
+    .with_stderr_data(str![[r#""#]])
+    .run();
+
+    // Configured values take precedence and are not reported as discovered.
+    write_config_at(
+        paths::home().join(".cargo/config.toml"),
+        "
+        [target.riscv64gc-unknown-linux-gnu]
+        linker = \"my-linker\"
+        ",
+    );
+    cargo_process(
+        "config get target.riscv64gc-unknown-linux-gnu --show-origin \
+         -Zunstable-options -Zcross-toolchains",
+    )
+    .masquerade_as_nightly_cargo(&["cargo-config", "cross-toolchains"])
+    .env("PATH", &path)
+    .with_stdout_data(str![[r#"
+target.riscv64gc-unknown-linux-gnu.linker = "my-linker" # [ROOT]/home/.cargo/config.toml
+# The following values were discovered on PATH and are used when not configured.
+# target.riscv64gc-unknown-linux-gnu.runner = ["[ROOT]/tools/qemu-riscv64"[..]]
+
+"#]])
+    .with_stderr_data(str![[r#""#]])
+    .run();
+
+    // WASI targets are run with a WebAssembly runtime.
+    cargo_process("config get target.wasm32-wasip2 -Zunstable-options -Zcross-toolchains")
+        .masquerade_as_nightly_cargo(&["cargo-config", "cross-toolchains"])
+        .env("PATH", &path)
+        .with_stdout_data(str![[r#"
+# The following values were discovered on PATH and are used when not configured.
+# target.wasm32-wasip2.runner = ["[ROOT]/tools/wasmtime", "run"]
+
+"#]])
+        .with_stderr_data(str![[r#""#]])
+        .run();
+
+    // Without the flag nothing is discovered.
+    cargo_process("config get target.riscv64gc-unknown-linux-gnu -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["cargo-config"])
+        .env("PATH", &path)
+        .with_stdout_data(str![[r#"
+target.riscv64gc-unknown-linux-gnu.linker = "my-linker"
+
 "#]])
         .with_stderr_data(str![[r#""#]])
         .run();
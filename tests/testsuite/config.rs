@@ -1990,6 +1990,19 @@ Caused by:
         Some(GitFeatures {
             shallow_index: false,
             shallow_deps: true,
+            sparse_checkout: false,
+        }),
+    ));
+
+    let gctx = GlobalContextBuilder::new()
+        .env("CARGO_UNSTABLE_GIT", "shallow-deps,sparse-checkout")
+        .build();
+    assert!(do_check(
+        gctx,
+        Some(GitFeatures {
+            shallow_deps: true,
+            sparse_checkout: true,
+            ..GitFeatures::default()
         }),
     ));
 
@@ -2034,6 +2047,7 @@ git = 'shallow-index'
         Some(GitFeatures {
             shallow_index: true,
             shallow_deps: false,
+            sparse_checkout: false,
         }),
     ));
 
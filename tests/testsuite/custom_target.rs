@@ -266,3 +266,182 @@ fn custom_target_ignores_filepath() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn target_specs_requires_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+                target-specs = ["custom-target.json"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("custom-target.json", SIMPLE_SPEC)
+        .build();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  feature `target-specs` is required
+
+  The package requires the Cargo feature called `target-specs`, but that feature is not stabilized in this version of Cargo ([..]).
+  Consider trying a newer version of Cargo (this may require the nightly release).
+  See https://doc.rust-lang.org/nightly/cargo/reference/unstable.html#target-specs for more information about the status of this feature.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn target_specs_must_be_json() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["target-specs"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+                target-specs = ["custom-target.toml"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo(&["target-specs"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  target spec `custom-target.toml` in `package.target-specs` must be a `.json` file
+
+"#]])
+        .run();
+}
+
+#[cargo_test(nightly, reason = "requires features no_core, lang_items")]
+fn target_specs_by_name() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["target-specs"]
+
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+                target-specs = ["targets/custom-target.json"]
+            "#,
+        )
+        .file(
+            "src/lib.rs",
+            &"
+                __MINIMAL_LIB__
+
+                pub fn foo() -> u32 {
+                    42
+                }
+            "
+            .replace("__MINIMAL_LIB__", MINIMAL_LIB),
+        )
+        .file("targets/custom-target.json", SIMPLE_SPEC)
+        .build();
+
+    p.cargo("build --lib --target custom-target -v")
+        .masquerade_as_nightly_cargo(&["target-specs"])
+        .with_stderr_data(str![[r#"
+[COMPILING] foo v0.0.1 ([ROOT]/foo)
+[RUNNING] `rustc [..]--target [ROOT]/foo/targets/custom-target.json [..]
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    assert!(p
+        .root()
+        .join("target/custom-target/debug/libfoo.rlib")
+        .is_file());
+}
+
+#[cargo_test(nightly, reason = "requires features no_core, lang_items")]
+fn target_specs_inherited_forced_target() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["kernel"]
+
+                [workspace.package]
+                target-specs = ["targets/custom-target.json"]
+            "#,
+        )
+        .file(
+            "kernel/Cargo.toml",
+            r#"
+                cargo-features = ["target-specs", "per-package-target"]
+
+                [package]
+                name = "kernel"
+                version = "0.0.1"
+                edition = "2015"
+                target-specs.workspace = true
+                forced-target = "custom-target"
+            "#,
+        )
+        .file(
+            "kernel/src/lib.rs",
+            &"
+                __MINIMAL_LIB__
+
+                pub fn foo() -> u32 {
+                    42
+                }
+            "
+            .replace("__MINIMAL_LIB__", MINIMAL_LIB),
+        )
+        .file("targets/custom-target.json", SIMPLE_SPEC)
+        .build();
+
+    p.cargo("build -v")
+        .masquerade_as_nightly_cargo(&["target-specs", "per-package-target"])
+        .with_stderr_data(str![[r#"
+[COMPILING] kernel v0.0.1 ([ROOT]/foo/kernel)
+[RUNNING] `rustc [..]--target [ROOT]/foo/targets/custom-target.json [..]
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    // Editing the shared spec rebuilds the member using it.
+    let spec_path = p.root().join("targets/custom-target.json");
+    let spec = fs::read_to_string(&spec_path).unwrap();
+    fs::write(
+        &spec_path,
+        spec.replace('{', "{\n\"vendor\": \"unknown\",\n"),
+    )
+    .unwrap();
+
+    p.cargo("build -v")
+        .masquerade_as_nightly_cargo(&["target-specs", "per-package-target"])
+        .with_stderr_data(str![[r#"
+[COMPILING] kernel v0.0.1 ([ROOT]/foo/kernel)
+[RUNNING] `rustc [..]--target [ROOT]/foo/targets/custom-target.json [..]
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,270 @@
+//! Tests for the `-Zfeature-unification` feature.
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{project, str, Project};
+
+/// A workspace where `a` and `b` enable different features of the shared
+/// path dependency `common`, whose library checks them with `cfg`.
+fn workspace(common_lib: &str) -> Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                resolver = "2"
+                members = ["a", "b"]
+            "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.1.0"
+                edition = "2021"
+
+                [dependencies]
+                common = { path = "../common", features = ["a"] }
+            "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+                [package]
+                name = "b"
+                version = "0.1.0"
+                edition = "2021"
+
+                [dependencies]
+                common = { path = "../common", features = ["b"] }
+            "#,
+        )
+        .file("b/src/lib.rs", "")
+        .file(
+            "common/Cargo.toml",
+            r#"
+                [package]
+                name = "common"
+                version = "0.1.0"
+                edition = "2021"
+
+                [features]
+                a = []
+                b = []
+            "#,
+        )
+        .file("common/src/lib.rs", common_lib)
+        .build()
+}
+
+#[cargo_test]
+fn workspace_feature_unification() {
+    let p = workspace(
+        r#"
+            #[cfg(not(all(feature = "a", feature = "b")))]
+            compile_error!("features were not unified");
+        "#,
+    );
+
+    p.cargo("check -p a")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[ERROR] features were not unified
+ --> common/src/lib.rs:3:13
+...
+"#]])
+        .run();
+
+    p.cargo("check -p a -Zfeature-unification")
+        .masquerade_as_nightly_cargo(&["feature-unification"])
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "workspace")
+        .with_stderr_data(str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[CHECKING] a v0.1.0 ([ROOT]/foo/a)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    // `common` is already built with the workspace's features.
+    p.cargo("check -p b -Zfeature-unification")
+        .masquerade_as_nightly_cargo(&["feature-unification"])
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "workspace")
+        .with_stderr_data(str![[r#"
+[CHECKING] b v0.1.0 ([ROOT]/foo/b)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn package_feature_unification() {
+    let p = workspace(
+        r#"
+            #[cfg(all(feature = "a", feature = "b"))]
+            compile_error!("features were unified");
+        "#,
+    );
+
+    p.cargo("check --workspace")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[ERROR] features were unified
+ --> common/src/lib.rs:3:13
+...
+"#]])
+        .run();
+
+    p.cargo("check --workspace -Zfeature-unification")
+        .masquerade_as_nightly_cargo(&["feature-unification"])
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "package")
+        .with_stderr_data(
+            str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[CHECKING] a v0.1.0 ([ROOT]/foo/a)
+[CHECKING] b v0.1.0 ([ROOT]/foo/b)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn package_feature_unification_cli_features() {
+    let p = workspace(
+        r#"
+            #[cfg(all(feature = "a", feature = "b"))]
+            compile_error!("features were unified");
+        "#,
+    );
+    p.change_file(
+        "a/Cargo.toml",
+        r#"
+            [package]
+            name = "a"
+            version = "0.1.0"
+            edition = "2021"
+
+            [features]
+            extra = []
+
+            [dependencies]
+            common = { path = "../common", features = ["a"] }
+        "#,
+    );
+
+    // `--features` only applies to the packages that have the feature.
+    p.cargo("check --workspace --features extra -Zfeature-unification")
+        .masquerade_as_nightly_cargo(&["feature-unification"])
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "package")
+        .with_stderr_data(
+            str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[CHECKING] a v0.1.0 ([ROOT]/foo/a)
+[CHECKING] b v0.1.0 ([ROOT]/foo/b)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn workspace_feature_unification_cli_features() {
+    Package::new("bar", "0.1.0").publish();
+    let p = workspace(
+        r#"
+            #[cfg(not(all(feature = "a", feature = "b")))]
+            compile_error!("features were not unified");
+        "#,
+    );
+    p.change_file(
+        "b/Cargo.toml",
+        r#"
+            [package]
+            name = "b"
+            version = "0.1.0"
+            edition = "2021"
+
+            [dependencies]
+            bar = { version = "0.1", optional = true }
+            common = { path = "../common", features = ["b"] }
+        "#,
+    );
+
+    // `--all-features` only applies to the selected package, so the optional
+    // dependency of `b` isn't needed.
+    p.cargo("check -p a --all-features -Zfeature-unification")
+        .masquerade_as_nightly_cargo(&["feature-unification"])
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "workspace")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[CHECKING] a v0.1.0 ([ROOT]/foo/a)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn feature_unification_requires_nightly_flag() {
+    let p = workspace(
+        r#"
+            #[cfg(not(all(feature = "a", feature = "b")))]
+            compile_error!("features were not unified");
+        "#,
+    );
+
+    // The config is ignored without `-Zfeature-unification`.
+    p.cargo("check -p a")
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "workspace")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[ERROR] features were not unified
+ --> common/src/lib.rs:3:13
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn feature_unification_resolver_v1() {
+    let p = workspace(
+        r#"
+            #[cfg(all(feature = "a", feature = "b"))]
+            compile_error!("features were unified");
+        "#,
+    );
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [workspace]
+            resolver = "1"
+            members = ["a", "b"]
+        "#,
+    );
+
+    // The old feature resolver always unifies across the selected packages.
+    p.cargo("check --workspace -Zfeature-unification")
+        .masquerade_as_nightly_cargo(&["feature-unification"])
+        .env("CARGO_RESOLVER_FEATURE_UNIFICATION", "package")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[CHECKING] common v0.1.0 ([ROOT]/foo/common)
+[ERROR] features were unified
+ --> common/src/lib.rs:3:13
+...
+"#]])
+        .run();
+}
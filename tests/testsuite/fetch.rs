@@ -1,7 +1,8 @@
 //! Tests for the `cargo fetch` command.
 
+use cargo_test_support::paths;
 use cargo_test_support::prelude::*;
-use cargo_test_support::registry::Package;
+use cargo_test_support::registry::{cksum, Package, RegistryBuilder};
 use cargo_test_support::rustc_host;
 use cargo_test_support::{basic_manifest, cross_compile, project, str};
 
@@ -145,3 +146,237 @@ fn fetch_warning() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn index_snapshot_requires_z_flag() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("fetch --export-index snapshot")
+        .masquerade_as_nightly_cargo(&["index-snapshot"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--export-index` flag is unstable, pass `-Z index-snapshot` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_snapshot_export_import() {
+    let _registry = RegistryBuilder::new().http_index().build();
+    Package::new("bar", "0.0.1").publish();
+    Package::new("bar", "0.0.2").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "0.0.1"
+            edition = "2015"
+
+            [dependencies]
+            bar = "=0.0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fetch -Zindex-snapshot --export-index snapshot")
+        .masquerade_as_nightly_cargo(&["index-snapshot"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[ADDING] bar v0.0.1 (available: v0.0.2)
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.0.1 (registry `dummy-registry`)
+[EXPORTED] 1 package to `[ROOT]/foo/snapshot`
+
+"#]])
+        .run();
+
+    // Every registry in the snapshot is a local registry.
+    let registries = std::fs::read_dir(p.root().join("snapshot"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(registries.len(), 1);
+    let registry = &registries[0];
+    assert!(registry.join("bar-0.0.1.crate").is_file());
+    assert!(!registry.join("bar-0.0.2.crate").exists());
+    // Index files are exported whole, so that they can be verified.
+    let index = std::fs::read_to_string(registry.join("index/3/b/bar")).unwrap();
+    assert_eq!(index.lines().count(), 2);
+    assert!(index.contains(r#""vers":"0.0.1""#));
+
+    // Start over on a "different machine".
+    paths::cargo_home().join("registry").rm_rf();
+
+    p.cargo("check --offline")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] no matching package named `bar` found
+location searched: `dummy-registry` index (which is replacing registry `crates-io`)
+required by package `foo v0.0.1 ([ROOT]/foo)`
+As a reminder, you're using offline mode (--offline) which can sometimes cause surprising resolution failures, if this error is too confusing you may wish to retry without the offline flag.
+
+"#]])
+        .run();
+
+    p.cargo("fetch -Zindex-snapshot --import-index snapshot --offline")
+        .masquerade_as_nightly_cargo(&["index-snapshot"])
+        .with_stderr_data(str![[r#"
+[IMPORTED] 1 package from `[ROOT]/foo/snapshot`
+
+"#]])
+        .run();
+
+    p.cargo("check --offline")
+        .with_stderr_data(str![[r#"
+[CHECKING] bar v0.0.1
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+fn index_snapshot_project() -> cargo_test_support::Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "0.0.1"
+            edition = "2015"
+
+            [dependencies]
+            bar = "0.0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build()
+}
+
+/// Gets the directory of the only registry exported to `snapshot`.
+fn snapshot_registry(p: &cargo_test_support::Project) -> std::path::PathBuf {
+    std::fs::read_dir(p.root().join("snapshot"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path()
+}
+
+#[cargo_test]
+fn index_snapshot_tampered() {
+    let _registry = RegistryBuilder::new().http_index().build();
+    Package::new("bar", "0.0.1")
+        .file("src/lib.rs", "")
+        .publish();
+    let p = index_snapshot_project();
+
+    p.cargo("fetch -Zindex-snapshot --export-index snapshot")
+        .masquerade_as_nightly_cargo(&["index-snapshot"])
+        .run();
+    paths::cargo_home().join("registry").rm_rf();
+
+    // Swap the `.crate` file, along with its checksum in the index.
+    let registry = snapshot_registry(&p);
+    let crate_file = registry.join("bar-0.0.1.crate");
+    let original = cksum(&std::fs::read(&crate_file).unwrap());
+    let mut tampered = std::fs::read(&crate_file).unwrap();
+    tampered.extend_from_slice(b"evil");
+    std::fs::write(&crate_file, &tampered).unwrap();
+    let index_file = registry.join("index/3/b/bar");
+    let index = std::fs::read_to_string(&index_file)
+        .unwrap()
+        .replace(&original, &cksum(&tampered));
+    std::fs::write(&index_file, index).unwrap();
+
+    p.cargo("fetch -Zindex-snapshot --import-index snapshot --offline")
+        .masquerade_as_nightly_cargo(&["index-snapshot"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] checksum for `bar v0.0.1` in `[ROOT]/foo/snapshot/[..]/index/3/b/bar` does not match the lockfile
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_snapshot_signed() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing("k3.secret.fNYVuMvBgOlljt9TDohnaYLblghqaHoQquVZwgR6X12cBFHZLFsaU3q7X3k1Zn36")
+        .build();
+    Package::new("bar", "0.0.1").publish();
+    let p = index_snapshot_project();
+
+    p.cargo("fetch -Zindex-snapshot -Zindex-signing --export-index snapshot")
+        .masquerade_as_nightly_cargo(&["index-snapshot", "index-signing"])
+        .run();
+    let registry = snapshot_registry(&p);
+    assert!(registry.join("index/3/b/bar.sig").is_file());
+    paths::cargo_home().join("registry").rm_rf();
+
+    p.cargo("fetch -Zindex-snapshot -Zindex-signing --import-index snapshot --offline")
+        .masquerade_as_nightly_cargo(&["index-snapshot", "index-signing"])
+        .with_stderr_data(str![[r#"
+[IMPORTED] 1 package from `[ROOT]/foo/snapshot`
+
+"#]])
+        .run();
+    // The imported index file is verified again when used.
+    p.cargo("check --offline -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_stderr_data(str![[r#"
+[CHECKING] bar v0.0.1
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    paths::cargo_home().join("registry").rm_rf();
+
+    let index_file = registry.join("index/3/b/bar");
+    let mut index = std::fs::read_to_string(&index_file).unwrap();
+    index.push_str(
+        r#"{"name":"bar","vers":"0.0.2","deps":[],"cksum":"","features":{},"yanked":false}"#,
+    );
+    index.push('\n');
+    std::fs::write(&index_file, index).unwrap();
+
+    p.cargo("fetch -Zindex-snapshot -Zindex-signing --import-index snapshot --offline")
+        .masquerade_as_nightly_cargo(&["index-snapshot", "index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to verify the signature of `[ROOT]/foo/snapshot/[..]/index/3/b/bar`
+
+Caused by:
+  the signature is for different contents
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_snapshot_export_git_registry() {
+    Package::new("bar", "0.0.1").publish();
+    let p = index_snapshot_project();
+
+    p.cargo("fetch -Zindex-snapshot --export-index snapshot")
+        .masquerade_as_nightly_cargo(&["index-snapshot"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.0.1 (registry `dummy-registry`)
+[ERROR] cannot export the index of registry `dummy-registry`, only sparse registries can be exported
+
+"#]])
+        .run();
+}
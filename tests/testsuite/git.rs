@@ -669,6 +669,7 @@ fn recompilation() {
 [UPDATING] git repository `[ROOTURL]/bar`
 [LOCKING] 1 package to latest compatible version
 [UPDATING] bar v0.5.0 ([ROOTURL]/bar#[..]) -> #[..]
+             [..] test
 
 "#]])
         .run();
@@ -803,6 +804,7 @@ fn update_with_shared_deps() {
 [UPDATING] git repository `[ROOTURL]/bar`
 [LOCKING] 1 package to latest compatible version
 [UPDATING] bar v0.5.0 ([ROOTURL]/bar#[..]) -> #[..]
+             [..] test
 
 "#]])
         .run();
@@ -839,6 +841,7 @@ Caused by:
 [UPDATING] git repository `[ROOTURL]/bar`
 [LOCKING] 1 package to latest compatible version
 [UPDATING] bar v0.5.0 ([ROOTURL]/bar#[..]) -> #[..]
+             [..] test
 
 "#]])
         .run();
@@ -1368,6 +1371,7 @@ fn two_deps_only_update_one() {
 [UPDATING] git repository `[ROOTURL]/dep1`
 [LOCKING] 1 package to latest compatible version
 [UPDATING] dep1 v0.5.0 ([ROOTURL]/dep1#[..]) -> #[..]
+             [..] test
 
 "#]])
         .run();
@@ -1558,6 +1562,7 @@ project2
 [UPDATING] git submodule `[ROOTURL]/dep3`
 [LOCKING] 1 package to latest compatible version
 [UPDATING] dep1 v0.5.0 ([ROOTURL]/dep1#[..]) -> #[..]
+             [..] test
 
 "#]])
         .run();
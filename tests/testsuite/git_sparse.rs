@@ -0,0 +1,388 @@
+//! Tests for `-Zgit=sparse-checkout`.
+
+use std::path::PathBuf;
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::{basic_manifest, git, paths, project, str, Project};
+
+/// A repository hosting a workspace with a few packages: `a` depends on `b`
+/// through a path dependency inherited from the workspace, and `c` is
+/// unrelated.
+fn monorepo() -> Project {
+    git::new("monorepo", |project| {
+        project
+            .file(
+                "Cargo.toml",
+                r#"
+                    [workspace]
+                    members = ["crates/*"]
+
+                    [workspace.package]
+                    version = "0.1.0"
+
+                    [workspace.dependencies]
+                    b = { path = "crates/b" }
+                "#,
+            )
+            .file("README.md", "monorepo")
+            .file(
+                "crates/a/Cargo.toml",
+                r#"
+                    [package]
+                    name = "a"
+                    version.workspace = true
+                    edition = "2015"
+
+                    [dependencies]
+                    b.workspace = true
+                "#,
+            )
+            .file("crates/a/src/lib.rs", "extern crate b; pub use b::b;")
+            .file("crates/b/Cargo.toml", &basic_manifest("b", "0.1.0"))
+            .file("crates/b/src/lib.rs", "pub fn b() {}")
+            .file("crates/c/Cargo.toml", &basic_manifest("c", "0.1.0"))
+            .file("crates/c/src/lib.rs", "pub fn c() {}")
+            .file("docs/big.txt", "not needed")
+    })
+}
+
+fn sparse_checkouts() -> Vec<PathBuf> {
+    glob::glob(
+        paths::home()
+            .join(".cargo/git/checkouts/monorepo-*/*")
+            .to_str()
+            .unwrap(),
+    )
+    .unwrap()
+    .map(Result::unwrap)
+    .collect()
+}
+
+#[cargo_test]
+fn only_checks_out_needed_packages() {
+    let repo = monorepo();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    a = {{ git = "{}" }}
+                "#,
+                repo.url()
+            ),
+        )
+        .file("src/lib.rs", "extern crate a; pub fn foo() { a::b() }")
+        .build();
+
+    p.cargo("check -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["git"])
+        .with_stderr_data(str![[r#"
+[UPDATING] git repository `[ROOTURL]/monorepo`
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] b v0.1.0 ([ROOTURL]/monorepo#[..])
+[CHECKING] a v0.1.0 ([ROOTURL]/monorepo#[..])
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 1);
+    let checkout = &checkouts[0];
+    assert!(checkout.to_str().unwrap().ends_with("-sparse"));
+    assert!(checkout.join("Cargo.toml").is_file());
+    assert!(checkout.join("README.md").is_file());
+    assert!(checkout.join("crates/a/src/lib.rs").is_file());
+    assert!(checkout.join("crates/b/src/lib.rs").is_file());
+    assert!(!checkout.join("crates/c").exists());
+    assert!(!checkout.join("docs").exists());
+    assert_eq!(
+        std::fs::read_to_string(checkout.join(".cargo-sparse")).unwrap(),
+        "crates/a\ncrates/b\n"
+    );
+
+    // A full checkout lives next to the sparse one.
+    p.cargo("check")
+        .with_stderr_data(str![[r#"
+[CHECKING] b v0.1.0 ([ROOTURL]/monorepo#[..])
+[CHECKING] a v0.1.0 ([ROOTURL]/monorepo#[..])
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 2);
+    let full = checkouts
+        .iter()
+        .find(|c| !c.to_str().unwrap().ends_with("-sparse"))
+        .unwrap();
+    assert!(full.join("crates/c/src/lib.rs").is_file());
+    assert!(full.join("docs/big.txt").is_file());
+}
+
+#[cargo_test]
+fn widens_checkout_for_another_package() {
+    let repo = monorepo();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    b = {{ git = "{}" }}
+                "#,
+                repo.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["git"])
+        .run();
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 1);
+    let checkout = &checkouts[0];
+    assert!(checkout.join("crates/b/src/lib.rs").is_file());
+    assert!(!checkout.join("crates/a").exists());
+    assert!(!checkout.join("crates/c").exists());
+
+    p.change_file(
+        "Cargo.toml",
+        &format!(
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                b = {{ git = "{0}" }}
+                c = {{ git = "{0}" }}
+            "#,
+            repo.url()
+        ),
+    );
+    p.cargo("check -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["git"])
+        .with_stderr_data(str![[r#"
+[UPDATING] git repository `[ROOTURL]/monorepo`
+[LOCKING] 1 package to latest compatible version
+[ADDING] c v0.1.0 ([ROOTURL]/monorepo#[..])
+[CHECKING] c v0.1.0 ([ROOTURL]/monorepo#[..])
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    assert_eq!(sparse_checkouts(), checkouts);
+    assert!(checkout.join("crates/b/src/lib.rs").is_file());
+    assert!(checkout.join("crates/c/src/lib.rs").is_file());
+    assert!(!checkout.join("crates/a").exists());
+    assert_eq!(
+        std::fs::read_to_string(checkout.join(".cargo-sparse")).unwrap(),
+        "crates/b\ncrates/c\n"
+    );
+}
+
+#[cargo_test]
+fn gitoxide_sparse_checkout() {
+    let repo = monorepo();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    a = {{ git = "{}" }}
+                "#,
+                repo.url()
+            ),
+        )
+        .file("src/lib.rs", "extern crate a; pub fn foo() { a::b() }")
+        .build();
+
+    p.cargo("check -Zgitoxide=fetch,checkout -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["gitoxide", "git"])
+        .run();
+
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 1);
+    let checkout = &checkouts[0];
+    assert!(checkout.join("crates/a/src/lib.rs").is_file());
+    assert!(checkout.join("crates/b/src/lib.rs").is_file());
+    assert!(!checkout.join("crates/c").exists());
+}
+
+#[cargo_test]
+fn checks_out_paths_referred_to_from_outside_package() {
+    let repo = git::new("monorepo", |project| {
+        project
+            .file(
+                "Cargo.toml",
+                r#"
+                    [workspace]
+                    members = ["crates/*"]
+                "#,
+            )
+            .file("crates/a/Cargo.toml", &basic_manifest("a", "0.1.0"))
+            .file(
+                "crates/a/src/lib.rs",
+                r#"pub const DATA: &str = include_str!("../../../shared/data.txt");"#,
+            )
+            .file(
+                "crates/a/build.rs",
+                r#"
+                    fn main() {
+                        println!("cargo::rerun-if-changed=../../proto/a.proto");
+                        std::fs::read_to_string("../../proto/a.proto").unwrap();
+                    }
+                "#,
+            )
+            .file("crates/c/Cargo.toml", &basic_manifest("c", "0.1.0"))
+            .file("crates/c/src/lib.rs", "")
+            .file("shared/data.txt", "data")
+            .file("proto/a.proto", "syntax = \"proto3\";")
+            .file("docs/big.txt", "not needed")
+    });
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    a = {{ git = "{}" }}
+                "#,
+                repo.url()
+            ),
+        )
+        .file("src/lib.rs", "extern crate a; pub use a::DATA;")
+        .build();
+
+    p.cargo("check -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["git"])
+        .run();
+
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 1);
+    let checkout = &checkouts[0];
+    assert!(checkout.to_str().unwrap().ends_with("-sparse"));
+    assert!(checkout.join("shared/data.txt").is_file());
+    assert!(checkout.join("proto/a.proto").is_file());
+    assert!(!checkout.join("crates/c").exists());
+    assert!(!checkout.join("docs").exists());
+    assert_eq!(
+        std::fs::read_to_string(checkout.join(".cargo-sparse")).unwrap(),
+        "crates/a\nproto\nshared\n"
+    );
+}
+
+#[cargo_test]
+fn checks_out_directories_with_glob_characters() {
+    let repo = git::new("monorepo", |project| {
+        project
+            .file(
+                "Cargo.toml",
+                r#"
+                    [workspace]
+                    members = ["crates/*"]
+                "#,
+            )
+            .file("crates/a[1]/Cargo.toml", &basic_manifest("a", "0.1.0"))
+            .file("crates/a[1]/src/lib.rs", "pub fn a() {}")
+            .file("crates/a1/Cargo.toml", &basic_manifest("c", "0.1.0"))
+            .file("crates/a1/src/lib.rs", "")
+    });
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    a = {{ git = "{}" }}
+                "#,
+                repo.url()
+            ),
+        )
+        .file("src/lib.rs", "extern crate a; pub use a::a;")
+        .build();
+
+    p.cargo("check -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["git"])
+        .run();
+
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 1);
+    let checkout = &checkouts[0];
+    assert!(checkout.join("crates/a[1]/src/lib.rs").is_file());
+    assert!(!checkout.join("crates/a1").exists());
+}
+
+#[cargo_test]
+fn falls_back_to_full_checkout_for_root_package() {
+    let repo = git::new("monorepo", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("root", "0.1.0"))
+            .file("src/lib.rs", "")
+            .file("nested/Cargo.toml", &basic_manifest("nested", "0.1.0"))
+            .file("nested/src/lib.rs", "")
+    });
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    root = {{ git = "{}" }}
+                "#,
+                repo.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zgit=sparse-checkout")
+        .masquerade_as_nightly_cargo(&["git"])
+        .run();
+
+    let checkouts = sparse_checkouts();
+    assert_eq!(checkouts.len(), 1);
+    let checkout = &checkouts[0];
+    assert!(!checkout.to_str().unwrap().ends_with("-sparse"));
+    assert!(checkout.join("nested/src/lib.rs").is_file());
+    assert!(!checkout.join(".cargo-sparse").exists());
+}
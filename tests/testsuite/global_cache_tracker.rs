@@ -1541,6 +1541,7 @@ fn clean_max_git_age() {
 [UPDATING] git repository `[ROOTURL]/git_a`
 [LOCKING] 1 package to latest compatible version
 [UPDATING] git_a v1.0.0 ([ROOTURL]/git_a#[..]) -> #[..]
+             [..] test
 
 "#]])
         .run();
@@ -2888,3 +2888,119 @@ fn dry_run_remove_orphan() {
     // Ensure server is still installed after the dry run
     assert_has_installed_exe(paths::cargo_home(), "server");
 }
+
+#[cargo_test]
+fn debug_symbols_requires_nightly() {
+    pkg("foo", "0.0.1");
+
+    cargo_process("install --debug-symbols foo")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--debug-symbols` flag is unstable, pass `-Z debug-symbols` to enable it
+
+"#]])
+        .run();
+    assert_has_not_installed_exe(paths::cargo_home(), "foo");
+}
+
+#[cargo_test]
+#[cfg(target_os = "linux")]
+fn debug_symbols_dwp() {
+    let p = project()
+        .file("src/main.rs", "fn main() {}")
+        .file("src/bin/foo-bar.rs", "fn main() {}")
+        .build();
+
+    p.cargo("install --path . --debug-symbols -Zdebug-symbols")
+        .masquerade_as_nightly_cargo(&["debug-symbols"])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .enable_split_debuginfo_packed()
+        .with_stderr_data(str![[r#"
+[INSTALLING] foo v0.0.1 ([ROOT]/foo)
+[COMPILING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `release` profile [optimized + debuginfo] target(s) in [ELAPSED]s
+[INSTALLING] [ROOT]/home/.cargo/bin/foo
+[INSTALLING] [ROOT]/home/.cargo/bin/foo-bar
+[INSTALLING] [ROOT]/home/.cargo/bin/foo-bar.dwp
+[INSTALLING] [ROOT]/home/.cargo/bin/foo.dwp
+[INSTALLED] package `foo v0.0.1 ([ROOT]/foo)` (executables `foo`, `foo-bar`)
+[WARNING] be sure to add `[ROOT]/home/.cargo/bin` to your PATH to be able to run the installed binaries
+
+"#]])
+        .run();
+    assert_has_installed_exe(paths::cargo_home(), "foo");
+    assert!(paths::cargo_home().join("bin/foo.dwp").is_file());
+    assert!(paths::cargo_home().join("bin/foo-bar.dwp").is_file());
+
+    // Without the flag, only the binaries are installed.
+    p.cargo("install --path .")
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .enable_split_debuginfo_packed()
+        .with_stderr_data(str![[r#"
+[INSTALLING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `release` profile [optimized + debuginfo] target(s) in [ELAPSED]s
+[REPLACING] [ROOT]/home/.cargo/bin/foo
+[REPLACING] [ROOT]/home/.cargo/bin/foo-bar
+[REMOVING] debuginfo `[ROOT]/home/.cargo/bin/foo-bar.dwp` from previous version foo v0.0.1 ([ROOT]/foo)
+[REMOVING] debuginfo `[ROOT]/home/.cargo/bin/foo.dwp` from previous version foo v0.0.1 ([ROOT]/foo)
+[REPLACED] package `foo v0.0.1 ([ROOT]/foo)` with `foo v0.0.1 ([ROOT]/foo)` (executables `foo`, `foo-bar`)
+[WARNING] be sure to add `[ROOT]/home/.cargo/bin` to your PATH to be able to run the installed binaries
+
+"#]])
+        .run();
+    assert!(!paths::cargo_home().join("bin/foo.dwp").exists());
+
+    // Installed debuginfo is tracked, so uninstalling removes it.
+    p.cargo("install --path . --debug-symbols -Zdebug-symbols")
+        .masquerade_as_nightly_cargo(&["debug-symbols"])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .enable_split_debuginfo_packed()
+        .run();
+    assert!(paths::cargo_home().join("bin/foo.dwp").is_file());
+    cargo_process("uninstall foo")
+        .with_stderr_data(str![[r#"
+[REMOVING] [ROOT]/home/.cargo/bin/foo
+[REMOVING] [ROOT]/home/.cargo/bin/foo-bar
+[REMOVING] [ROOT]/home/.cargo/bin/foo-bar.dwp (debuginfo)
+[REMOVING] [ROOT]/home/.cargo/bin/foo.dwp (debuginfo)
+
+"#]])
+        .run();
+    assert_has_not_installed_exe(paths::cargo_home(), "foo");
+    assert!(!paths::cargo_home().join("bin/foo.dwp").exists());
+    assert!(!paths::cargo_home().join("bin/foo-bar.dwp").exists());
+}
+
+#[cargo_test]
+#[cfg(target_os = "linux")]
+fn debug_symbols_dwp_already_exists() {
+    let p = project().file("src/main.rs", "fn main() {}").build();
+    let bin = paths::cargo_home().join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    fs::write(bin.join("foo.dwp"), "not ours").unwrap();
+
+    p.cargo("install --path . --debug-symbols -Zdebug-symbols")
+        .masquerade_as_nightly_cargo(&["debug-symbols"])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .enable_split_debuginfo_packed()
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[INSTALLING] foo v0.0.1 ([ROOT]/foo)
+[COMPILING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `release` profile [optimized + debuginfo] target(s) in [ELAPSED]s
+[ERROR] debuginfo `foo.dwp` already exists in destination
+Add --force to overwrite
+
+"#]])
+        .run();
+    assert_has_not_installed_exe(paths::cargo_home(), "foo");
+    assert_eq!(fs::read_to_string(bin.join("foo.dwp")).unwrap(), "not ours");
+
+    p.cargo("install --path . --debug-symbols -Zdebug-symbols --force")
+        .masquerade_as_nightly_cargo(&["debug-symbols"])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .enable_split_debuginfo_packed()
+        .run();
+    assert_has_installed_exe(paths::cargo_home(), "foo");
+    assert_ne!(fs::read(bin.join("foo.dwp")).unwrap(), b"not ours");
+}
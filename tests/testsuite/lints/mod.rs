@@ -6,6 +6,8 @@ use cargo_test_support::str;
 mod error;
 mod inherited;
 mod unknown_lints;
+mod unused_dependencies;
+mod unused_features;
 mod warning;
 
 #[cargo_test]
@@ -0,0 +1,140 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::prelude::*;
+use cargo_test_support::str;
+use cargo_test_support::{basic_manifest, project, Project};
+
+fn unused_project() -> Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+a = { path = "a" }
+b = { path = "b" }
+
+[dev-dependencies]
+c = { path = "c" }
+
+[lints.cargo]
+unused_dependencies = "warn"
+"#,
+        )
+        .file("src/lib.rs", "pub fn foo() { a::a() }")
+        .file("tests/t.rs", "#[test] fn t() { c::c() }")
+        .file("a/Cargo.toml", &basic_manifest("a", "0.0.1"))
+        .file("a/src/lib.rs", "pub fn a() {}")
+        .file("b/Cargo.toml", &basic_manifest("b", "0.0.1"))
+        .file("b/src/lib.rs", "pub fn b() {}")
+        .file("c/Cargo.toml", &basic_manifest("c", "0.0.1"))
+        .file("c/src/lib.rs", "pub fn c() {}")
+        .build()
+}
+
+#[cargo_test]
+fn all_targets() {
+    let p = unused_project();
+
+    // The tests were not checked, so they could still use `b`.
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 3 packages to latest compatible versions
+[CHECKING] a v0.0.1 ([ROOT]/foo/a)
+[CHECKING] b v0.0.1 ([ROOT]/foo/b)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    p.cargo("check -Zcargo-lints --all-targets")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] c v0.0.1 ([ROOT]/foo/c)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[WARNING] unused dependency: `b`
+ --> Cargo.toml:9:1
+  |
+9 | b = { path = "b" }
+  | ------------------
+  |
+  = [NOTE] `cargo::unused_dependencies` is set to `warn` in `[lints]`
+  = [HELP] remove the dependency, or run `cargo fix` to remove it
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    // Fresh units report it again.
+    p.cargo("check -Zcargo-lints --all-targets")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused dependency: `b`
+ --> Cargo.toml:9:1
+  |
+9 | b = { path = "b" }
+  | ------------------
+  |
+  = [NOTE] `cargo::unused_dependencies` is set to `warn` in `[lints]`
+  = [HELP] remove the dependency, or run `cargo fix` to remove it
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix_removes_dependency() {
+    let p = unused_project();
+
+    p.cargo("fix -Zcargo-lints --all-targets --allow-no-vcs")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[LOCKING] 3 packages to latest compatible versions
+[CHECKING] a v0.0.1 ([ROOT]/foo/a)
+[CHECKING] b v0.0.1 ([ROOT]/foo/b)
+[CHECKING] c v0.0.1 ([ROOT]/foo/c)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[WARNING] unused dependency: `b`
+ --> Cargo.toml:9:1
+  |
+9 | b = { path = "b" }
+  | ------------------
+  |
+  = [NOTE] `cargo::unused_dependencies` is set to `warn` in `[lints]`
+  = [HELP] remove the dependency, or run `cargo fix` to remove it
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[REMOVING] unused b from dependencies of foo
+
+"#]]
+            .unordered(),
+        )
+        .run();
+
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+a = { path = "a" }
+
+[dev-dependencies]
+c = { path = "c" }
+
+[lints.cargo]
+unused_dependencies = "warn"
+
+"#]],
+    );
+}
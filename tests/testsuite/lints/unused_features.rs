@@ -0,0 +1,214 @@
+use cargo_test_support::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn workspace() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo", "bar"]
+resolver = "2"
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+default = ["std"]
+std = []
+used = []
+unread = []
+never = []
+group = ["never"]
+cli = []
+docs = []
+
+[[bin]]
+name = "cli"
+path = "src/main.rs"
+required-features = ["cli"]
+
+[package.metadata.docs.rs]
+features = ["docs"]
+
+[lints.cargo]
+unused_features = "warn"
+"#,
+        )
+        .file(
+            "foo/src/lib.rs",
+            r#"
+#[cfg(feature = "std")]
+pub fn s() {}
+#[cfg(feature="used")]
+pub fn u() {}
+#[cfg(feature = "docs")]
+pub fn d() {}
+"#,
+        )
+        .file("foo/src/main.rs", "fn main() {}")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+
+[dependencies]
+foo = { path = "../foo", features = ["used", "unread"] }
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.0.1 ([ROOT]/foo/foo)
+[CHECKING] bar v0.0.1 ([ROOT]/foo/bar)
+[WARNING] unused feature: `group`
+  --> foo/Cargo.toml:13:1
+   |
+13 | group = ["never"]
+   | ----------------- it is not enabled by any workspace member or target
+   |
+   = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+   = [HELP] remove the feature from `[features]`
+[WARNING] unused feature: `never`
+  --> foo/Cargo.toml:12:1
+   |
+12 | never = []
+   | ---------- it is not enabled by any workspace member or target
+   |
+   = [HELP] remove the feature from `[features]`
+[WARNING] unused feature: `unread`
+  --> foo/Cargo.toml:11:1
+   |
+11 | unread = []
+   | ----------- it is not read by any `cfg(feature = "...")`
+   |
+   = [HELP] remove the feature from `[features]`
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cli_features_and_unknown_cfg() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+
+[features]
+extra = []
+
+[lints.cargo]
+unused_features = "warn"
+"#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+#[cfg(feature = "extra")]
+pub fn e() {}
+// #[cfg(feature = "commented")]
+#[cfg(feature = "missing")]
+pub fn m() {}
+"#,
+        )
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[WARNING] unexpected `cfg` condition value: `missing`
+ --> src/lib.rs:5:7
+  |
+5 | #[cfg(feature = "missing")]
+  |       ^^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] expected values for `feature` are: `extra`
+  = [HELP] consider adding `missing` as a feature in `Cargo.toml`
+  = [NOTE] see <https://doc.rust-lang.org/nightly/rustc/check-cfg/cargo-specifics.html> for more information about checking conditional configuration
+  = [NOTE] `#[warn(unexpected_cfgs)]` on by default
+
+[WARNING] `foo` (lib) generated 1 warning
+[WARNING] unused feature: `extra`
+ --> Cargo.toml:8:1
+  |
+8 | extra = []
+  | ---------- it is not enabled by any workspace member or target
+  |
+  = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+  = [HELP] remove the feature from `[features]`
+[WARNING] `cfg(feature = "missing")` checks a feature that `foo` does not declare
+ = [HELP] add the feature to `[features]`, or remove the check
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    p.cargo("check -Zcargo-lints --features extra")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[WARNING] unexpected `cfg` condition value: `missing`
+ --> src/lib.rs:5:7
+  |
+5 | #[cfg(feature = "missing")]
+  |       ^^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] expected values for `feature` are: `extra`
+  = [HELP] consider adding `missing` as a feature in `Cargo.toml`
+  = [NOTE] see <https://doc.rust-lang.org/nightly/rustc/check-cfg/cargo-specifics.html> for more information about checking conditional configuration
+  = [NOTE] `#[warn(unexpected_cfgs)]` on by default
+
+[WARNING] `foo` (lib) generated 1 warning
+[WARNING] `cfg(feature = "missing")` checks a feature that `foo` does not declare
+ = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+ = [HELP] add the feature to `[features]`, or remove the check
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    // Fresh units report from the features recorded by the previous build.
+    p.cargo("check -Zcargo-lints --features extra")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unexpected `cfg` condition value: `missing`
+ --> src/lib.rs:5:7
+  |
+5 | #[cfg(feature = "missing")]
+  |       ^^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] expected values for `feature` are: `extra`
+  = [HELP] consider adding `missing` as a feature in `Cargo.toml`
+  = [NOTE] see <https://doc.rust-lang.org/nightly/rustc/check-cfg/cargo-specifics.html> for more information about checking conditional configuration
+  = [NOTE] `#[warn(unexpected_cfgs)]` on by default
+
+[WARNING] `foo` (lib) generated 1 warning
+[WARNING] `cfg(feature = "missing")` checks a feature that `foo` does not declare
+ = [NOTE] `cargo::unused_features` is set to `warn` in `[lints]`
+ = [HELP] add the feature to `[features]`, or remove the check
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -8,61 +8,15 @@ use cargo_test_support::compare::assert_e2e;
 use cargo_test_support::install::assert_has_installed_exe;
 use cargo_test_support::registry::{Package, RegistryBuilder};
 use cargo_test_support::{
-    basic_bin_manifest, cargo_process, cargo_test, paths, project, symlink_supported,
+    basic_bin_manifest, cargo_process, cargo_test, git, paths, project, symlink_supported,
     ProjectBuilder,
 };
-///////////////////////////////
-//// Unstable feature tests start
-///////////////////////////////
-
-#[cargo_test]
-fn must_have_unstable_options() {
-    let lockfile_path = "mylockfile/is/burried/Cargo.lock";
-    let p = make_project().build();
-
-    p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("--lockfile-path")
-        .arg(lockfile_path)
-        .with_stderr_data(str![[
-            r#"[ERROR] the `--lockfile-path` flag is unstable, pass `-Z unstable-options` to enable it
-See https://github.com/rust-lang/cargo/issues/14421 for more information about the `--lockfile-path` flag.
-
-"#]])
-        .with_status(101)
-        .run();
-}
-
-#[cargo_test]
-fn must_be_nightly() {
-    let lockfile_path = "mylockfile/is/burried/Cargo.lock";
-    let p = make_project().build();
-
-    p.cargo("generate-lockfile")
-        .arg("-Zunstable-options")
-        .arg("--lockfile-path")
-        .arg(lockfile_path)
-        .with_stderr_data(str![[
-            r#"[ERROR] the `-Z` flag is only accepted on the nightly channel of Cargo, but this is the `stable` channel
-See https://doc.rust-lang.org/book/appendix-07-nightly-rust.html for more information about Rust release channels.
-
-"#]])
-        .with_status(101)
-        .run();
-}
-
-///////////////////////////////
-//// Unstable feature tests end
-///////////////////////////////
-
 #[cargo_test]
 fn basic_lockfile_created() {
     let lockfile_path = "mylockfile/is/burried/Cargo.lock";
     let p = make_project().build();
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .run();
@@ -76,8 +30,6 @@ fn basic_lockfile_read() {
     let p = make_project().file(lockfile_path, VALID_LOCKFILE).build();
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .run();
@@ -94,8 +46,6 @@ fn basic_lockfile_override() {
         .build();
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .run();
@@ -123,8 +73,6 @@ fn symlink_in_path() {
     assert!(p.root().join(src).is_dir());
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path.as_str())
         .run();
@@ -151,8 +99,6 @@ fn symlink_lockfile() {
     assert!(p.root().join(src).is_file());
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .run();
@@ -174,8 +120,6 @@ fn broken_symlink() {
     assert!(!p.root().join(src).is_dir());
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .with_status(101)
@@ -206,8 +150,6 @@ fn loop_symlink() {
     assert!(!p.root().join(src).is_dir());
 
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .with_status(101)
@@ -237,8 +179,6 @@ fn add_lockfile_override() {
         .file("Cargo.lock", "This is an invalid lock file!")
         .build();
     p.cargo("add")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .arg("--path")
@@ -255,8 +195,6 @@ fn clean_lockfile_override() {
         .file("Cargo.lock", "This is an invalid lock file!")
         .build();
     p.cargo("clean")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .arg("--package")
@@ -273,8 +211,6 @@ fn fix_lockfile_override() {
         .file("Cargo.lock", "This is an invalid lock file!")
         .build();
     p.cargo("fix")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .arg("--package")
@@ -292,8 +228,6 @@ fn publish_lockfile_read() {
     let registry = RegistryBuilder::new().http_api().http_index().build();
 
     p.cargo("publish")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .replace_crates_io(registry.index_url())
@@ -334,8 +268,6 @@ fn remove_lockfile_override() {
         .file("Cargo.lock", "This is an invalid lock file!")
         .build();
     p.cargo("remove")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .arg("test_bar")
@@ -371,8 +303,6 @@ bar = "0.1.0"
 
     Package::new("bar", "0.1.0").publish();
     p.cargo("generate-lockfile")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .run();
@@ -384,8 +314,6 @@ bar = "0.1.0"
 
     Package::new("bar", "0.1.1").publish();
     p.cargo("package")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
         .arg("--lockfile-path")
         .arg(lockfile_path)
         .run();
@@ -401,6 +329,52 @@ bar = "0.1.0"
     assert_e2e().eq(contents, lockfile_original);
 }
 
+#[cargo_test]
+fn package_excludes_named_lockfiles() {
+    let p = make_project()
+        .file("Cargo.lock", VALID_LOCKFILE)
+        .file("Cargo.msrv.lock", VALID_LOCKFILE)
+        .file("Cargo.minimal.lock", VALID_LOCKFILE)
+        .build();
+
+    p.cargo("package --list")
+        .with_stdout_data(str![[r#"
+Cargo.lock
+Cargo.toml
+Cargo.toml.orig
+src/main.rs
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn package_excludes_named_lockfiles_in_git() {
+    let p = git::new("foo", |p| {
+        p.file("Cargo.toml", &basic_bin_manifest("test_foo"))
+            .file("src/main.rs", "fn main() {}")
+            .file("Cargo.msrv.lock", VALID_LOCKFILE)
+    });
+    // Untracked and ignored named lock files don't make the package dirty.
+    p.change_file(".gitignore", "Cargo.lock\nCargo.minimal.lock\n");
+    git::add(&git2::Repository::open(p.root()).unwrap());
+    git::commit(&git2::Repository::open(p.root()).unwrap());
+    p.change_file("Cargo.minimal.lock", VALID_LOCKFILE);
+    p.change_file("Cargo.beta.lock", VALID_LOCKFILE);
+
+    p.cargo("package --list")
+        .with_stdout_data(str![[r#"
+.cargo_vcs_info.json
+.gitignore
+Cargo.lock
+Cargo.toml
+Cargo.toml.orig
+src/main.rs
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn install_respects_lock_file_path() {
     // `cargo install` will imply --locked when lockfile path is provided
@@ -462,9 +436,7 @@ dependencies = [
 "#,
         )
         .build();
-    cargo_process("install foo -Zunstable-options --lockfile-path foo/Cargo.lock")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .run();
+    cargo_process("install foo --lockfile-path foo/Cargo.lock").run();
 
     assert!(paths::root().join("foo/Cargo.lock").is_file());
     assert_has_installed_exe(paths::cargo_home(), "foo");
@@ -483,8 +455,7 @@ fn install_lock_file_path_must_present() {
         )
         .publish();
 
-    cargo_process("install foo -Zunstable-options --lockfile-path lockfile_dir/Cargo.lock")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
+    cargo_process("install foo --lockfile-path lockfile_dir/Cargo.lock")
         .with_stderr_data(str![[r#"
 ...
 [ERROR] no Cargo.lock file found in the requested path [ROOT]/lockfile_dir/Cargo.lock
@@ -494,6 +465,52 @@ fn install_lock_file_path_must_present() {
         .run();
 }
 
+#[cargo_test]
+fn install_lock_file_path_checks_yanked() {
+    Package::new("bar", "0.1.0").yanked(true).publish();
+    Package::new("foo", "0.1.0")
+        .dep("bar", "0.1")
+        .file("src/main.rs", "extern crate bar; fn main() {}")
+        .publish();
+
+    project()
+        .file(
+            "Cargo.lock",
+            r#"
+[[package]]
+name = "bar"
+version = "0.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "foo"
+version = "0.1.0"
+dependencies = [
+ "bar 0.1.0 (registry+https://github.com/rust-lang/crates.io-index)",
+]
+"#,
+        )
+        .build();
+    cargo_process("install foo --lockfile-path foo/Cargo.lock")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[DOWNLOADING] crates ...
+[DOWNLOADED] foo v0.1.0 (registry `dummy-registry`)
+[INSTALLING] foo v0.1.0
+[WARNING] package `bar v0.1.0` in Cargo.lock is yanked in registry `crates-io`, consider running without --locked
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.1.0 (registry `dummy-registry`)
+[COMPILING] bar v0.1.0
+[COMPILING] foo v0.1.0
+[FINISHED] `release` profile [optimized] target(s) in [ELAPSED]s
+[INSTALLING] [ROOT]/home/.cargo/bin/foo[EXE]
+[INSTALLED] package `foo v0.1.0` (executable `foo[EXE]`)
+[WARNING] be sure to add `[ROOT]/home/.cargo/bin` to your PATH to be able to run the installed binaries
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn run_embed() {
     let lockfile_path = "mylockfile/Cargo.lock";
@@ -504,8 +521,7 @@ fn run_embed() {
         .build();
 
     p.cargo("run")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
+        .masquerade_as_nightly_cargo(&["script"])
         .arg("-Zscript")
         .arg("--lockfile-path")
         .arg(lockfile_path)
@@ -516,8 +532,7 @@ fn run_embed() {
     assert!(p.root().join(lockfile_path).is_file());
 
     p.cargo("run")
-        .masquerade_as_nightly_cargo(&["lockfile-path"])
-        .arg("-Zunstable-options")
+        .masquerade_as_nightly_cargo(&["script"])
         .arg("-Zscript")
         .arg("--lockfile-path")
         .arg(invalid_lockfile)
@@ -534,6 +549,132 @@ fn run_embed() {
         .run();
 }
 
+#[cargo_test]
+fn must_be_lockfile_name() {
+    let p = make_project().build();
+
+    p.cargo("generate-lockfile --lockfile-path mylockfile/deps.lock")
+        .with_stderr_data(str![[r#"
+[ERROR] the lockfile-path must be a path to a Cargo.lock or Cargo.<name>.lock file
+
+"#]])
+        .with_status(101)
+        .run();
+
+    p.cargo("generate-lockfile --lockfile-path mylockfile/Cargo..lock")
+        .with_stderr_data(str![[r#"
+[ERROR] the lockfile-path must be a path to a Cargo.lock or Cargo.<name>.lock file
+
+"#]])
+        .with_status(101)
+        .run();
+}
+
+#[cargo_test]
+fn named_lockfiles() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile --lockfile-path Cargo.msrv.lock")
+        .run();
+    assert!(!p.root().join("Cargo.lock").exists());
+    assert!(p.root().join("Cargo.msrv.lock").is_file());
+
+    // Each named lockfile is tracked independently.
+    Package::new("bar", "0.1.1").publish();
+    p.cargo("generate-lockfile").run();
+    p.cargo("check --locked --lockfile-path Cargo.msrv.lock")
+        .with_stderr_data(str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.1.0 (registry `dummy-registry`)
+[CHECKING] bar v0.1.0
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert!(p
+        .read_file("Cargo.msrv.lock")
+        .contains("version = \"0.1.0\""));
+    assert!(p.read_lockfile().contains("version = \"0.1.1\""));
+}
+
+#[cargo_test]
+fn config_lockfile_path() {
+    let lockfile_path = "mylockfile/Cargo.lock";
+    let p = make_project()
+        .file("Cargo.lock", "This is an invalid lock file!")
+        .file(
+            ".cargo/config.toml",
+            &format!(
+                r#"
+                    [resolver]
+                    lockfile-path = "{lockfile_path}"
+                "#
+            ),
+        )
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    assert!(p.root().join(lockfile_path).is_file());
+
+    // The CLI flag overrides the config.
+    p.cargo("generate-lockfile --lockfile-path other/Cargo.lock")
+        .run();
+    assert!(p.root().join("other/Cargo.lock").is_file());
+
+    // Relative paths from the environment are relative to the cwd.
+    p.cargo("generate-lockfile")
+        .cwd(p.root().join("src"))
+        .env("CARGO_RESOLVER_LOCKFILE_PATH", "Cargo.env.lock")
+        .run();
+    assert!(p.root().join("src/Cargo.env.lock").is_file());
+
+    p.cargo("generate-lockfile")
+        .env("CARGO_RESOLVER_LOCKFILE_PATH", "Cargo.toml")
+        .with_stderr_data(str![[r#"
+[ERROR] failed to load config value `resolver.lockfile-path`
+
+Caused by:
+  the lockfile-path must be a path to a Cargo.lock or Cargo.<name>.lock file
+
+"#]])
+        .with_status(101)
+        .run();
+}
+
+#[cargo_test]
+fn install_ignores_config_lockfile_path() {
+    Package::new("foo", "0.1.0")
+        .file("src/main.rs", "fn main() {}")
+        .publish();
+
+    cargo_process("install foo")
+        .env("CARGO_RESOLVER_LOCKFILE_PATH", "lockfile_dir/Cargo.lock")
+        .with_stderr_data(str![[r#"
+[WARNING] `cargo install` ignores the `resolver.lockfile-path` config, pass `--lockfile-path` to install with that lockfile
+[UPDATING] `dummy-registry` index
+...
+"#]])
+        .run();
+    assert!(!paths::root().join("lockfile_dir/Cargo.lock").exists());
+    assert_has_installed_exe(paths::cargo_home(), "foo");
+}
+
 const VALID_LOCKFILE: &str = r#"# Test lockfile
 version = 4
 
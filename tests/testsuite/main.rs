@@ -40,6 +40,7 @@ mod cargo_install;
 mod cargo_locate_project;
 mod cargo_login;
 mod cargo_logout;
+mod cargo_merge_lockfile;
 mod cargo_metadata;
 mod cargo_new;
 mod cargo_owner;
@@ -85,6 +86,7 @@ mod doc;
 mod docscrape;
 mod edition;
 mod error;
+mod feature_unification;
 mod features;
 mod features2;
 mod features_namespaced;
@@ -99,6 +101,7 @@ mod git;
 mod git_auth;
 mod git_gc;
 mod git_shallow;
+mod git_sparse;
 mod glob_targets;
 mod global_cache_tracker;
 mod help;
@@ -119,6 +122,7 @@ mod logout;
 mod lto;
 mod member_discovery;
 mod member_errors;
+mod merge_lockfile;
 mod message_format;
 mod messages;
 mod metabuild;
@@ -183,6 +187,7 @@ mod verify_project;
 mod version;
 mod warn_on_failure;
 mod weak_dep_features;
+mod workspace_constraints;
 mod workspaces;
 mod yank;
 
@@ -0,0 +1,193 @@
+//! Tests for the `cargo merge-lockfile` command.
+
+use std::fs;
+
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{project, str, Project};
+
+/// Sets up `foo` depending on `a` and `b`, with `base.lock` locking both to
+/// 1.0.0.
+fn setup() -> Project {
+    for v in ["1.0.0", "1.0.1", "1.0.2"] {
+        Package::new("a", v).publish();
+    }
+    Package::new("b", "1.0.0").publish();
+    Package::new("b", "1.1.0").publish();
+    Package::new("c", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = "1"
+                b = "1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    p.cargo("update a --precise 1.0.0").run();
+    p.cargo("update b --precise 1.0.0").run();
+    save_lockfile(&p, "base.lock");
+    p
+}
+
+fn save_lockfile(p: &Project, name: &str) {
+    fs::copy(p.root().join("Cargo.lock"), p.root().join(name)).unwrap();
+}
+
+fn restore_lockfile(p: &Project, name: &str) {
+    fs::copy(p.root().join(name), p.root().join("Cargo.lock")).unwrap();
+}
+
+#[cargo_test]
+fn merge_lockfile_requires_nightly() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("merge-lockfile base.lock ours.lock theirs.lock")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `cargo merge-lockfile` command is unstable, pass `-Z merge-lockfile` to enable it
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn merge_independent_changes() {
+    let p = setup();
+
+    // We update `a`.
+    p.cargo("update a --precise 1.0.1").run();
+    save_lockfile(&p, "ours.lock");
+
+    // They update `b` and add `c`.
+    restore_lockfile(&p, "base.lock");
+    p.cargo("update b --precise 1.1.0").run();
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.0.1"
+            edition = "2015"
+
+            [dependencies]
+            a = "1"
+            b = "1"
+            c = "1"
+        "#,
+    );
+    p.cargo("metadata --format-version 1").run();
+    save_lockfile(&p, "theirs.lock");
+
+    p.cargo("-Zmerge-lockfile merge-lockfile base.lock ours.lock theirs.lock")
+        .masquerade_as_nightly_cargo(&["merge-lockfile"])
+        .with_stderr_data(str![[r#"
+[MERGED] ours.lock
+
+"#]])
+        .run();
+
+    let lockfile = fs::read_to_string(p.root().join("ours.lock")).unwrap();
+    assert_e2e().eq(
+        &lockfile,
+        str![[r##"
+# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "a"
+version = "1.0.1"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "[..]"
+
+[[package]]
+name = "b"
+version = "1.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "[..]"
+
+[[package]]
+name = "c"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "[..]"
+
+[[package]]
+name = "foo"
+version = "0.0.1"
+dependencies = [
+ "a",
+ "b",
+ "c",
+]
+
+"##]],
+    );
+}
+
+#[cargo_test]
+fn merge_conflicting_changes() {
+    let p = setup();
+
+    // We update `a` to 1.0.1, they update it to 1.0.2, and both update `b`
+    // the same way.
+    p.cargo("update a --precise 1.0.1").run();
+    p.cargo("update b --precise 1.1.0").run();
+    save_lockfile(&p, "ours.lock");
+    restore_lockfile(&p, "base.lock");
+    p.cargo("update a --precise 1.0.2").run();
+    p.cargo("update b --precise 1.1.0").run();
+    save_lockfile(&p, "theirs.lock");
+
+    p.cargo("-Zmerge-lockfile merge-lockfile base.lock ours.lock theirs.lock")
+        .masquerade_as_nightly_cargo(&["merge-lockfile"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[MERGED] ours.lock, re-resolving `a`
+
+"#]])
+        .run();
+
+    let lockfile = fs::read_to_string(p.root().join("ours.lock")).unwrap();
+    assert_e2e().eq(
+        &lockfile,
+        str![[r##"
+# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "a"
+version = "1.0.2"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "[..]"
+
+[[package]]
+name = "b"
+version = "1.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "[..]"
+
+[[package]]
+name = "foo"
+version = "0.0.1"
+dependencies = [
+ "a",
+ "b",
+]
+
+"##]],
+    );
+}
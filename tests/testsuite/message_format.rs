@@ -1,6 +1,7 @@
 //! Tests for --message-format flag.
 
 use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
 use cargo_test_support::{basic_lib_manifest, basic_manifest, project, str};
 
 #[cargo_test]
@@ -164,3 +165,135 @@ test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out;
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn resolve_error_json() {
+    Package::new("bad", "1.0.0").publish();
+    Package::new("bad", "1.0.1").publish();
+    Package::new("bad", "1.0.2").publish();
+    Package::new("bad", "1.0.3").yanked(true).publish();
+    Package::new("baz", "0.1.1").dep("bad", "=1.0.0").publish();
+    Package::new("baz", "0.1.0").dep("bad", "=1.0.0").publish();
+    Package::new("qux", "0.1.2").dep("bad", ">=1.0.1").publish();
+    Package::new("qux", "0.1.1").dep("bad", ">=1.0.1").publish();
+    Package::new("qux", "0.1.0").dep("bad", ">=1.0.1").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                baz = "0.1.0"
+                qux = "0.1.0"
+            "#,
+        )
+        .file("src/main.rs", "fn main(){}")
+        .build();
+
+    p.cargo("check --message-format json")
+        .with_status(101)
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "candidates": [
+      {
+        "rust_version": null,
+        "version": "1.0.2"
+      },
+      {
+        "rust_version": null,
+        "version": "1.0.1"
+      }
+    ],
+    "conflicts": [
+      {
+        "detail": null,
+        "package_id": "registry+https://github.com/rust-lang/crates.io-index#bad@1.0.0",
+        "reason": "semver",
+        "required_by": {
+          "package_id": "registry+https://github.com/rust-lang/crates.io-index#baz@0.1.0",
+          "requirement": "=1.0.0"
+        }
+      }
+    ],
+    "dependency": {
+      "name": "bad",
+      "requirement": ">=1.0.1",
+      "required_by": "registry+https://github.com/rust-lang/crates.io-index#qux@0.1.0",
+      "source": "registry+https://github.com/rust-lang/crates.io-index"
+    },
+    "filtered": {
+      "incompatible_rust_version": [],
+      "offline": false,
+      "yanked": [
+        "1.0.3"
+      ]
+    },
+    "message": "{...}",
+    "package_path": [
+      "registry+https://github.com/rust-lang/crates.io-index#qux@0.1.0",
+      "path+[ROOTURL]/foo#0.0.1"
+    ],
+    "reason": "resolve-error"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for `bad`.
+...
+"#]])
+        .run();
+
+    // Other commands with JSON output report it as well.
+    p.cargo("metadata --format-version 1")
+        .with_status(101)
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "candidates": "{...}",
+    "conflicts": "{...}",
+    "dependency": "{...}",
+    "filtered": {
+      "incompatible_rust_version": [],
+      "offline": false,
+      "yanked": [
+        "1.0.3"
+      ]
+    },
+    "message": "{...}",
+    "package_path": "{...}",
+    "reason": "resolve-error"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        )
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for `bad`.
+...
+"#]])
+        .run();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stdout_data("")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for `bad`.
+...
+"#]])
+        .run();
+}
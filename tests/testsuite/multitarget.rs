@@ -268,3 +268,204 @@ fn works_with_env() {
 
     assert!(p.target_bin(t, "foo").is_file());
 }
+
+#[cargo_test]
+fn summary_keeps_going() {
+    if cross_compile::disabled() {
+        return;
+    }
+    let t1 = cross_compile::alternate();
+    let t2 = rustc_host();
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "1.0.0"))
+        .file(
+            "src/main.rs",
+            &format!(
+                r#"
+                    #[cfg(target_arch = "{}")]
+                    compile_error!("unsupported");
+                    fn main() {{}}
+                "#,
+                cross_compile::alternate_arch()
+            ),
+        )
+        .build();
+
+    p.cargo("build -Zmultitarget-summary")
+        .masquerade_as_nightly_cargo(&["multitarget-summary"])
+        .arg("--target")
+        .arg(&t1)
+        .arg("--target")
+        .arg(&t2)
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[SUMMARY] Successfully built for [HOST_TARGET]! Failed to build for [ALT_TARGET] (see error(s) above).
+
+"#]])
+        .run();
+
+    assert!(!p.target_bin(t1, "foo").is_file());
+    assert!(p.target_bin(t2, "foo").is_file());
+}
+
+#[cargo_test]
+fn summary_artifact_dir() {
+    if cross_compile::disabled() {
+        return;
+    }
+    let t1 = cross_compile::alternate();
+    let t2 = rustc_host();
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "1.0.0"))
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build -Zmultitarget-summary -Zunstable-options --artifact-dir out")
+        .masquerade_as_nightly_cargo(&["multitarget-summary", "artifact-dir"])
+        .arg("--target")
+        .arg(&t1)
+        .arg("--target")
+        .arg(&t2)
+        .with_stderr_data(
+            str![[r#"
+[COMPILING] foo v1.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[SUMMARY] Successfully built for [..], [..]!
+
+"#]]
+            .unordered(),
+        )
+        .run();
+
+    let bin = format!("foo{}", std::env::consts::EXE_SUFFIX);
+    assert!(p.root().join("out").join(t1).join(&bin).is_file());
+    assert!(p.root().join("out").join(t2).join(&bin).is_file());
+    assert!(!p.root().join("out").join(&bin).exists());
+}
+
+#[cargo_test]
+fn summary_json() {
+    if cross_compile::disabled() {
+        return;
+    }
+    let t1 = cross_compile::alternate();
+    let t2 = rustc_host();
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "1.0.0"))
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build -Zmultitarget-summary --message-format=json")
+        .masquerade_as_nightly_cargo(&["multitarget-summary"])
+        .arg("--target")
+        .arg(&t1)
+        .arg("--target")
+        .arg(&t2)
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "reason": "compiler-artifact",
+    "target_triple": "[ALT_TARGET]",
+    "...": "{...}"
+  },
+  {
+    "reason": "compiler-artifact",
+    "target_triple": "[HOST_TARGET]",
+    "...": "{...}"
+  },
+  {
+    "reason": "build-finished",
+    "success": true,
+    "targets": [
+      {
+        "success": true,
+        "target": "[..]"
+      },
+      {
+        "success": true,
+        "target": "[..]"
+      }
+    ]
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines()
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn summary_timings_json() {
+    if cross_compile::disabled() {
+        return;
+    }
+    let t1 = cross_compile::alternate();
+    let t2 = rustc_host();
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "1.0.0"))
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build -Zmultitarget-summary -Zunstable-options --timings=json")
+        .masquerade_as_nightly_cargo(&["multitarget-summary", "timings=json"])
+        .arg("--target")
+        .arg(&t1)
+        .arg("--target")
+        .arg(&t2)
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "reason": "timing-info",
+    "target_triple": "[ALT_TARGET]",
+    "...": "{...}"
+  },
+  {
+    "reason": "timing-info",
+    "target_triple": "[HOST_TARGET]",
+    "...": "{...}"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines()
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn summary_test_grouped_per_target() {
+    if cross_compile::disabled() {
+        return;
+    }
+    let t1 = cross_compile::alternate();
+    let t2 = rustc_host();
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "1.0.0"))
+        .file("src/lib.rs", "")
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("test -Zmultitarget-summary --no-run")
+        .masquerade_as_nightly_cargo(&["multitarget-summary"])
+        .arg("--target")
+        .arg(&t1)
+        .arg("--target")
+        .arg(&t2)
+        .with_stderr_data(str![[r#"
+[COMPILING] foo v1.0.0 ([ROOT]/foo)
+[FINISHED] `test` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[EXECUTABLE] unittests src/lib.rs ([ROOT]/foo/target/[ALT_TARGET]/debug/deps/foo-[HASH][EXE])
+[EXECUTABLE] unittests src/main.rs ([ROOT]/foo/target/[ALT_TARGET]/debug/deps/foo-[HASH][EXE])
+[EXECUTABLE] unittests src/lib.rs ([ROOT]/foo/target/[HOST_TARGET]/debug/deps/foo-[HASH][EXE])
+[EXECUTABLE] unittests src/main.rs ([ROOT]/foo/target/[HOST_TARGET]/debug/deps/foo-[HASH][EXE])
+[SUMMARY] Successfully built for [..], [..]!
+
+"#]])
+        .run();
+}
@@ -3,6 +3,7 @@
 use std::fmt::Write;
 use std::fs::{self, File};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -11,7 +12,8 @@ use cargo_test_support::cargo_process;
 use cargo_test_support::paths;
 use cargo_test_support::prelude::*;
 use cargo_test_support::registry::{
-    self, registry_path, Dependency, Package, RegistryBuilder, Response, TestRegistry,
+    self, registry_path, sign_index_file, Dependency, Package, RegistryBuilder, Response,
+    TestRegistry,
 };
 use cargo_test_support::{basic_manifest, project, str};
 use cargo_test_support::{git, t};
@@ -3314,6 +3316,178 @@ Caused by:
         .run();
 }
 
+#[cargo_test]
+fn sparse_index_changes() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_changes()
+        .add_responder("/index/3/b/bar", {
+            let requests = requests.clone();
+            move |req, server| {
+                requests.fetch_add(1, SeqCst);
+                server.index(req)
+            }
+        })
+        .build();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+                authors = []
+
+                [dependencies]
+                bar = ">= 0.0.0"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zsparse-index-changes")
+        .masquerade_as_nightly_cargo(&["sparse-index-changes"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+    assert_eq!(requests.load(SeqCst), 1);
+
+    // The change log says `bar` didn't change, so it is not revalidated.
+    p.cargo("update -Zsparse-index-changes")
+        .masquerade_as_nightly_cargo(&["sparse-index-changes"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 0 packages to latest compatible versions
+
+"#]])
+        .run();
+    assert_eq!(requests.load(SeqCst), 1);
+
+    Package::new("bar", "0.0.2").publish();
+
+    p.cargo("update -Zsparse-index-changes")
+        .masquerade_as_nightly_cargo(&["sparse-index-changes"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[UPDATING] bar v0.0.1 -> v0.0.2
+
+"#]])
+        .run();
+    assert_eq!(requests.load(SeqCst), 2);
+
+    // Without the flag, every index file is revalidated.
+    p.cargo("update")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 0 packages to latest compatible versions
+
+"#]])
+        .run();
+    assert_eq!(requests.load(SeqCst), 3);
+}
+
+#[cargo_test]
+fn sparse_index_changes_publish() {
+    // Waiting for a publish updates the index several times in one process.
+    let registry = RegistryBuilder::new()
+        .http_api()
+        .http_index()
+        .index_changes()
+        .delayed_index_update(2)
+        .build();
+
+    Package::new("foo", "0.0.1").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.2"
+                edition = "2015"
+                authors = []
+                license = "MIT"
+                description = "foo"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [publish]
+                timeout = 10
+            "#,
+        )
+        .build();
+
+    p.cargo("publish --no-verify -Zsparse-index-changes -Zpublish-timeout")
+        .masquerade_as_nightly_cargo(&["sparse-index-changes", "publish-timeout"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+[UPDATING] crates.io index
+[WARNING] manifest has no documentation, homepage or repository.
+See https://doc.rust-lang.org/cargo/reference/manifest.html#package-metadata for more info.
+[PACKAGING] foo v0.0.2 ([ROOT]/foo)
+[PACKAGED] 4 files, [FILE_SIZE]B ([FILE_SIZE]B compressed)
+[UPLOADING] foo v0.0.2 ([ROOT]/foo)
+[UPLOADED] foo v0.0.2 to registry `crates-io`
+[NOTE] waiting for `foo v0.0.2` to be available at registry `crates-io`.
+You may press ctrl-c to skip waiting; the crate should be available shortly.
+[PUBLISHED] foo v0.0.2 at registry `crates-io`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn sparse_index_changes_not_found() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_changes()
+        .add_responder("/changes", |req, server| server.not_found(req))
+        .build();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+                authors = []
+
+                [dependencies]
+                bar = ">= 0.0.0"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zsparse-index-changes")
+        .masquerade_as_nightly_cargo(&["sparse-index-changes"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[WARNING] failed to fetch the index change log of `dummy-registry` index, checking every index file for updates
+the index change log was not found
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn sparse_blocking_count() {
     let fail_count = Mutex::new(0);
@@ -4388,3 +4562,409 @@ required by package `foo v0.0.1 ([ROOT]/foo)`
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn registry_mirrors() {
+    let _registry = RegistryBuilder::new().http_index().build();
+    let _mirror = RegistryBuilder::new().alternative().http_index().build();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.0.1"
+                baz = "0.0.1"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    Package::new("bar", "0.0.1").publish();
+    Package::new("baz", "0.0.1").publish();
+
+    // Only `bar` is available from the mirror.
+    let mirrored = registry::alt_dl_path().join("bar/0.0.1/download");
+    fs::create_dir_all(mirrored.parent().unwrap()).unwrap();
+    fs::copy(registry::dl_path().join("bar/0.0.1/download"), &mirrored).unwrap();
+
+    p.cargo("fetch -Zregistry-mirrors")
+        .arg("--config")
+        .arg(r#"source.dummy-registry.mirrors=["alternative"]"#)
+        .masquerade_as_nightly_cargo(&["registry-mirrors"])
+        .with_stderr_data(
+            str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[UPDATING] `alternative` index
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.0.1 (registry `alternative`)
+[WARNING] failed to download `baz v0.0.1 (registry `alternative`)`, trying the next location: failed to get successful HTTP response from `http://127.0.0.1:[..]/dl/baz/0.0.1/download` (127.0.0.1), got 404
+body:
+not found
+[DOWNLOADED] baz v0.0.1 (registry `dummy-registry`)
+
+"#]]
+            .unordered(),
+        )
+        .run();
+
+    // Without the flag, the `mirrors` key is ignored.
+    paths::home().join(".cargo/registry/cache").rm_rf();
+    p.cargo("fetch")
+        .arg("--config")
+        .arg(r#"source.dummy-registry.mirrors=["alternative"]"#)
+        .with_stderr_data(
+            str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.0.1 (registry `dummy-registry`)
+[DOWNLOADED] baz v0.0.1 (registry `dummy-registry`)
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn registry_mirrors_bad_checksum() {
+    let _registry = RegistryBuilder::new().http_index().build();
+    let _mirror = RegistryBuilder::new().alternative().http_index().build();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.0.1"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    Package::new("bar", "0.0.1").publish();
+
+    // The mirror serves different contents than the original registry.
+    let mirrored = registry::alt_dl_path().join("bar/0.0.1/download");
+    fs::create_dir_all(mirrored.parent().unwrap()).unwrap();
+    fs::write(&mirrored, b"not the original crate").unwrap();
+
+    p.cargo("fetch -Zregistry-mirrors")
+        .arg("--config")
+        .arg(r#"source.dummy-registry.mirrors=["alternative"]"#)
+        .masquerade_as_nightly_cargo(&["registry-mirrors"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[UPDATING] `alternative` index
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.0.1 (registry `alternative`)
+[ERROR] failed to download replaced source registry `crates-io`
+
+Caused by:
+  failed to verify the checksum of `bar v0.0.1 (registry `dummy-registry`)`
+
+"#]])
+        .run();
+}
+
+const INDEX_SIGNING_KEY: &str =
+    "k3.secret.fNYVuMvBgOlljt9TDohnaYLblghqaHoQquVZwgR6X12cBFHZLFsaU3q7X3k1Zn36";
+const INDEX_SIGNING_PUBLIC_KEY: &str =
+    "k3.public.AmDwjlyf8jAV3gm5Z7Kz9xAOcsKslt_Vwp5v-emjFzBHLCtcANzTaVEghTNEMj9PkQ";
+
+fn index_signing_project() -> cargo_test_support::Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.0.1"
+            "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .build()
+}
+
+#[cargo_test]
+fn index_signing() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing(INDEX_SIGNING_KEY)
+        .build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_signing_bad_signature() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing(INDEX_SIGNING_KEY)
+        .add_responder("/index/3/b/bar", |req, server| {
+            let mut response = server.index(req);
+            response.body.extend_from_slice(b"\n");
+            response
+        })
+        .build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to get `bar` as a dependency of package `foo v0.0.1 ([ROOT]/foo)`
+
+Caused by:
+  failed to query replaced source registry `crates-io`
+
+Caused by:
+  failed to verify the signature of the index file `3/b/bar` of registry `dummy-registry`
+
+Caused by:
+  the signature is for different contents
+
+"#]])
+        .run();
+
+    // Without the flag, the signature is not checked.
+    p.cargo("generate-lockfile")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_signing_missing_signature() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing(INDEX_SIGNING_KEY)
+        .add_responder("/index/3/b/bar", |req, server| {
+            let mut response = server.index(req);
+            response
+                .headers
+                .retain(|header| !header.starts_with("Cargo-Index-Signature"));
+            response
+        })
+        .build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to get `bar` as a dependency of package `foo v0.0.1 ([ROOT]/foo)`
+
+Caused by:
+  failed to query replaced source registry `crates-io`
+
+Caused by:
+  failed to verify the signature of the index file `3/b/bar` of registry `dummy-registry`
+
+Caused by:
+  the registry sent no signature
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_signing_expired_signature() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing(INDEX_SIGNING_KEY)
+        .add_responder("/index/3/b/bar", |req, server| {
+            let mut response = server.index(req);
+            let expires = time::OffsetDateTime::now_utc() - time::Duration::hours(1);
+            let signature = sign_index_file(INDEX_SIGNING_KEY, "3/b/bar", &response.body, expires);
+            response
+                .headers
+                .retain(|header| !header.starts_with("Cargo-Index-Signature"));
+            response
+                .headers
+                .push(format!("Cargo-Index-Signature: {signature}"));
+            response
+        })
+        .build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to get `bar` as a dependency of package `foo v0.0.1 ([ROOT]/foo)`
+
+Caused by:
+  failed to query replaced source registry `crates-io`
+
+Caused by:
+  failed to verify the signature of the index file `3/b/bar` of registry `dummy-registry`
+
+Caused by:
+  the signature expired at [..]
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_signing_pinned_key() {
+    // The registry doesn't advertise a signing key, nor signs its index.
+    let _registry = RegistryBuilder::new().http_index().build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .arg("--config")
+        .arg(format!(
+            "registries.dummy-registry.signing-key='{INDEX_SIGNING_PUBLIC_KEY}'"
+        ))
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to get `bar` as a dependency of package `foo v0.0.1 ([ROOT]/foo)`
+
+Caused by:
+  failed to query replaced source registry `crates-io`
+
+Caused by:
+  failed to verify the signature of the index file `3/b/bar` of registry `dummy-registry`
+
+Caused by:
+  the registry sent no signature
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_signing_pinned_key_mismatch() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing(INDEX_SIGNING_KEY)
+        .build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .arg("--config")
+        .arg(
+            "registries.dummy-registry.signing-key=\
+             'k3.public.A4CC8NfeQ7QG21O1hXAtClGr-uRL9arY9G4mSGjfsg_nsXW0bJLsS34l3ubvPdy3hw'",
+        )
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to get `bar` as a dependency of package `foo v0.0.1 ([ROOT]/foo)`
+
+Caused by:
+  failed to query replaced source registry `crates-io`
+
+Caused by:
+  registry `dummy-registry` advertises the signing key `k3.public.AmDwjlyf8jAV3gm5Z7Kz9xAOcsKslt_Vwp5v-emjFzBHLCtcANzTaVEghTNEMj9PkQ`, but the signing key `k3.public.A4CC8NfeQ7QG21O1hXAtClGr-uRL9arY9G4mSGjfsg_nsXW0bJLsS34l3ubvPdy3hw` is pinned in the configuration
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn index_signing_tampered_cache() {
+    let _registry = RegistryBuilder::new()
+        .http_index()
+        .index_signing(INDEX_SIGNING_KEY)
+        .build();
+    let p = index_signing_project();
+    Package::new("bar", "0.0.1").publish();
+
+    p.cargo("generate-lockfile -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .run();
+
+    // The cached index file is verified again when used offline.
+    p.cargo("generate-lockfile --offline -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+
+    let cache = glob::glob(
+        &paths::cargo_home()
+            .join("registry/index/*/.cache/3/b/bar")
+            .to_string_lossy(),
+    )
+    .unwrap()
+    .next()
+    .unwrap()
+    .unwrap();
+    let contents = fs::read(&cache).unwrap();
+    let tampered = String::from_utf8(contents)
+        .unwrap()
+        .replace(r#""features":{}"#, r#""features":{"evil":[]}"#);
+    fs::write(&cache, tampered).unwrap();
+
+    p.cargo("generate-lockfile --offline -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to get `bar` as a dependency of package `foo v0.0.1 ([ROOT]/foo)`
+
+Caused by:
+  failed to query replaced source registry `crates-io`
+
+Caused by:
+  failed to verify the signature of the cached index file `3/b/bar` of registry `dummy-registry`
+
+Caused by:
+  the signature is for different contents
+
+"#]])
+        .run();
+
+    // Online, the index file is loaded again and the cache replaced.
+    p.cargo("generate-lockfile -Zindex-signing")
+        .masquerade_as_nightly_cargo(&["index-signing"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+
+"#]])
+        .run();
+}
@@ -515,6 +515,138 @@ higher v0.0.1 ([ROOT]/foo)
         .run();
 }
 
+#[cargo_test]
+fn resolve_with_msrv_per_member() {
+    for ver in ["1.45.0", "1.55.0"] {
+        Package::new("higher-dep", ver)
+            .rust_version(ver)
+            .file("src/lib.rs", "fn other_stuff() {}")
+            .publish();
+        Package::new("shared-dep", ver)
+            .rust_version(ver)
+            .file("src/lib.rs", "fn other_stuff() {}")
+            .publish();
+    }
+    Package::new("higher-only-newer", "1.65.0")
+        .rust_version("1.65.0")
+        .file("src/lib.rs", "fn other_stuff() {}")
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [workspace]
+            members = ["lower"]
+
+            [package]
+            name = "higher"
+            version = "0.0.1"
+            edition = "2015"
+            authors = []
+            rust-version = "1.60.0"
+
+            [dependencies]
+            higher-dep = "1"
+            higher-only-newer = "1"
+            shared-dep = "1"
+        "#,
+        )
+        .file("src/main.rs", "fn main() {}")
+        .file(
+            "lower/Cargo.toml",
+            r#"
+            [package]
+            name = "lower"
+            version = "0.0.1"
+            edition = "2015"
+            authors = []
+            rust-version = "1.50.0"
+
+            [dependencies]
+            shared-dep = "1"
+        "#,
+        )
+        .file("lower/src/main.rs", "fn main() {}")
+        .build();
+
+    // Without the flag, everything is held back to `lower`'s MSRV.
+    p.cargo("generate-lockfile")
+        .env("CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS", "fallback")
+        .run();
+    p.cargo("tree")
+        .with_stdout_data(str![[r#"
+higher v0.0.1 ([ROOT]/foo)
+├── higher-dep v1.45.0
+├── higher-only-newer v1.65.0
+└── shared-dep v1.45.0
+
+"#]])
+        .run();
+
+    p.cargo("generate-lockfile -Zmsrv-per-member")
+        .masquerade_as_nightly_cargo(&["msrv-per-member"])
+        .env("CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS", "fallback")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest Rust 1.50.0 compatible versions
+[ADDING] higher-only-newer v1.65.0 (requires Rust 1.65.0)
+[ADDING] shared-dep v1.45.0 (available: v1.55.0, requires Rust 1.55.0)
+
+"#]])
+        .run();
+    p.cargo("tree -Zmsrv-per-member")
+        .masquerade_as_nightly_cargo(&["msrv-per-member"])
+        .with_stdout_data(str![[r#"
+higher v0.0.1 ([ROOT]/foo)
+├── higher-dep v1.55.0
+├── higher-only-newer v1.65.0 (rust-version 1.65.0 exceeds the MSRV of `higher`)
+└── shared-dep v1.45.0
+
+"#]])
+        .run();
+
+    p.cargo("metadata -q --format-version 1 -Zmsrv-per-member")
+        .masquerade_as_nightly_cargo(&["msrv-per-member"])
+        .with_stdout_data(
+            str![[r#"
+{
+  "resolve": {
+    "nodes": [
+      {
+        "id": "path+[ROOTURL]/foo#higher@0.0.1",
+        "...": "{...}"
+      },
+      {
+        "id": "registry+https://github.com/rust-lang/crates.io-index#higher-dep@1.55.0",
+        "...": "{...}"
+      },
+      {
+        "id": "registry+https://github.com/rust-lang/crates.io-index#higher-only-newer@1.65.0",
+        "rust_version_exceeds": [
+          "path+[ROOTURL]/foo#higher@0.0.1"
+        ],
+        "...": "{...}"
+      },
+      {
+        "id": "path+[ROOTURL]/foo/lower#0.0.1",
+        "...": "{...}"
+      },
+      {
+        "id": "registry+https://github.com/rust-lang/crates.io-index#shared-dep@1.45.0",
+        "...": "{...}"
+      }
+    ],
+    "...": "{...}"
+  },
+  "...": "{...}"
+}
+"#]]
+            .is_json(),
+        )
+        .run();
+}
+
 #[cargo_test(nightly, reason = "edition2024 in rustc is unstable")]
 fn resolve_edition2024() {
     Package::new("only-newer", "1.6.0")
@@ -1,7 +1,12 @@
 //! Tests for configuration values that point to programs.
 
+use std::fs;
+
 use cargo_test_support::prelude::*;
-use cargo_test_support::{basic_lib_manifest, project, rustc_host, rustc_host_env, str};
+use cargo_test_support::{
+    basic_lib_manifest, basic_manifest, cross_compile, project, rustc_host, rustc_host_env, str,
+    tools,
+};
 
 #[cargo_test]
 fn pathless_tools() {
@@ -248,6 +253,112 @@ fn custom_runner() {
         .run();
 }
 
+#[cargo_test]
+fn discovered_cross_linker() {
+    if cross_compile::disabled() || !cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        return;
+    }
+    let target = cross_compile::alternate();
+
+    let p = project()
+        .file("Cargo.toml", &basic_lib_manifest("foo"))
+        .file("src/lib.rs", "")
+        .executable("tools/i686-linux-gnu-gcc", "")
+        .build();
+    let path = std::env::join_paths(std::iter::once(p.root().join("tools")).chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))
+    .unwrap();
+
+    p.cargo("build -v -Zcross-toolchains --target")
+        .arg(target)
+        .masquerade_as_nightly_cargo(&["cross-toolchains"])
+        .env("PATH", &path)
+        .with_stderr_data(str![[r#"
+[COMPILING] foo v0.5.0 ([ROOT]/foo)
+[RUNNING] `rustc [..]-C linker=[ROOT]/foo/tools/i686-linux-gnu-gcc [..]`
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    // An explicitly configured linker always wins.
+    p.cargo("build -v -Zcross-toolchains --target")
+        .arg(target)
+        .arg("--config")
+        .arg(format!("target.{target}.linker='nonexistent-linker'"))
+        .masquerade_as_nightly_cargo(&["cross-toolchains"])
+        .env("PATH", &path)
+        .with_stderr_data(str![[r#"
+[COMPILING] foo v0.5.0 ([ROOT]/foo)
+[RUNNING] `rustc [..]-C linker=nonexistent-linker [..]`
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn discovered_wasi_runtime_args() {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+    // Pretends that `wasm32-wasip1` is installed by building for the host
+    // instead, while reporting `target_os = "wasi"` to Cargo.
+    let fake_rustc = project()
+        .at("fake-rustc")
+        .file("Cargo.toml", &basic_manifest("fake-rustc", "1.0.0"))
+        .file(
+            "src/main.rs",
+            r#"
+                use std::process::Command;
+                fn main() {
+                    let host = std::env::var("FAKE_RUSTC_HOST").unwrap();
+                    let mut args = std::env::args().skip(1);
+                    let rustc = args.next().unwrap();
+                    let args = args.map(|arg| if arg == "wasm32-wasip1" { host.clone() } else { arg });
+                    let output = Command::new(rustc).args(args).output().unwrap();
+                    let stdout = String::from_utf8(output.stdout).unwrap();
+                    print!("{}", stdout.replace("target_os=\"linux\"", "target_os=\"wasi\""));
+                    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                    std::process::exit(output.status.code().unwrap_or(1));
+                }
+            "#,
+        )
+        .build();
+    fake_rustc.cargo("build").run();
+
+    let p = project()
+        .file("src/main.rs", "fn main() {}")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [env]
+                FIXTURES = "fixtures"
+            "#,
+        )
+        .build();
+    // The runtime prints its arguments instead of running the module.
+    let tools = p.root().join("tools");
+    fs::create_dir_all(&tools).unwrap();
+    fs::copy(tools::echo(), tools.join("wasmtime")).unwrap();
+    let path = std::env::join_paths(std::iter::once(tools).chain(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    )))
+    .unwrap();
+
+    p.cargo("run -Zcross-toolchains --target wasm32-wasip1 -- --arg")
+        .masquerade_as_nightly_cargo(&["cross-toolchains"])
+        .env("PATH", &path)
+        .env("RUSTC_WRAPPER", fake_rustc.bin("fake-rustc"))
+        .env("FAKE_RUSTC_HOST", rustc_host())
+        .with_stdout_data(str![[r#"
+run --dir=. --dir=[ROOT]/foo --env CARGO --env CARGO_MANIFEST_DIR --env CARGO_MANIFEST_PATH --env CARGO_PKG_AUTHORS --env CARGO_PKG_DESCRIPTION --env CARGO_PKG_HOMEPAGE --env CARGO_PKG_LICENSE --env CARGO_PKG_LICENSE_FILE --env CARGO_PKG_NAME --env CARGO_PKG_README --env CARGO_PKG_REPOSITORY --env CARGO_PKG_RUST_VERSION --env CARGO_PKG_VERSION --env CARGO_PKG_VERSION_MAJOR --env CARGO_PKG_VERSION_MINOR --env CARGO_PKG_VERSION_PATCH --env CARGO_PKG_VERSION_PRE --env FIXTURES --env LD_LIBRARY_PATH target/wasm32-wasip1/debug/foo --arg
+
+"#]])
+        .run();
+}
+
 // can set a custom runner via `target.'cfg(..)'.runner`
 #[cargo_test]
 fn custom_runner_cfg() {
@@ -350,3 +350,155 @@ foo v0.1.0 ([ROOT]/foo)
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn why_feature() {
+    Package::new("optdep", "1.0.0")
+        .feature("default", &["cat"])
+        .feature("cat", &[])
+        .publish();
+    Package::new("defaultdep", "1.0.0")
+        .add_dep(Dependency::new("optdep", "1.0").optional(true))
+        .feature("default", &["f1"])
+        .feature("f1", &["dep:optdep"])
+        .feature("f2", &["optdep?/cat"])
+        .publish();
+    Package::new("weakdep", "1.0.0")
+        .add_dep(
+            Dependency::new("optdep", "1.0")
+                .optional(true)
+                .default_features(false),
+        )
+        .feature("w", &["optdep/cat"])
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+
+            [dependencies]
+            defaultdep = "1.0"
+            weakdep = { version = "1.0", features = ["w"] }
+
+            [features]
+            default = ["extra"]
+            extra = ["defaultdep/f2"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("tree --why-feature optdep/cat")
+        .masquerade_as_nightly_cargo(&["why-feature"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--why-feature` flag is unstable, pass `-Z why-feature` to enable it
+
+"#]])
+        .run();
+
+    p.cargo("tree -Zwhy-feature --why-feature optdep/cat")
+        .masquerade_as_nightly_cargo(&["why-feature"])
+        .with_stdout_data(
+            str![[r#"
+foo v0.1.0 ([ROOT]/foo) -> defaultdep feature "default" -> defaultdep feature "f1" -> optdep feature "default" -> optdep feature "cat"
+foo v0.1.0 ([ROOT]/foo) -> weakdep feature "w" -> optdep feature "cat"
+foo feature "default" (command-line) -> foo feature "extra" -> defaultdep feature "f2" -> optdep feature "cat"
+
+"#]]
+            .unordered(),
+        )
+        .run();
+
+    p.cargo("tree -Zwhy-feature --why-feature optdep/default --message-format json")
+        .masquerade_as_nightly_cargo(&["why-feature"])
+        .with_stdout_data(
+            str![[r#"
+{
+  "chains": [
+    [
+      {
+        "package": "path+[ROOTURL]/foo#0.1.0"
+      },
+      {
+        "feature": "default",
+        "package": "registry+https://github.com/rust-lang/crates.io-index#defaultdep@1.0.0"
+      },
+      {
+        "feature": "f1",
+        "package": "registry+https://github.com/rust-lang/crates.io-index#defaultdep@1.0.0"
+      },
+      {
+        "feature": "default",
+        "package": "registry+https://github.com/rust-lang/crates.io-index#optdep@1.0.0"
+      }
+    ]
+  ],
+  "feature": "default",
+  "package": "registry+https://github.com/rust-lang/crates.io-index#optdep@1.0.0",
+  "reason": "why-feature"
+}
+"#]]
+            .is_json(),
+        )
+        .run();
+
+    p.cargo("tree -Zwhy-feature --why-feature defaultdep/f2 --no-default-features")
+        .masquerade_as_nightly_cargo(&["why-feature"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `defaultdep/f2` is not enabled
+
+"#]])
+        .run();
+
+    p.cargo("tree -Zwhy-feature --why-feature optdep")
+        .masquerade_as_nightly_cargo(&["why-feature"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `--why-feature` expects a package and feature in the form `<SPEC>/<FEATURE>`, found `optdep`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn why_feature_shortest_chain() {
+    Package::new("optdep", "1.0.0")
+        .feature("cat", &[])
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+
+            [dependencies]
+            optdep = "1.0"
+
+            [features]
+            default = ["a", "extra"]
+            a = ["b"]
+            b = ["extra"]
+            extra = ["optdep/cat"]
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    // `extra` is also enabled through `a` and `b`, but only the shortest
+    // chain is shown.
+    p.cargo("tree -Zwhy-feature --why-feature optdep/cat")
+        .masquerade_as_nightly_cargo(&["why-feature"])
+        .with_stdout_data(str![[r#"
+foo feature "default" (command-line) -> foo feature "extra" -> optdep feature "cat"
+
+"#]])
+        .run();
+}
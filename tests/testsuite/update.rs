@@ -932,6 +932,612 @@ fn dry_run_update() {
     assert_eq!(old_lockfile, new_lockfile)
 }
 
+#[cargo_test]
+fn update_report() {
+    Package::new("log", "0.1.0").publish();
+    Package::new("itoa", "1.0.0").publish();
+    Package::new("dup", "1.0.0").publish();
+    Package::new("serde", "1.0.0").dep("itoa", "1").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["a", "b"]
+            "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                dup = "1"
+                log = "0.1"
+                serde = "1"
+            "#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+                [package]
+                name = "b"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                log = "0.1"
+            "#,
+        )
+        .file("b/src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    let old_lockfile = p.read_lockfile();
+
+    Package::new("log", "0.1.1").publish();
+    Package::new("itoa", "2.0.0").publish();
+    Package::new("dup", "2.0.0").publish();
+    Package::new("serde", "1.1.0")
+        .dep("itoa", "2")
+        .dep("dup", "2")
+        .publish();
+
+    p.cargo("update --report human")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--report` flag is unstable, pass `-Z update-report` to enable it
+
+"#]])
+        .run();
+
+    p.cargo("update --report human -Zupdate-report")
+        .masquerade_as_nightly_cargo(&["update-report"])
+        .with_stdout_data(str![[r#"
+major (1):
+  itoa v1.0.0 -> v2.0.0 (used by a)
+minor (1):
+  serde v1.0.0 -> v1.1.0 (used by a)
+patch (1):
+  log v0.1.0 -> v0.1.1 (used by a, b)
+added (1):
+  dup v2.0.0 (used by a)
+new duplicates (1):
+  dup v1.0.0, v2.0.0
+
+"#]])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+
+"#]])
+        .run();
+
+    p.cargo("update --report json -Zupdate-report")
+        .masquerade_as_nightly_cargo(&["update-report"])
+        .with_stdout_data(
+            str![[r#"
+{
+  "changes": [
+    {
+      "class": "major",
+      "from": "1.0.0",
+      "members": [
+        "a"
+      ],
+      "name": "itoa",
+      "source": "registry+https://github.com/rust-lang/crates.io-index",
+      "to": "2.0.0"
+    },
+    {
+      "class": "minor",
+      "from": "1.0.0",
+      "members": [
+        "a"
+      ],
+      "name": "serde",
+      "source": "registry+https://github.com/rust-lang/crates.io-index",
+      "to": "1.1.0"
+    },
+    {
+      "class": "patch",
+      "from": "0.1.0",
+      "members": [
+        "a",
+        "b"
+      ],
+      "name": "log",
+      "source": "registry+https://github.com/rust-lang/crates.io-index",
+      "to": "0.1.1"
+    },
+    {
+      "class": "added",
+      "from": null,
+      "members": [
+        "a"
+      ],
+      "name": "dup",
+      "source": "registry+https://github.com/rust-lang/crates.io-index",
+      "to": "2.0.0"
+    }
+  ],
+  "duplicates": [
+    {
+      "name": "dup",
+      "versions": [
+        "1.0.0",
+        "2.0.0"
+      ]
+    }
+  ]
+}
+"#]]
+            .is_json(),
+        )
+        .run();
+
+    assert_eq!(old_lockfile, p.read_lockfile());
+}
+
+#[cargo_test]
+fn update_git_branch_movement() {
+    let (git_project, git_repo) = git::new_repo("git", |project| {
+        project
+            .file("Cargo.toml", &basic_lib_manifest("git"))
+            .file("src/lib.rs", "")
+    });
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.0.1"
+                    edition = "2015"
+
+                    [dependencies]
+                    git = {{ git = '{}', branch = 'master' }}
+                "#,
+                git_project.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    let old_head = git_repo.head().unwrap().target().unwrap().to_string();
+
+    git_project.change_file("src/lib.rs", "pub fn f() {}");
+    git::add(&git_repo);
+    let first = git::commit(&git_repo).to_string();
+    git_project.change_file("src/lib.rs", "pub fn g() {}");
+    git::add(&git_repo);
+    let second = git::commit(&git_repo).to_string();
+
+    p.cargo("update --dry-run")
+        .with_stderr_data(format!(
+            "\
+[UPDATING] git repository `[ROOTURL]/git`
+[LOCKING] 1 package to latest compatible version
+[UPDATING] git v0.5.0 ([ROOTURL]/git?branch=master#{}) -> #{} (2 commits ahead)
+             {} test
+             {} test
+[WARNING] not updating lockfile due to dry run
+",
+            &old_head[..8],
+            &second[..8],
+            &second[..8],
+            &first[..8],
+        ))
+        .run();
+    assert!(p.read_lockfile().contains(&old_head));
+
+    p.cargo("update --report json -Zupdate-report")
+        .masquerade_as_nightly_cargo(&["update-report"])
+        .with_stdout_data(
+            format!(
+                r#"
+{{
+  "changes": [
+    {{
+      "class": "revision",
+      "from": "0.5.0",
+      "git": {{
+        "ahead": 2,
+        "commits": [
+          {{
+            "id": "{second}",
+            "summary": "test"
+          }},
+          {{
+            "id": "{first}",
+            "summary": "test"
+          }}
+        ],
+        "from": "{old_head}",
+        "to": "{second}"
+      }},
+      "members": [
+        "foo"
+      ],
+      "name": "git",
+      "source": "git+[ROOTURL]/git?branch=master#{second}",
+      "to": "0.5.0"
+    }}
+  ],
+  "duplicates": []
+}}
+"#
+            )
+            .is_json(),
+        )
+        .run();
+
+    p.cargo("update")
+        .with_stderr_data(format!(
+            "\
+[UPDATING] git repository `[ROOTURL]/git`
+[LOCKING] 1 package to latest compatible version
+[UPDATING] git v0.5.0 ([ROOTURL]/git?branch=master#{}) -> #{}
+             {} test
+             {} test
+",
+            &old_head[..8],
+            &second[..8],
+            &second[..8],
+            &first[..8],
+        ))
+        .run();
+    assert!(p.read_lockfile().contains(&second));
+}
+
+const ADVISORIES: &str = r#"
+    [[advisory]]
+    id = "RUSTSEC-0000-0001"
+    package = "a"
+    vulnerable = ["<1.0.2"]
+"#;
+
+#[cargo_test]
+fn advisory_fix() {
+    for v in ["1.0.0", "1.0.1", "1.0.2"] {
+        Package::new("a", v).publish();
+    }
+    Package::new("b", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = "1"
+                b = "1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("advisories.toml", ADVISORIES)
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    p.cargo("update a --precise 1.0.0").run();
+    Package::new("b", "1.1.0").publish();
+
+    p.cargo("update --advisory-fix advisories.toml")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--advisory-fix` flag is unstable, pass `-Z advisory-fix` to enable it
+
+"#]])
+        .run();
+
+    // `b` has a newer version but isn't affected, so it stays locked.
+    p.cargo("update --advisory-fix advisories.toml -Zadvisory-fix")
+        .masquerade_as_nightly_cargo(&["advisory-fix"])
+        .with_stderr_data(str![[r#"
+[NOTE] a v1.0.0 is affected by RUSTSEC-0000-0001
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[UPDATING] a v1.0.0 -> v1.0.2
+[NOTE] pass `--verbose` to see 1 unchanged dependencies behind latest
+
+"#]])
+        .run();
+
+    p.cargo("update --advisory-fix advisories.toml -Zadvisory-fix")
+        .masquerade_as_nightly_cargo(&["advisory-fix"])
+        .with_stderr_data(str![[r#"
+[NOTE] no locked package is affected by the advisories
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn advisory_fix_unlocks_dependents() {
+    Package::new("a", "1.0.0").publish();
+    Package::new("a", "1.0.2").publish();
+    Package::new("c", "1.0.0").dep("a", "=1.0.0").publish();
+    Package::new("d", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                c = "1"
+                d = "1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("advisories.toml", ADVISORIES)
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    Package::new("c", "1.0.1").dep("a", "1.0.2").publish();
+    Package::new("d", "1.0.1").publish();
+
+    // The locked `c` pins the vulnerable `a`, so `c` has to move too.
+    p.cargo("update --advisory-fix advisories.toml -Zadvisory-fix")
+        .masquerade_as_nightly_cargo(&["advisory-fix"])
+        .with_stderr_data(str![[r#"
+[NOTE] a v1.0.0 is affected by RUSTSEC-0000-0001
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[UPDATING] a v1.0.0 -> v1.0.2
+[UPDATING] c v1.0.0 -> v1.0.1
+[NOTE] pass `--verbose` to see 1 unchanged dependencies behind latest
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn advisory_fix_only_affects_its_registry() {
+    registry::alt_init();
+    Package::new("a", "1.0.0").publish();
+    Package::new("a", "1.0.0").alternative(true).publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = "1"
+                alt-a = { package = "a", version = "1", registry = "alternative" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "advisories.toml",
+            r#"
+                [[advisory]]
+                package = "a"
+                registry = "alternative"
+                vulnerable = ["<1.0.2"]
+            "#,
+        )
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    Package::new("a", "1.0.2").publish();
+    Package::new("a", "1.0.2").alternative(true).publish();
+
+    // Only the `a` from the alternative registry is affected.
+    p.cargo("update --advisory-fix advisories.toml -Zadvisory-fix")
+        .masquerade_as_nightly_cargo(&["advisory-fix"])
+        .with_stderr_data(str![[r#"
+[NOTE] a v1.0.0 (registry `alternative`) is affected by an advisory
+[UPDATING] `alternative` index
+[LOCKING] 1 package to latest compatible version
+[UPDATING] a v1.0.0 (registry `alternative`) -> v1.0.2
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn advisory_fix_requires_manifest_change() {
+    Package::new("a", "1.0.0").publish();
+    Package::new("a", "1.0.2").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = "=1.0.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("advisories.toml", ADVISORIES)
+        .build();
+
+    p.cargo("generate-lockfile").run();
+
+    p.cargo("update --advisory-fix advisories.toml -Zadvisory-fix")
+        .masquerade_as_nightly_cargo(&["advisory-fix"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[NOTE] a v1.0.0 is affected by RUSTSEC-0000-0001
+[UPDATING] `dummy-registry` index
+[ERROR] cannot update `a` to a version outside the advisory ranges without changing a manifest
+
+Caused by:
+  failed to select a version for the requirement `a = "=1.0.0"`
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn publish_before() {
+    Package::new("a", "1.0.0")
+        .pubtime("2024-01-01T00:00:00Z")
+        .publish();
+    Package::new("a", "1.0.1")
+        .pubtime("2024-03-01T00:00:00Z")
+        .publish();
+    Package::new("a", "1.1.0")
+        .pubtime("2024-06-01T00:00:00Z")
+        .publish();
+    // Versions without a publish time are always considered.
+    Package::new("b", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = "1"
+                b = "1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("update --publish-before 2024-04-01")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--publish-before` flag is unstable, pass `-Z publish-time` to enable it
+
+"#]])
+        .run();
+
+    p.cargo("update --publish-before 2024-04-01 -Zpublish-time")
+        .masquerade_as_nightly_cargo(&["publish-time"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[ADDING] a v1.0.1 (available: v1.1.0)
+
+"#]])
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.0.1"
+            edition = "2015"
+
+            [dependencies]
+            a = "1.1"
+            b = "1"
+        "#,
+    );
+    p.cargo("update --publish-before 2024-04-01T00:00:00Z -Zpublish-time")
+        .masquerade_as_nightly_cargo(&["publish-time"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for the requirement `a = "^1.1"`
+candidate versions found which didn't match: 1.1.0, 1.0.1, 1.0.0
+location searched: `dummy-registry` index (which is replacing registry `crates-io`)
+required by package `foo v0.0.1 ([ROOT]/foo)`
+perhaps a crate was updated and forgotten to be re-vendored?
+[NOTE] versions matching the requirement were filtered out for being published after 2024-04-01T00:00:00Z: 1.1.0
+
+"#]])
+        .run();
+
+    p.cargo("update --publish-before yesterday -Zpublish-time")
+        .masquerade_as_nightly_cargo(&["publish-time"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] invalid value `yesterday` for `--publish-before`, expected a date like `2024-03-01` or an RFC 3339 time like `2024-03-01T12:00:00Z`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn min_release_age() {
+    Package::new("a", "1.0.0")
+        .pubtime("2024-01-01T00:00:00Z")
+        .publish();
+    Package::new("a", "1.0.1")
+        .pubtime("2999-01-01T00:00:00Z")
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                a = "1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [resolver]
+                min-release-age = "7 days"
+            "#,
+        )
+        .build();
+
+    p.cargo("generate-lockfile -Zpublish-time")
+        .masquerade_as_nightly_cargo(&["publish-time"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to latest compatible version
+[ADDING] a v1.0.0 (available: v1.0.1)
+
+"#]])
+        .run();
+
+    // The config is ignored without `-Zpublish-time`, but the locked version
+    // is kept.
+    p.cargo("check")
+        .with_stderr_data(str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] a v1.0.0 (registry `dummy-registry`)
+[CHECKING] a v1.0.0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn workspace_only() {
     let p = project().file("src/main.rs", "fn main() {}").build();
@@ -1366,6 +1972,7 @@ fn update_precise_git_revisions() {
             "\
 [UPDATING] git repository `[ROOTURL]/git`
 [UPDATING] git v0.5.0 ([ROOTURL]/git[..]) -> #{short_id}
+             {short_id} test
 ",
         ))
         .run();
@@ -1399,7 +2006,8 @@ fn update_precise_git_revisions() {
         .with_stderr_data(format!(
             "\
 [UPDATING] git repository `[ROOTURL]/git`
-[UPDATING] git v0.5.0 ([ROOTURL]/git#[..]) -> #{}
+[UPDATING] git v0.5.0 ([ROOTURL]/git#[..]) -> #{0}
+             {0} test
 ",
             &head_id[..8],
         ))
@@ -2709,3 +3317,125 @@ fn update_breaking_pre_release_upgrade() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn update_minimal_versions() {
+    Package::new("bar", "1.0.0").publish();
+    Package::new("bar", "1.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    Package::new("baz", "0.1.1").dep("bar", "1.1").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "1"
+                baz = "0.1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile").run();
+    let lockfile = p.read_lockfile();
+
+    p.cargo("update --minimal-versions")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to earliest compatible versions
+...
+"#]])
+        .run();
+
+    // `Cargo.lock` is left alone.
+    assert_eq!(p.read_lockfile(), lockfile);
+    let minimal = p.read_file("Cargo.minimal.lock");
+    assert!(minimal.contains("name = \"bar\"\nversion = \"1.0.0\""));
+    assert!(minimal.contains("name = \"baz\"\nversion = \"0.1.0\""));
+
+    // A conservative update within the minimal lock file.
+    p.cargo("update --minimal-versions baz --precise 0.1.1")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+...
+[UPDATING] bar v1.0.0 -> v1.1.0
+[UPDATING] baz v0.1.0 -> v0.1.1
+...
+"#]])
+        .run();
+    assert_eq!(p.read_lockfile(), lockfile);
+    let minimal = p.read_file("Cargo.minimal.lock");
+    assert!(minimal.contains("name = \"bar\"\nversion = \"1.1.0\""));
+    assert!(minimal.contains("name = \"baz\"\nversion = \"0.1.1\""));
+}
+
+#[cargo_test]
+fn check_with_minimal_versions_lockfile() {
+    Package::new("bar", "1.0.0").publish();
+    Package::new("bar", "1.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "1"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("update --minimal-versions").run();
+    assert!(!p.root().join("Cargo.lock").exists());
+
+    p.cargo("check --locked --config resolver.minimal-versions=true")
+        .with_stderr_data(str![[r#"
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v1.0.0 (registry `dummy-registry`)
+[CHECKING] bar v1.0.0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    assert!(!p.root().join("Cargo.lock").exists());
+
+    // Adding a dependency re-resolves the minimal lock file with minimal
+    // versions when asked to.
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.0.1"
+            edition = "2015"
+
+            [dependencies]
+            bar = "1"
+            baz = "0.1"
+        "#,
+    );
+    Package::new("baz", "0.1.0").publish();
+    Package::new("baz", "0.1.1").publish();
+    p.cargo("check --config resolver.minimal-versions=true")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 1 package to earliest compatible version
+...
+[CHECKING] baz v0.1.0
+...
+"#]])
+        .run();
+    assert!(!p.root().join("Cargo.lock").exists());
+}
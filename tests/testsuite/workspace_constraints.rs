@@ -0,0 +1,212 @@
+//! Tests for `[workspace.constraints]`.
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{project, str, Project};
+
+/// Publishes `bar`, which depends on `baz = "0.3"`, and `baz` 0.3.34 and
+/// 0.3.35.
+fn publish_packages() {
+    Package::new("baz", "0.3.34").publish();
+    Package::new("baz", "0.3.35").publish();
+    Package::new("bar", "1.0.0").dep("baz", "0.3").publish();
+}
+
+fn constrained_project(constraint: &str) -> Project {
+    project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    cargo-features = ["workspace-constraints"]
+
+                    [workspace]
+
+                    [workspace.constraints]
+                    baz = "{constraint}"
+
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    bar = "1"
+                "#
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build()
+}
+
+#[cargo_test]
+fn constraints_require_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+
+                [workspace.constraints]
+                baz = "!=0.3.35"
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  feature `workspace-constraints` is required
+
+  The package requires the Cargo feature called `workspace-constraints`, but that feature is not stabilized in this version of Cargo ([..]).
+  Consider trying a newer version of Cargo (this may require the nightly release).
+  See https://doc.rust-lang.org/nightly/cargo/reference/unstable.html#workspace-constraints for more information about the status of this feature.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn invalid_constraint() {
+    let p = constrained_project("!=0.3");
+
+    p.cargo("check")
+        .masquerade_as_nightly_cargo(&["workspace-constraints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  failed to parse the version constraint `!=0.3` for `workspace.constraints.baz`
+
+Caused by:
+  unexpected end of input while parsing minor version number
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn constraint_on_transitive_dependency() {
+    publish_packages();
+    let p = constrained_project("!=0.3.35");
+
+    p.cargo("tree -i baz")
+        .masquerade_as_nightly_cargo(&["workspace-constraints"])
+        .with_stdout_data(str![[r#"
+baz v0.3.34
+├── [workspace.constraints] baz = "!=0.3.35"
+└── bar v1.0.0
+    └── foo v0.1.0 ([ROOT]/foo)
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn constraint_relocks_disallowed_versions() {
+    publish_packages();
+    let p = constrained_project("*");
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo(&["workspace-constraints"])
+        .run();
+    p.cargo("tree -i baz")
+        .masquerade_as_nightly_cargo(&["workspace-constraints"])
+        .with_stdout_data(str![[r#"
+baz v0.3.35
+├── [workspace.constraints] baz = "*"
+└── bar v1.0.0
+    └── foo v0.1.0 ([ROOT]/foo)
+
+"#]])
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            cargo-features = ["workspace-constraints"]
+
+            [workspace]
+
+            [workspace.constraints]
+            baz = "<0.3.35"
+
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            edition = "2015"
+
+            [dependencies]
+            bar = "1"
+        "#,
+    );
+    p.cargo("tree -i baz")
+        .masquerade_as_nightly_cargo(&["workspace-constraints"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 1 package to latest compatible version
+[DOWNGRADING] baz v0.3.35 -> v0.3.34 (available: v0.3.35)
+[DOWNLOADING] crates ...
+[DOWNLOADED] baz v0.3.34 (registry `dummy-registry`)
+
+"#]])
+        .with_stdout_data(str![[r#"
+baz v0.3.34
+├── [workspace.constraints] baz = "<0.3.35"
+└── bar v1.0.0
+    └── foo v0.1.0 ([ROOT]/foo)
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn constraint_violation_is_explained() {
+    publish_packages();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                cargo-features = ["workspace-constraints"]
+
+                [workspace]
+
+                [workspace.constraints]
+                baz = "<0.3"
+
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                baz = "0.3"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("generate-lockfile")
+        .masquerade_as_nightly_cargo(&["workspace-constraints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[ERROR] failed to select a version for the requirement `baz = "^0.3"`
+candidate versions found which didn't match: 0.3.35, 0.3.34
+location searched: `dummy-registry` index (which is replacing registry `crates-io`)
+required by package `foo v0.1.0 ([ROOT]/foo)`
+perhaps a crate was updated and forgotten to be re-vendored?
+[NOTE] versions matching the requirement are excluded by `baz = "<0.3"` in `[workspace.constraints]`: 0.3.35, 0.3.34
+
+"#]])
+        .run();
+}